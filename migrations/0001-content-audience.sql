@@ -0,0 +1,6 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD groups ON TABLE schemas TYPE option<array<string>>;
+DEFINE FIELD groups ON TABLE singles TYPE option<array<string>>;
+
+COMMIT TRANSACTION;
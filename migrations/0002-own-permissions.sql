@@ -0,0 +1,30 @@
+BEGIN TRANSACTION;
+
+CREATE permissions CONTENT {
+    id: 'content_write_own',
+    slug: 'content::write_own'
+};
+CREATE permissions CONTENT {
+    id: 'content_delete_own',
+    slug: 'content::delete_own'
+};
+
+RELATE roles:administrator->role_permissions->permissions:content_write_own;
+RELATE roles:administrator->role_permissions->permissions:content_delete_own;
+
+FOR $schema IN (SELECT VALUE slug FROM schemas WHERE is_system = false AND is_public = false) {
+    LET $write_own = type::thing('permissions', string::concat($schema, '_write_own'));
+    LET $delete_own = type::thing('permissions', string::concat($schema, '_delete_own'));
+
+    CREATE $write_own CONTENT {
+        slug: string::concat($schema, '::write_own')
+    };
+    CREATE $delete_own CONTENT {
+        slug: string::concat($schema, '::delete_own')
+    };
+
+    RELATE roles:administrator->role_permissions->$write_own;
+    RELATE roles:administrator->role_permissions->$delete_own;
+};
+
+COMMIT TRANSACTION;
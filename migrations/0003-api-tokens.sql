@@ -0,0 +1,24 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE tokens SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'tokens',
+    title: 'Tokens',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD login ON TABLE tokens TYPE string;
+DEFINE FIELD title ON TABLE tokens TYPE string;
+DEFINE FIELD hash ON TABLE tokens TYPE string;
+DEFINE FIELD scope ON TABLE tokens TYPE array<string>;
+DEFINE FIELD expires_at ON TABLE tokens TYPE option<datetime>;
+DEFINE FIELD last_used_at ON TABLE tokens TYPE option<datetime>;
+DEFINE FIELD last_used_ip ON TABLE tokens TYPE option<string>;
+DEFINE FIELD created_at ON TABLE tokens TYPE datetime DEFAULT time::now();
+DEFINE INDEX idx_tokens_hash ON TABLE tokens COLUMNS hash UNIQUE;
+DEFINE INDEX idx_tokens_login ON TABLE tokens COLUMNS login;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,12 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD totp_secret ON TABLE users TYPE option<string>;
+DEFINE FIELD totp_enabled ON TABLE users TYPE bool DEFAULT false;
+DEFINE FIELD totp_recovery ON TABLE users TYPE array<string> DEFAULT [];
+
+DEFINE FIELD two_factor ON TABLE roles TYPE bool DEFAULT false;
+
+UPDATE users SET totp_enabled = false, totp_recovery = [] WHERE totp_enabled = NONE;
+UPDATE roles SET two_factor = false WHERE two_factor = NONE;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,23 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD failed_count ON TABLE users TYPE int DEFAULT 0;
+DEFINE FIELD failed_last ON TABLE users TYPE option<datetime>;
+DEFINE FIELD locked_until ON TABLE users TYPE option<datetime>;
+
+UPDATE users SET failed_count = 0 WHERE failed_count = NONE;
+
+DEFINE TABLE sign_in_attempts SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'sign_in_attempts',
+    title: 'Sign in attempts',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD failed_count ON TABLE sign_in_attempts TYPE int DEFAULT 0;
+DEFINE FIELD failed_last ON TABLE sign_in_attempts TYPE option<datetime>;
+DEFINE FIELD locked_until ON TABLE sign_in_attempts TYPE option<datetime>;
+
+COMMIT TRANSACTION;
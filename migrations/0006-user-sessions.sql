@@ -0,0 +1,20 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE user_sessions SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'user_sessions',
+    title: 'User sessions',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD login ON TABLE user_sessions TYPE string;
+DEFINE FIELD ip ON TABLE user_sessions TYPE string;
+DEFINE FIELD user_agent ON TABLE user_sessions TYPE string;
+DEFINE FIELD created_at ON TABLE user_sessions TYPE datetime DEFAULT time::now();
+DEFINE FIELD last_seen ON TABLE user_sessions TYPE datetime DEFAULT time::now();
+DEFINE INDEX idx_user_sessions_login ON TABLE user_sessions COLUMNS login;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,8 @@
+BEGIN TRANSACTION;
+
+CREATE mtc_system CONTENT {
+    c_key: 'auth_epoch',
+    c_value: 0
+};
+
+COMMIT TRANSACTION;
@@ -0,0 +1,35 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE audit_log SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'audit_log',
+    title: 'Audit log',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD actor ON TABLE audit_log TYPE string;
+DEFINE FIELD action ON TABLE audit_log TYPE string;
+DEFINE FIELD target ON TABLE audit_log TYPE string;
+DEFINE FIELD before ON TABLE audit_log TYPE option<string>;
+DEFINE FIELD after ON TABLE audit_log TYPE option<string>;
+DEFINE FIELD ip ON TABLE audit_log TYPE string;
+DEFINE FIELD created_at ON TABLE audit_log TYPE datetime DEFAULT time::now();
+DEFINE INDEX idx_audit_log_actor ON TABLE audit_log COLUMNS actor;
+DEFINE INDEX idx_audit_log_created_at ON TABLE audit_log COLUMNS created_at;
+
+-- entries are only ever appended, the retention job is the only one removing them
+DEFINE EVENT audit_log_append_only ON TABLE audit_log WHEN $event = "UPDATE" THEN {
+    THROW "audit log entries can't be changed";
+};
+
+CREATE permissions CONTENT {
+    id: 'audit_read',
+    slug: 'audit::read'
+};
+
+RELATE roles:administrator->role_permissions->permissions:audit_read;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,8 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD must_change_password ON TABLE users TYPE bool DEFAULT false;
+DEFINE FIELD password_changed_at ON TABLE users TYPE option<datetime>;
+
+UPDATE users SET must_change_password = false, password_changed_at = time::now() WHERE must_change_password = NONE;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,8 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD valid_from ON TABLE users TYPE option<datetime>;
+DEFINE FIELD valid_until ON TABLE users TYPE option<datetime>;
+
+DEFINE INDEX idx_users_valid_until ON TABLE users COLUMNS valid_until;
+
+COMMIT TRANSACTION;
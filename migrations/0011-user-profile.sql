@@ -0,0 +1,8 @@
+BEGIN TRANSACTION;
+
+UPDATE schemas SET fields = [
+    { id: 'rank', slug: 'rank', title: 'Rank', type: 'Str' },
+    { id: 'name', slug: 'name', title: 'Name', type: 'Str' }
+] WHERE slug = 'users' AND (fields = NONE OR fields = []);
+
+COMMIT TRANSACTION;
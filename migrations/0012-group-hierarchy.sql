@@ -0,0 +1,13 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD parent ON TABLE groups TYPE option<string>;
+DEFINE INDEX idx_groups_parent ON TABLE groups COLUMNS parent;
+
+CREATE permissions CONTENT {
+    id: 'group_descendants',
+    slug: 'group::descendants'
+};
+
+RELATE roles:administrator->role_permissions->permissions:group_descendants;
+
+COMMIT TRANSACTION;
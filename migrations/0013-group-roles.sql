@@ -0,0 +1,45 @@
+BEGIN TRANSACTION;
+
+REMOVE TABLE IF EXISTS group_roles;
+DEFINE TABLE group_roles SCHEMAFULL TYPE RELATION IN groups OUT roles;
+
+CREATE schemas CONTENT {
+    slug: 'group_roles',
+    title: 'Group roles',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD created_at ON TABLE group_roles TYPE datetime VALUE time::now();
+DEFINE INDEX idx_group_roles ON TABLE group_roles COLUMNS in, out UNIQUE;
+
+-- the given groups together with all of their parents
+DEFINE FUNCTION fn::group_lineage($slugs: array<string>) {
+    LET $parents = array::distinct((SELECT VALUE parent FROM groups WHERE slug IN $slugs AND parent != NONE));
+    LET $added = array::complement($parents, $slugs);
+
+    RETURN IF array::len($added) = 0 {
+        $slugs
+    } ELSE {
+        fn::group_lineage(array::union($slugs, $added))
+    };
+};
+
+-- roles assigned to the user directly
+DEFINE FUNCTION fn::user_direct_roles($login: string) {
+    RETURN array::distinct(array::flatten((SELECT VALUE ->user_roles->roles FROM users WHERE login = $login)));
+};
+
+-- roles the user gets from the groups, including the parents of the groups
+DEFINE FUNCTION fn::user_inherited_roles($login: string) {
+    LET $groups = fn::group_lineage(array::flatten((SELECT VALUE ->user_groups->groups.slug FROM users WHERE login = $login)));
+
+    RETURN array::distinct(array::flatten((SELECT VALUE ->group_roles->roles FROM groups WHERE slug IN $groups)));
+};
+
+DEFINE FUNCTION fn::user_roles($login: string) {
+    RETURN array::union(fn::user_direct_roles($login), fn::user_inherited_roles($login));
+};
+
+COMMIT TRANSACTION;
@@ -0,0 +1,31 @@
+BEGIN TRANSACTION;
+
+-- a single '*' segment of the rule stands for one or more segments of the permission
+DEFINE FUNCTION fn::permission_match($rule: string, $permission: string) {
+    LET $parts = string::split($rule, '*');
+
+    RETURN IF array::len($parts) = 1 {
+        $rule = $permission
+    } ELSE {
+        string::len($permission) > string::len($parts[0]) + string::len($parts[1])
+            AND string::starts_with($permission, $parts[0])
+            AND string::ends_with($permission, $parts[1])
+    };
+};
+
+-- concrete permissions the user ends up with, rules starting with '!' deny what they match
+DEFINE FUNCTION fn::user_permissions($login: string) {
+    LET $rules = array::distinct(array::flatten((SELECT VALUE ->role_permissions->permissions.slug FROM fn::user_roles($login))));
+    LET $allow = (SELECT VALUE $this FROM $rules WHERE !string::starts_with($this, '!'));
+    LET $deny = (SELECT VALUE string::slice($this, 1) FROM $rules WHERE string::starts_with($this, '!'));
+
+    RETURN array::sort((
+        SELECT VALUE slug FROM permissions
+        WHERE !string::contains(slug, '*')
+            AND !string::starts_with(slug, '!')
+            AND array::any((SELECT VALUE fn::permission_match($this, $parent.slug) FROM $allow))
+            AND !array::any((SELECT VALUE fn::permission_match($this, $parent.slug) FROM $deny))
+    ));
+};
+
+COMMIT TRANSACTION;
@@ -0,0 +1,17 @@
+BEGIN TRANSACTION;
+
+REMOVE TABLE IF EXISTS group_managers;
+DEFINE TABLE group_managers SCHEMAFULL TYPE RELATION IN users OUT groups;
+
+CREATE schemas CONTENT {
+    slug: 'group_managers',
+    title: 'Group managers',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD created_at ON TABLE group_managers TYPE datetime VALUE time::now();
+DEFINE INDEX idx_group_managers ON TABLE group_managers COLUMNS in, out UNIQUE;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,10 @@
+BEGIN TRANSACTION;
+
+CREATE permissions CONTENT {
+    id: 'user_impersonate',
+    slug: 'user::impersonate'
+};
+
+RELATE roles:administrator->role_permissions->permissions:user_impersonate;
+
+COMMIT TRANSACTION;
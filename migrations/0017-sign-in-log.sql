@@ -0,0 +1,27 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE sign_in_log SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'sign_in_log',
+    title: 'Sign-in log',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD login ON TABLE sign_in_log TYPE string;
+DEFINE FIELD success ON TABLE sign_in_log TYPE bool;
+DEFINE FIELD reason ON TABLE sign_in_log TYPE option<string>;
+DEFINE FIELD ip ON TABLE sign_in_log TYPE string;
+DEFINE FIELD user_agent ON TABLE sign_in_log TYPE string;
+DEFINE FIELD created_at ON TABLE sign_in_log TYPE datetime DEFAULT time::now();
+DEFINE INDEX idx_sign_in_log_login ON TABLE sign_in_log COLUMNS login;
+DEFINE INDEX idx_sign_in_log_created_at ON TABLE sign_in_log COLUMNS created_at;
+
+-- attempts are only ever appended, the retention job is the only one removing them
+DEFINE EVENT sign_in_log_append_only ON TABLE sign_in_log WHEN $event = "UPDATE" THEN {
+    THROW "sign-in log entries can't be changed";
+};
+
+COMMIT TRANSACTION;
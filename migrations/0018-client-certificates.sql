@@ -0,0 +1,26 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE user_certificates SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'user_certificates',
+    title: 'User certificates',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD login ON TABLE user_certificates TYPE string;
+DEFINE FIELD kind ON TABLE user_certificates TYPE string ASSERT $value IN ['subject', 'serial'];
+DEFINE FIELD value ON TABLE user_certificates TYPE string;
+DEFINE FIELD created_at ON TABLE user_certificates TYPE datetime DEFAULT time::now();
+DEFINE FIELD created_by ON TABLE user_certificates TYPE string;
+DEFINE INDEX idx_user_certificates_value ON TABLE user_certificates COLUMNS kind, value UNIQUE;
+DEFINE INDEX idx_user_certificates_login ON TABLE user_certificates COLUMNS login;
+
+-- a certificate must never lead to an account created later under the same login
+DEFINE EVENT user_certificates_cleanup ON TABLE users WHEN $event = "DELETE" THEN {
+    DELETE user_certificates WHERE login = $before.login;
+};
+
+COMMIT TRANSACTION;
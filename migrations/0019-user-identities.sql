@@ -0,0 +1,25 @@
+BEGIN TRANSACTION;
+
+DEFINE TABLE user_identities SCHEMAFULL;
+
+CREATE schemas CONTENT {
+    slug: 'user_identities',
+    title: 'User identities',
+    is_system: true,
+    created_by: $login,
+    updated_by: $login
+};
+
+DEFINE FIELD login ON TABLE user_identities TYPE string;
+DEFINE FIELD issuer ON TABLE user_identities TYPE string;
+DEFINE FIELD subject ON TABLE user_identities TYPE string;
+DEFINE FIELD created_at ON TABLE user_identities TYPE datetime DEFAULT time::now();
+DEFINE INDEX idx_user_identities_subject ON TABLE user_identities COLUMNS issuer, subject UNIQUE;
+DEFINE INDEX idx_user_identities_login ON TABLE user_identities COLUMNS login;
+
+-- an identity provider account must never lead to an account created later under the same login
+DEFINE EVENT user_identities_cleanup ON TABLE users WHEN $event = "DELETE" THEN {
+    DELETE user_identities WHERE login = $before.login;
+};
+
+COMMIT TRANSACTION;
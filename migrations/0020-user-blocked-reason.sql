@@ -0,0 +1,8 @@
+BEGIN TRANSACTION;
+
+DEFINE FIELD blocked_reason ON TABLE users TYPE option<string>;
+
+-- accounts the expiry job blocked before the reason was recorded
+UPDATE users SET blocked_reason = 'expired' WHERE blocked = true AND valid_until != NONE AND valid_until < time::now();
+
+COMMIT TRANSACTION;
@@ -0,0 +1,11 @@
+BEGIN TRANSACTION;
+
+-- tokens left behind by users deleted before the cleanup existed
+DELETE tokens WHERE login NOTINSIDE (SELECT VALUE login FROM users);
+
+-- a token must never lead to an account created later under the same login
+DEFINE EVENT tokens_cleanup ON TABLE users WHEN $event = "DELETE" THEN {
+    DELETE tokens WHERE login = $before.login;
+};
+
+COMMIT TRANSACTION;
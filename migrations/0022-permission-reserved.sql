@@ -0,0 +1,45 @@
+BEGIN TRANSACTION;
+
+-- a bare '*' is not a rule anymore, it would hand out every permission there is
+DELETE FROM permissions WHERE slug INSIDE ['*', '!*'] AND is_custom = true;
+
+-- a single '*' segment of the rule stands for one or more segments of the permission
+DEFINE FUNCTION fn::rule_match($rule: string, $permission: string) {
+    LET $parts = string::split($rule, '*');
+
+    RETURN IF array::len($parts) = 1 {
+        $rule = $permission
+    } ELSE {
+        string::len($permission) > string::len($parts[0]) + string::len($parts[1])
+            AND string::starts_with($permission, $parts[0])
+            AND string::ends_with($permission, $parts[1])
+    };
+};
+
+-- a wildcard never grants the administrator or the access management scopes, see RESERVED_PERMISSION_SCOPES
+DEFINE FUNCTION fn::permission_match($rule: string, $permission: string) {
+    LET $reserved = string::split($permission, '::')[0] INSIDE ['administrator', 'user', 'role', 'group', 'schema', 'audit'];
+
+    RETURN IF string::contains($rule, '*') AND $reserved {
+        false
+    } ELSE {
+        fn::rule_match($rule, $permission)
+    };
+};
+
+-- denying rules still reach the reserved permissions
+DEFINE FUNCTION fn::user_permissions($login: string) {
+    LET $rules = array::distinct(array::flatten((SELECT VALUE ->role_permissions->permissions.slug FROM fn::user_roles($login))));
+    LET $allow = (SELECT VALUE $this FROM $rules WHERE !string::starts_with($this, '!'));
+    LET $deny = (SELECT VALUE string::slice($this, 1) FROM $rules WHERE string::starts_with($this, '!'));
+
+    RETURN array::sort((
+        SELECT VALUE slug FROM permissions
+        WHERE !string::contains(slug, '*')
+            AND !string::starts_with(slug, '!')
+            AND array::any((SELECT VALUE fn::permission_match($this, $parent.slug) FROM $allow))
+            AND !array::any((SELECT VALUE fn::rule_match($this, $parent.slug) FROM $deny))
+    ));
+};
+
+COMMIT TRANSACTION;
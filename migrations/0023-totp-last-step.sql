@@ -0,0 +1,6 @@
+BEGIN TRANSACTION;
+
+-- time step of the last accepted code, a code can't be used again within its validity window
+DEFINE FIELD totp_last_step ON TABLE users TYPE option<int>;
+
+COMMIT TRANSACTION;
@@ -0,0 +1,18 @@
+BEGIN TRANSACTION;
+
+-- public schemas are read by everybody, but their own items are still written per schema
+FOR $schema IN (SELECT VALUE slug FROM schemas WHERE is_system = false AND is_public = true) {
+    FOR $action IN ['write_own', 'delete_own'] {
+        LET $permission = type::thing('permissions', string::concat($schema, '_', $action));
+
+        IF array::len((SELECT VALUE id FROM $permission)) = 0 {
+            CREATE $permission CONTENT {
+                slug: string::concat($schema, '::', $action)
+            };
+
+            RELATE roles:administrator->role_permissions->$permission;
+        };
+    };
+};
+
+COMMIT TRANSACTION;
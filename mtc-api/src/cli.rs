@@ -0,0 +1,39 @@
+use std::error::Error;
+
+use tokio::fs;
+use tracing::log::info;
+
+use mtc_model::bundle_model::SchemaBundleModel;
+
+use crate::service::bundle_service::SchemaBundleTrait;
+use crate::state::AppState;
+
+static CLI_USAGE: &str = r#"usage:
+    mtc-api schema export <file> [slug ...]
+    mtc-api schema import <file> [--dry-run]"#;
+
+pub async fn cli(state: &AppState, args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        ["schema", "export", file, slugs @ ..] => {
+            let bundle = state
+                .bundle_export(&slugs.iter().map(|slug| slug.to_string()).collect::<Vec<String>>())
+                .await?;
+            fs::write(file, serde_json::to_string_pretty(&bundle)?).await?;
+
+            info!("\x1b[38;5;6mExported {} schemas to \x1b[38;5;13m{file}\x1b[0m", bundle.schemas.len());
+        }
+        ["schema", "import", file, options @ ..] => {
+            let bundle =
+                serde_json::from_str::<SchemaBundleModel>(&fs::read_to_string(file).await?)?;
+            let diff = match options.contains(&"--dry-run") {
+                true => state.bundle_diff(&bundle).await?,
+                false => state.bundle_import("system", bundle).await?,
+            };
+
+            info!("\x1b[38;5;6mSchema bundle changes:\x1b[0m {}", serde_json::to_string_pretty(&diff)?);
+        }
+        _ => Err(CLI_USAGE)?,
+    }
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde_json::json;
 use thiserror::Error;
 
+use mtc_model::schema_model::FieldMigrationItemModel;
+
 use crate::model::response_model::ApiErrorResponse;
 
 #[derive(Error, Debug)]
@@ -16,6 +19,10 @@ pub enum DbError {
     EntryUpdate,
     #[error("errors.not_deleted")]
     EntryDelete,
+    #[error("errors.group_cycle")]
+    GroupCycle,
+    #[error("errors.field_migration")]
+    FieldMigration(Vec<FieldMigrationItemModel>),
 }
 
 impl IntoResponse for DbError {
@@ -25,9 +32,18 @@ impl IntoResponse for DbError {
             DbError::EntryAlreadyExists
             | DbError::EntryNotFound
             | DbError::EntryUpdate
-            | DbError::EntryDelete => StatusCode::CONFLICT,
+            | DbError::EntryDelete
+            | DbError::GroupCycle
+            | DbError::FieldMigration(_) => StatusCode::CONFLICT,
         };
 
-        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+        match self {
+            DbError::FieldMigration(ref items) => ApiErrorResponse::send_details(
+                status_code.as_u16(),
+                Some(self.to_string()),
+                json!(items),
+            ),
+            _ => ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string())),
+        }
     }
 }
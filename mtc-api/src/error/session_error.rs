@@ -16,16 +16,64 @@ pub enum SessionError {
     UserBlocked,
     #[error("errors.password_hash")]
     PasswordHash,
+    #[error("errors.password_length")]
+    PasswordLength,
+    #[error("errors.password_classes")]
+    PasswordClasses,
+    #[error("errors.password_common")]
+    PasswordCommon,
+    #[error("errors.password_unchanged")]
+    PasswordUnchanged,
+    #[error("errors.two_factor_required")]
+    TwoFactorRequired,
+    #[error("errors.two_factor_enrollment")]
+    TwoFactorEnrollment,
+    #[error("errors.two_factor_secret")]
+    TwoFactorSecret,
+    #[error("errors.account_locked")]
+    AccountLocked,
+    #[error("errors.too_many_attempts")]
+    TooManyAttempts,
+    #[error("errors.password_change_required")]
+    PasswordChangeRequired,
+    #[error("errors.account_inactive")]
+    AccountInactive,
+    #[error("errors.impersonation_read_only")]
+    ImpersonationReadOnly,
+    #[error("errors.certificate_required")]
+    CertificateRequired,
+    #[error("errors.certificate_password_required")]
+    CertificatePasswordRequired,
+    #[error("errors.identity_not_linked")]
+    IdentityNotLinked,
+    #[error("errors.identity_provider")]
+    IdentityProvider,
 }
 
 impl IntoResponse for SessionError {
     fn into_response(self) -> Response {
         let status_code = match self {
             SessionError::InvalidSession
-            | SessionError::InvalidCredentials => StatusCode::UNAUTHORIZED,
-            SessionError::PasswordHash => StatusCode::INTERNAL_SERVER_ERROR,
+            | SessionError::InvalidCredentials
+            | SessionError::TwoFactorRequired
+            | SessionError::TwoFactorEnrollment
+            | SessionError::CertificateRequired
+            | SessionError::CertificatePasswordRequired
+            | SessionError::IdentityNotLinked => StatusCode::UNAUTHORIZED,
+            SessionError::PasswordHash
+            | SessionError::TwoFactorSecret => StatusCode::INTERNAL_SERVER_ERROR,
             SessionError::UserBlocked
-            | SessionError::AccessForbidden => StatusCode::FORBIDDEN,
+            | SessionError::AccessForbidden
+            | SessionError::PasswordChangeRequired
+            | SessionError::AccountInactive
+            | SessionError::ImpersonationReadOnly => StatusCode::FORBIDDEN,
+            SessionError::PasswordLength
+            | SessionError::PasswordClasses
+            | SessionError::PasswordCommon
+            | SessionError::PasswordUnchanged => StatusCode::BAD_REQUEST,
+            SessionError::AccountLocked => StatusCode::LOCKED,
+            SessionError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+            SessionError::IdentityProvider => StatusCode::BAD_GATEWAY,
         };
 
         ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
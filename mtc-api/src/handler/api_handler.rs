@@ -5,14 +5,17 @@ use tower_sessions::Session;
 
 use mtc_model::api_model::{ApiListItemModel, ApiModel, ApiPostModel};
 use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
+use mtc_model::schema_model::SchemaModel;
 
 use crate::error::api_error::ToApiError;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::{ApiPageRequest, ValidatedPayload};
-use crate::model::response_model::HandlerResult;
+use crate::model::access_model::AudienceModel;
+use crate::model::request_model::{ApiPageRequest, ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::api_repository::ApiRepositoryTrait;
 use crate::repository::schema_repository::SchemaRepositoryTrait;
+use crate::service::audit_service::{audit_summary, AuditTrait};
 use crate::service::storage_service::StorageTrait;
 use crate::state::AppState;
 
@@ -34,17 +37,17 @@ pub async fn api_collection_list_handler(
             .permission(&[api.as_str(), "::read"].concat())
             .await?;
     }
-    let is_admin = session.is_admin().await?;
+    let audience = session.audience().await?;
 
     let pagination = PaginationModel::new(
-        state.api_service.get_total(&api, is_admin).await?,
+        state.api_service.get_total(&api, &audience).await?,
         state.cfg.rows_per_page,
     )
     .page(page);
 
     state
         .api_service
-        .get_page(&api, pagination.from, pagination.per_page, is_admin)
+        .get_page(&api, pagination.from, pagination.per_page, &audience)
         .await?
         .ok_page(pagination)
 }
@@ -67,7 +70,7 @@ pub async fn api_get_single_handler(
 
     state
         .api_service
-        .find_by_slug("singles", &api)
+        .find_by_slug("singles", &api, &session.audience().await?)
         .await?
         .ok_model()
 }
@@ -76,6 +79,7 @@ pub async fn api_update_single_item_handler(
     Path(api): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<ApiPostModel>,
 ) -> Result<ApiModel> {
     let schema_model = state.schema_service.find_by_slug(&api).await?;
@@ -92,7 +96,20 @@ pub async fn api_update_single_item_handler(
         Err("Isn't a single type api end-point".to_bad_request_error())?
     }
 
-    state
+    let before = state
+        .api_service
+        .find_by_slug(
+            "singles",
+            &schema_model.slug,
+            &AudienceModel {
+                groups: vec![],
+                all: true,
+            },
+        )
+        .await
+        .ok();
+
+    let api_model = state
         .api_service
         .update(
             &session.auth_id().await?,
@@ -100,8 +117,20 @@ pub async fn api_update_single_item_handler(
             &schema_model.slug,
             payload,
         )
-        .await?
-        .ok_model()
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "content::update",
+            &schema_model.slug,
+            before.and_then(|value| audit_summary(&value)),
+            audit_summary(&api_model),
+        )
+        .await;
+
+    api_model.ok_model()
 }
 
 pub async fn api_get_collection_item_handler(
@@ -123,7 +152,7 @@ pub async fn api_get_collection_item_handler(
 
     state
         .api_service
-        .find_by_slug(&schema_model.slug, &slug)
+        .find_by_slug(&schema_model.slug, &slug, &session.audience().await?)
         .await?
         .ok_model()
 }
@@ -132,6 +161,7 @@ pub async fn api_create_collection_item_handler(
     Path((api, slug)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<ApiPostModel>,
 ) -> Result<ApiModel> {
     let schema_model = state.schema_service.find_by_slug(&api).await?;
@@ -140,18 +170,17 @@ pub async fn api_create_collection_item_handler(
         Err("Isn't a collection type api end-point".to_bad_request_error())?
     }
 
-    if schema_model.is_public {
-        session.permission("content::write").await?;
-    } else {
-        session
-            .permission(&[api.as_str(), "::write"].concat())
-            .await?;
-    }
+    let (permission, own_permissions) = item_permissions(&schema_model, "write");
+    let auth_id = session.auth_id().await?;
+
+    session
+        .permission_own(&permission, &own_permissions, &auth_id)
+        .await?;
 
     let api_model = state
         .api_service
         .create(
-            &session.auth_id().await?,
+            &auth_id,
             &schema_model.slug,
             &slug,
             payload,
@@ -160,6 +189,17 @@ pub async fn api_create_collection_item_handler(
 
     state.storage_service.create_assets(&api_model.id).await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "content::create",
+            &[schema_model.slug.as_str(), &slug].join("/"),
+            None,
+            audit_summary(&api_model),
+        )
+        .await;
+
     api_model.ok_model()
 }
 
@@ -167,6 +207,7 @@ pub async fn api_update_collection_item_handler(
     Path((api, slug)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<ApiPostModel>,
 ) -> Result<ApiModel> {
     let schema_model = state.schema_service.find_by_slug(&api).await?;
@@ -175,15 +216,24 @@ pub async fn api_update_collection_item_handler(
         Err("Isn't a collection type api end-point".to_bad_request_error())?
     }
 
-    if schema_model.is_public {
-        session.permission("content::write").await?;
-    } else {
-        session
-            .permission(&[api.as_str(), "::write"].concat())
-            .await?;
-    }
+    let (permission, own_permissions) = item_permissions(&schema_model, "write");
+    let api_model = state
+        .api_service
+        .find_by_slug(
+            &schema_model.slug,
+            &slug,
+            &AudienceModel {
+                groups: vec![],
+                all: true,
+            },
+        )
+        .await?;
 
-    state
+    session
+        .permission_own(&permission, &own_permissions, &api_model.created_by)
+        .await?;
+
+    let updated_model = state
         .api_service
         .update(
             &session.auth_id().await?,
@@ -191,14 +241,27 @@ pub async fn api_update_collection_item_handler(
             &slug,
             payload,
         )
-        .await?
-        .ok_model()
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "content::update",
+            &[schema_model.slug.as_str(), &slug].join("/"),
+            audit_summary(&api_model),
+            audit_summary(&updated_model),
+        )
+        .await;
+
+    updated_model.ok_model()
 }
 
 pub async fn api_delete_collection_item_handler(
     Path((api, slug)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     let schema_model = state.schema_service.find_by_slug(&api).await?;
 
@@ -206,26 +269,39 @@ pub async fn api_delete_collection_item_handler(
         Err("Isn't a collection type api end-point".to_bad_request_error())?
     }
 
-    if schema_model.is_public {
-        session.permission("content::write").await?;
-    } else {
-        session
-            .permission(&[api.as_str(), "::delete"].concat())
-            .await?;
-    }
-
+    let (permission, own_permissions) = item_permissions(&schema_model, "delete");
     let api_model = state
         .api_service
-        .find_by_slug(&schema_model.slug, &slug)
+        .find_by_slug(
+            &schema_model.slug,
+            &slug,
+            &AudienceModel {
+                groups: vec![],
+                all: true,
+            },
+        )
+        .await?;
+
+    session
+        .permission_own(&permission, &own_permissions, &api_model.created_by)
         .await?;
 
     state.storage_service.delete_assets(&api_model.id).await?;
 
+    state.api_service.delete(&schema_model.slug, &slug).await?;
+
     state
-        .api_service
-        .delete(&schema_model.slug, &slug)
-        .await?
-        .ok_ok()
+        .audit(
+            &session,
+            &client,
+            "content::delete",
+            &[schema_model.slug.as_str(), &slug].join("/"),
+            audit_summary(&api_model),
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
 }
 
 pub async fn api_get_all_single_items_handler(
@@ -246,3 +322,20 @@ pub async fn api_get_all_collection_items_handler(
 
     state.api_service.get_all_items(&api).await?.ok_model()
 }
+
+/// Public schemas are written with `content::write`, their own items also with the
+/// `content` own permissions next to the ones every schema has.
+fn item_permissions(schema_model: &SchemaModel, action: &str) -> (String, Vec<String>) {
+    let own_permission = [schema_model.slug.as_str(), "::", action, "_own"].concat();
+
+    match schema_model.is_public {
+        true => (
+            "content::write".to_string(),
+            vec![["content::", action, "_own"].concat(), own_permission],
+        ),
+        false => (
+            [schema_model.slug.as_str(), "::", action].concat(),
+            vec![own_permission],
+        ),
+    }
+}
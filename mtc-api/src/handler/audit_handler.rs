@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{AppendHeaders, IntoResponse, Response};
+use chrono::Utc;
+use tower_sessions::Session;
+
+use mtc_model::audit_model::{AuditFilterModel, AuditModel};
+use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
+
+use crate::error::api_error::ApiError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::ClientModel;
+use crate::model::response_model::HandlerResult;
+use crate::repository::audit_repository::AuditRepositoryTrait;
+use crate::service::audit_service::{AuditTrait, AUDIT_CSV_HEADER};
+use crate::state::AppState;
+
+pub async fn audit_list_handler(
+    page: Option<Path<usize>>,
+    Query(filter): Query<AuditFilterModel>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<AuditModel>> {
+    session.permission("audit::read").await?;
+
+    let page: usize = match page {
+        Some(Path(value)) => value,
+        _ => 1,
+    };
+
+    let pagination = PaginationModel::new(
+        state.audit_service.get_total(&filter).await?,
+        state.cfg.rows_per_page,
+    )
+    .page(page);
+
+    state
+        .audit_service
+        .get_page(pagination.from, pagination.per_page, &filter)
+        .await?
+        .ok_page(pagination)
+}
+
+pub async fn audit_export_handler(
+    Query(filter): Query<AuditFilterModel>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> core::result::Result<Response, ApiError> {
+    session.permission("audit::read").await?;
+
+    let mut csv = [AUDIT_CSV_HEADER, "\n"].concat();
+    for item in state.audit_service.find_all(&filter).await? {
+        csv.push_str(
+            &[
+                csv_field(&item.created_at.to_rfc3339()),
+                csv_field(&item.actor),
+                csv_field(&item.action),
+                csv_field(&item.target),
+                csv_field(&item.ip),
+                csv_field(&item.before.unwrap_or_default()),
+                csv_field(&item.after.unwrap_or_default()),
+            ]
+            .join(","),
+        );
+        csv.push('\n');
+    }
+
+    state
+        .audit(
+            &session,
+            &client,
+            "audit::export",
+            "audit_log",
+            None,
+            serde_json::to_string(&filter).ok(),
+        )
+        .await;
+
+    let headers = AppendHeaders([
+        (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"audit-{}.csv\"",
+                Utc::now().format("%Y%m%d%H%M%S")
+            ),
+        ),
+    ]);
+
+    Ok((headers, Body::from(csv)).into_response())
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => ["\"", &value.replace('"', "\"\""), "\""].concat(),
+        false => value.to_string(),
+    }
+}
@@ -1,31 +1,70 @@
 use std::sync::Arc;
 
-use argon2::{Argon2, PasswordHash, PasswordVerifier};
-use axum::extract::State;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::Redirect;
+use axum_extra::extract::cookie::{Cookie, Key, PrivateCookieJar, SameSite};
+use chrono::{TimeDelta, Utc};
 use tower_sessions::cookie::time::Duration;
-use tower_sessions::{Expiry, Session};
-use mtc_model::auth_model::{AuthModel, SignInModel};
-use mtc_model::list_model::StringListModel;
-use mtc_model::user_model::UserChangePasswordModel;
+use tower_sessions::Session;
+use tracing::warn;
+use mtc_model::auth_model::{AuthModel, AuthModelTrait, SignInModel};
+use mtc_model::certificate_model::ClientCertificateModel;
+use mtc_model::identity_model::IdentityProviderModel;
+use mtc_model::user_model::{UserChangePasswordModel, UserModel};
 
 use crate::error::api_error::ApiError;
 use crate::error::session_error::SessionError;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
 use crate::model::access_model::AccessModel;
-use crate::model::request_model::ValidatedPayload;
-use crate::model::response_model::HandlerResult;
-use crate::repository::group_repository::GroupRepositoryTrait;
-use crate::repository::permissions_repository::PermissionsRepositoryTrait;
-use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::model::oidc_model::{OidcCallbackModel, OidcClaimsModel, OidcPendingModel};
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::model::totp_model::TwoFactorPendingModel;
+use crate::provider::config_provider::{
+    OIDC_PENDING_COOKIE, OIDC_PENDING_PATH, OIDC_RESULT_COOKIE, OIDC_RESULT_PATH,
+    SESSION_TWO_FACTOR_KEY,
+};
+use crate::repository::identity_repository::IdentityRepositoryTrait;
+use crate::repository::lockout_repository::LockoutRepositoryTrait;
+use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::repository::totp_repository::TotpRepositoryTrait;
 use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::{audit_summary, AuditTrait};
+use crate::service::auth_service::AuthTrait;
+use crate::service::certificate_service::CertificateTrait;
+use crate::service::identity_service::{IdentityTrait, OidcTrait, OIDC_PENDING_MINUTES};
+use crate::service::sign_in_log_service::SignInLogTrait;
+use crate::service::totp_service::TOTP_PENDING_MINUTES;
+use crate::service::user_service::{AccountTrait, PasswordTrait};
 use crate::state::AppState;
 
 pub async fn sign_in_handler(
     state: State<Arc<AppState>>,
+    client: ClientModel,
     session: Session,
     ValidatedPayload(payload): ValidatedPayload<SignInModel>,
 ) -> Result<AuthModel> {
+    let result = sign_in(&state, &client, &session, &payload).await;
+    if let Err(ApiError::SessionError(error)) = &result {
+        state.sign_in_log(&payload.login, &client, Some(error)).await;
+    }
+
+    result?.ok_model()
+}
+
+async fn sign_in(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    payload: &SignInModel,
+) -> crate::error::Result<AuthModel> {
+    let ip = client.ip.clone();
+    if state.lockout_service.is_ip_locked(&ip).await? {
+        Err(ApiError::from(SessionError::TooManyAttempts))?
+    }
+
     let user_model = match state
         .user_service
         .find_by_login(
@@ -33,91 +72,380 @@ pub async fn sign_in_handler(
             &AccessModel {
                 users_level: -1,
                 users_all: true,
+                groups: None,
             },
         )
         .await
     {
         Ok(value) => value,
-        _ => Err(ApiError::from(SessionError::InvalidCredentials))?,
+        _ => {
+            state.sign_in_failed(None, &ip).await?;
+            Err(ApiError::from(SessionError::InvalidCredentials))?
+        }
     };
 
-    if user_model.blocked {
-        Err(ApiError::from(SessionError::UserBlocked))?
+    state.account_check(&user_model).await?;
+
+    if !state
+        .user_service
+        .verify_password(&payload.password, &user_model.password)?
+    {
+        state.sign_in_failed(Some(&user_model.login), &ip).await?;
+        Err(ApiError::from(SessionError::InvalidCredentials))?
     }
 
-    let argon2 = Argon2::default();
+    // the most privileged accounts can't leave the certificate out
+    if state.user_service.is_certificate_password_required(&user_model)
+        && certificate_login(state, client).await?.as_deref() != Some(user_model.login.as_str())
+    {
+        Err(ApiError::from(SessionError::CertificateRequired))?
+    }
 
-    let parsed_hash = match PasswordHash::new(&user_model.password) {
-        Ok(value) => value,
-        _ => Err(ApiError::from(SessionError::PasswordHash))?,
-    };
+    // hashes made with the shared salt or outdated parameters are upgraded while the password is known
+    if state.user_service.needs_rehash(&user_model.password) {
+        state
+            .user_service
+            .rehash_password(&user_model.login, &payload.password)
+            .await?;
+    }
 
-    if argon2
-        .verify_password(payload.password.as_bytes(), &parsed_hash)
-        .is_err()
-    {
-        Err(ApiError::from(SessionError::InvalidCredentials))?
+    second_step(state, client, session, &user_model).await
+}
+
+pub async fn certificate_get_handler(client: ClientModel) -> Result<Option<ClientCertificateModel>> {
+    client.certificate.ok_model()
+}
+
+pub async fn certificate_sign_in_handler(
+    state: State<Arc<AppState>>,
+    client: ClientModel,
+    session: Session,
+) -> Result<AuthModel> {
+    let login = certificate_login(&state, &client).await?;
+
+    let result = certificate_sign_in(&state, &client, &session, login.as_deref()).await;
+    if let Err(ApiError::SessionError(error)) = &result {
+        // an unknown certificate is still worth seeing, its subject helps to bind it
+        let login = login.or(client.certificate.as_ref().map(|value| value.subject.clone()));
+        if let Some(login) = login {
+            state.sign_in_log(&login, &client, Some(error)).await;
+        }
     }
 
-    let auth_model = AuthModel {
-        id: user_model.login.clone(),
-        roles: state
-            .role_service
-            .find_by_user(&user_model.login)
-            .await
-            .unwrap_or_default()
-            .list,
-        groups: state
-            .group_service
-            .find_by_user(&user_model.login)
-            .await
-            .unwrap_or_default()
-            .list,
-        permissions: state
-            .permissions_service
-            .find_by_user(&user_model.login)
+    result?.ok_model()
+}
+
+async fn certificate_sign_in(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    login: Option<&str>,
+) -> crate::error::Result<AuthModel> {
+    if state.lockout_service.is_ip_locked(&client.ip).await? {
+        Err(ApiError::from(SessionError::TooManyAttempts))?
+    }
+
+    let user_model = match login {
+        Some(login) => state
+            .user_service
+            .find_by_login(
+                login,
+                &AccessModel {
+                    users_level: -1,
+                    users_all: true,
+                    groups: None,
+                },
+            )
             .await
-            .unwrap_or(StringListModel {
-                list: vec!["content::read".to_string()],
-            })
-            .list,
+            .ok(),
+        None => None,
     };
+    let Some(user_model) = user_model else {
+        state.sign_in_failed(None, &client.ip).await?;
+        return Err(ApiError::from(SessionError::InvalidCredentials));
+    };
+
+    state.account_check(&user_model).await?;
 
-    if &auth_model.id != "anonymous" {
-        session.set_expiry(Some(Expiry::OnInactivity(Duration::minutes(
-            state.cfg.session_expiration,
-        ))));
+    if state.user_service.is_certificate_password_required(&user_model) {
+        Err(ApiError::from(SessionError::CertificatePasswordRequired))?
     }
 
-    state
-        .user_service
-        .update_access(&user_model.login, user_model.access_count + 1)
+    second_step(state, client, session, &user_model).await
+}
+
+pub async fn oidc_provider_handler(
+    state: State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> crate::error::Result<(PrivateCookieJar, ApiResponse<Option<IdentityProviderModel>>)> {
+    let jar = oidc_jar(&state, &headers);
+    if !state.identity_service.is_oidc_enabled() {
+        return Ok((jar, ApiResponse::Data(None)));
+    }
+
+    let message = jar
+        .get(OIDC_RESULT_COOKIE)
+        .map(|cookie| cookie.value().to_string());
+
+    Ok((
+        jar.remove(oidc_result_cookie(String::new())),
+        ApiResponse::Data(Some(IdentityProviderModel {
+            name: state.cfg.oidc_name.clone(),
+            message,
+        })),
+    ))
+}
+
+pub async fn oidc_sign_in_handler(
+    state: State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> crate::error::Result<(PrivateCookieJar, Redirect)> {
+    oidc_redirect(&state, oidc_jar(&state, &headers), None).await
+}
+
+pub async fn oidc_link_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    headers: HeaderMap,
+) -> crate::error::Result<(PrivateCookieJar, Redirect)> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+    if auth.impersonator.is_some() {
+        Err(ApiError::from(SessionError::ImpersonationReadOnly))?
+    }
+
+    oidc_redirect(&state, oidc_jar(&state, &headers), Some(auth.id)).await
+}
+
+/// The browser always comes back to the dashboard, the outcome is picked up from a cookie.
+pub async fn oidc_callback_handler(
+    Query(callback): Query<OidcCallbackModel>,
+    state: State<Arc<AppState>>,
+    client: ClientModel,
+    session: Session,
+    headers: HeaderMap,
+) -> crate::error::Result<(PrivateCookieJar, Redirect)> {
+    let jar = oidc_jar(&state, &headers);
+    let pending = jar
+        .get(OIDC_PENDING_COOKIE)
+        .and_then(|cookie| serde_json::from_str::<OidcPendingModel>(cookie.value()).ok());
+    let mut jar = jar.remove(oidc_pending_cookie(String::new()));
+
+    if let Err(e) = oidc_callback(&state, &client, &session, pending, &callback).await {
+        jar = jar.add(oidc_result_cookie(e.to_string()));
+    }
+
+    Ok((jar, Redirect::to("/dashboard")))
+}
+
+async fn oidc_redirect(
+    state: &AppState,
+    jar: PrivateCookieJar,
+    link: Option<String>,
+) -> crate::error::Result<(PrivateCookieJar, Redirect)> {
+    let pending = state.identity_service.oidc_pending(link);
+    let url = state
+        .identity_service
+        .oidc_authorization_url(&pending)
         .await?;
 
-    let access_model = AccessModel {
-        users_level: state
-            .user_service
-            .get_roles_min_access_level(&user_model.login)
-            .await
-            .unwrap_or(999),
-        users_all: state
-            .user_service
-            .get_roles_access_all(&user_model.login)
-            .await
-            .unwrap_or(false),
+    Ok((
+        jar.add(oidc_pending_cookie(serde_json::to_string(&pending)?)),
+        Redirect::to(&url),
+    ))
+}
+
+/// Encrypted with the session key, the cookies below are not readable or forgeable by the browser.
+fn oidc_jar(state: &AppState, headers: &HeaderMap) -> PrivateCookieJar {
+    PrivateCookieJar::from_headers(headers, Key::from(state.cfg.session_secure_key.as_bytes()))
+}
+
+/// The provider redirects back cross-site, where the strict session cookie stays behind,
+/// so the pending sign in travels in a lax cookie that only the callback gets to see.
+fn oidc_pending_cookie(value: String) -> Cookie<'static> {
+    Cookie::build((OIDC_PENDING_COOKIE, value))
+        .path(OIDC_PENDING_PATH)
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(Duration::minutes(OIDC_PENDING_MINUTES))
+        .build()
+}
+
+/// Written on the way back from the provider, where the session can't be written without
+/// replacing the one of the user linking an identity.
+fn oidc_result_cookie(value: String) -> Cookie<'static> {
+    Cookie::build((OIDC_RESULT_COOKIE, value))
+        .path(OIDC_RESULT_PATH)
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Strict)
+        .max_age(Duration::minutes(OIDC_PENDING_MINUTES))
+        .build()
+}
+
+async fn oidc_callback(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    pending: Option<OidcPendingModel>,
+    callback: &OidcCallbackModel,
+) -> crate::error::Result<()> {
+    // the state ties the callback to the redirect made from this very browser
+    let Some(pending) = pending.filter(|pending| {
+        pending.expires_at > Utc::now().timestamp()
+            && callback.state.as_deref() == Some(pending.state.as_str())
+    }) else {
+        return Err(ApiError::from(SessionError::InvalidSession));
     };
-    session.set_access(access_model).await?;
-    session.sign_in(auth_model.clone()).await?;
 
-    auth_model.ok_model()
+    if let Some(error) = &callback.error {
+        warn!("Identity provider refused the sign in: {error}");
+        Err(ApiError::from(SessionError::InvalidCredentials))?
+    }
+    let Some(code) = &callback.code else {
+        return Err(ApiError::from(SessionError::InvalidCredentials));
+    };
+
+    if state.lockout_service.is_ip_locked(&client.ip).await? {
+        Err(ApiError::from(SessionError::TooManyAttempts))?
+    }
+
+    let claims = state.identity_service.oidc_claims(code, &pending).await?;
+
+    if let Some(login) = &pending.link {
+        let identity_model = state
+            .identity_service
+            .create(login, &claims.iss, &claims.sub)
+            .await?;
+
+        // the session isn't sent along from the provider, the pending link names the user
+        state
+            .audit_as(
+                login,
+                client,
+                "auth::identity_link",
+                login,
+                None,
+                audit_summary(&identity_model),
+            )
+            .await;
+
+        return Ok(());
+    }
+
+    let result = oidc_sign_in(state, client, session, &claims).await;
+    if let Err(ApiError::SessionError(error)) = &result {
+        let login = claims
+            .claims
+            .get(&state.cfg.oidc_login_claim)
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_uppercase())
+            .unwrap_or(claims.sub.clone());
+        state.sign_in_log(&login, client, Some(error)).await;
+    }
+
+    result.map(|_| ())
+}
+
+async fn oidc_sign_in(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    claims: &OidcClaimsModel,
+) -> crate::error::Result<AuthModel> {
+    let user_model = match state.identity_user(claims).await {
+        Ok(value) => value,
+        Err(ApiError::SessionError(SessionError::IdentityNotLinked)) => {
+            state.sign_in_failed(None, &client.ip).await?;
+            return Err(ApiError::from(SessionError::IdentityNotLinked));
+        }
+        Err(e) => return Err(e),
+    };
+
+    state.account_check(&user_model).await?;
+
+    // the identity provider stands in for the password, the certificate is still demanded
+    if state.user_service.is_certificate_password_required(&user_model)
+        && certificate_login(state, client).await?.as_deref() != Some(user_model.login.as_str())
+    {
+        Err(ApiError::from(SessionError::CertificateRequired))?
+    }
+
+    state.identity_sync(&user_model, claims).await?;
+
+    second_step(state, client, session, &user_model).await
+}
+
+async fn certificate_login(state: &AppState, client: &ClientModel) -> crate::error::Result<Option<String>> {
+    match &client.certificate {
+        Some(certificate) => state.certificate_service.certificate_login(certificate).await,
+        None => Ok(None),
+    }
+}
+
+async fn second_step(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    user_model: &UserModel,
+) -> crate::error::Result<AuthModel> {
+    let totp_model = state.totp_service.find_by_login(&user_model.login).await?;
+    if totp_model.totp_enabled || state.totp_service.is_required(&user_model.login).await? {
+        // the password is correct, but the session is signed in only after the second step
+        session
+            .insert(
+                SESSION_TWO_FACTOR_KEY,
+                TwoFactorPendingModel {
+                    login: user_model.login.clone(),
+                    enrolled: totp_model.totp_enabled,
+                    attempts: 0,
+                    expires_at: (Utc::now() + TimeDelta::minutes(TOTP_PENDING_MINUTES))
+                        .timestamp(),
+                },
+            )
+            .await?;
+
+        match totp_model.totp_enabled {
+            true => Err(ApiError::from(SessionError::TwoFactorRequired))?,
+            false => Err(ApiError::from(SessionError::TwoFactorEnrollment))?,
+        }
+    }
+
+    state.user_sign_in(session, user_model, client).await
 }
 
 pub async fn sign_out_handler(state: State<Arc<AppState>>, session: Session) -> Result<AuthModel> {
+    if let Some(id) = session.id() {
+        // the session was registered for whoever signed in, not for the impersonated user
+        let auth = session.credentials().await?;
+        state
+            .session_service
+            .delete(&auth.impersonator.unwrap_or(auth.id), &id.to_string())
+            .await?;
+    }
     session.flush().await?;
 
     session.anonymous(&state).await?.ok_model()
 }
 
+pub async fn impersonation_end_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<AuthModel> {
+    let login = session.auth_id().await?;
+    let auth_model = state.impersonation_end(&session).await?;
+
+    state
+        .audit(&session, &client, "user::impersonate_end", &login, None, None)
+        .await;
+
+    auth_model.ok_model()
+}
+
 pub async fn get_credentials_handler(session: Session) -> Result<AuthModel> {
     session.credentials().await?.ok_model()
 }
@@ -125,6 +453,7 @@ pub async fn get_credentials_handler(session: Session) -> Result<AuthModel> {
 pub async fn change_password_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<UserChangePasswordModel>,
 ) -> Result<()> {
     let user_model = match state
@@ -134,6 +463,7 @@ pub async fn change_password_handler(
             &AccessModel {
                 users_level: 0,
                 users_all: true,
+                groups: None,
             },
         )
         .await
@@ -142,23 +472,32 @@ pub async fn change_password_handler(
         _ => Err(ApiError::from(SessionError::InvalidCredentials))?,
     };
 
-    let argon2 = Argon2::default();
-
-    let password_hash = match PasswordHash::new(&user_model.password) {
-        Ok(value) => value,
-        _ => Err(ApiError::from(SessionError::PasswordHash))?,
-    };
-    if argon2
-        .verify_password(payload.old_password.as_bytes(), &password_hash)
-        .is_err()
+    if !state
+        .user_service
+        .verify_password(&payload.old_password, &user_model.password)?
     {
         Err(ApiError::from(SessionError::InvalidCredentials))?
     }
 
+    if payload.old_password.eq(&payload.new_password) {
+        Err(ApiError::from(SessionError::PasswordUnchanged))?
+    }
+
     state
         .user_service
         .change_password(&user_model.login, &payload.new_password)
         .await?;
 
+    // lifts the restriction of a session signed in with a password which had to be changed
+    if session.credentials().await?.password_change {
+        session
+            .sign_in(state.user_credentials(&user_model.login).await?)
+            .await?;
+    }
+
+    state
+        .audit(&session, &client, "auth::password_change", &user_model.login, None, None)
+        .await;
+
     user_model.ok_ok()
 }
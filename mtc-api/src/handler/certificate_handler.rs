@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use tower_sessions::Session;
+
+use mtc_model::certificate_model::{CertificateCreateModel, CertificateModel};
+
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::repository::certificate_repository::CertificateRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::{audit_summary, AuditTrait};
+use crate::state::AppState;
+
+pub async fn user_certificate_list_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<CertificateModel>> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .certificate_service
+        .find_by_login(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_certificate_create_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<CertificateCreateModel>,
+) -> Result<CertificateModel> {
+    session.permission("user::write").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    let certificate_model = state
+        .certificate_service
+        .create(&session.auth_id().await?, &user_model.login, payload)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::certificate_add",
+            &user_model.login,
+            None,
+            audit_summary(&certificate_model),
+        )
+        .await;
+
+    certificate_model.ok_model()
+}
+
+pub async fn user_certificate_delete_handler(
+    Path((login, id)): Path<(String, String)>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .certificate_service
+        .delete(&user_model.login, &id)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::certificate_delete",
+            &[user_model.login.as_str(), &id].join("/"),
+            None,
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
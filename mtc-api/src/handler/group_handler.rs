@@ -2,18 +2,26 @@ use std::sync::Arc;
 
 use axum::extract::{Path, State};
 use tower_sessions::Session;
-use tracing::error;
+use tracing::{error, warn};
 
-use mtc_model::group_model::{GroupCreateModel, GroupModel, GroupUpdateModel};
+use mtc_model::group_model::{
+    GroupCreateModel, GroupModel, GroupParentModel, GroupTreeModel, GroupUpdateModel,
+};
 use mtc_model::list_model::{RecordListModel, StringListModel};
 use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
+use mtc_model::user_model::UserValidityModel;
 
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
 use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::group_repository::GroupRepositoryTrait;
+use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
 use crate::repository::RepositoryPaginate;
+use crate::service::audit_service::{audit_summary, AuditTrait};
+use crate::service::group_service::GroupTreeTrait;
 use crate::state::AppState;
 
 pub async fn group_all_handler(
@@ -25,6 +33,15 @@ pub async fn group_all_handler(
     state.group_service.all().await?.ok_model()
 }
 
+pub async fn group_tree_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<GroupTreeModel>> {
+    session.permission("group::read").await?;
+
+    state.group_service.tree().await?.ok_model()
+}
+
 pub async fn group_list_handler(
     page: Option<Path<usize>>,
     state: State<Arc<AppState>>,
@@ -64,54 +81,303 @@ pub async fn group_create_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<GroupCreateModel>,
 ) -> Result<GroupModel> {
     session.permission("group::write").await?;
 
     state
+        .group_service
+        .check_parent(&slug, payload.parent.as_deref())
+        .await?;
+
+    let group_model = state
         .group_service
         .create(&session.auth_id().await?, &slug, payload)
-        .await?
-        .ok_model()
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::create",
+            &group_model.slug,
+            None,
+            audit_summary(&group_model),
+        )
+        .await;
+
+    group_model.ok_model()
 }
 
 pub async fn group_update_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<GroupUpdateModel>,
 ) -> Result<GroupModel> {
     session.permission("group::write").await?;
 
-    state
+    let before = state.group_service.find_by_slug(&slug).await?;
+    let group_model = state
         .group_service
         .update(&session.auth_id().await?, &slug, payload)
-        .await?
-        .ok_model()
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::update",
+            &group_model.slug,
+            audit_summary(&before),
+            audit_summary(&group_model),
+        )
+        .await;
+
+    group_model.ok_model()
+}
+
+pub async fn group_parent_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<GroupParentModel>,
+) -> Result<GroupModel> {
+    session.permission("group::write").await?;
+
+    let before = state.group_service.find_by_slug(&slug).await?;
+    state
+        .group_service
+        .check_parent(&slug, payload.parent.as_deref())
+        .await?;
+
+    let group_model = state
+        .group_service
+        .set_parent(&session.auth_id().await?, &slug, payload.parent)
+        .await?;
+    // inherited memberships and roles follow the hierarchy, so every session has to pick them up again
+    state.user_service.update_access_levels().await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::parent",
+            &group_model.slug,
+            audit_summary(&before),
+            audit_summary(&group_model),
+        )
+        .await;
+
+    group_model.ok_model()
+}
+
+pub async fn group_get_roles_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<StringListModel> {
+    session.permission("group::read").await?;
+
+    state.role_service.find_by_group(&slug).await?.ok_model()
+}
+
+pub async fn group_set_roles_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<StringListModel>,
+) -> Result<StringListModel> {
+    session.permission("group::write").await?;
+    session.permission("user::write").await?;
+
+    let group_model = state.group_service.find_by_slug(&slug).await?;
+    let before = state.role_service.find_by_group(&slug).await?;
+
+    state.group_service.roles_drop(&group_model.id).await?;
+
+    for role in payload.list {
+        match state.role_service.find_by_slug(&role).await {
+            Ok(value) => state.group_service.role_assign(&group_model.id, &value.id).await?,
+            _ => warn!("can't find role -> {role}"),
+        }
+    }
+
+    // every member down the hierarchy may have gained or lost a role
+    state.user_service.update_access_levels().await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    let roles = state.role_service.find_by_group(&slug).await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::roles",
+            &group_model.slug,
+            audit_summary(&before),
+            audit_summary(&roles),
+        )
+        .await;
+
+    roles.ok_model()
+}
+
+pub async fn group_get_managers_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<StringListModel> {
+    session.permission("group::read").await?;
+
+    state.group_service.find_managers(&slug).await?.ok_model()
+}
+
+pub async fn group_set_managers_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<StringListModel>,
+) -> Result<StringListModel> {
+    session.permission("group::write").await?;
+    session.permission("user::write").await?;
+    let access = session.get_access().await?;
+
+    let group_model = state.group_service.find_by_slug(&slug).await?;
+    let before = state.group_service.find_managers(&slug).await?;
+
+    state.group_service.managers_drop(&group_model.id).await?;
+
+    for login in payload.list {
+        match state.user_service.find_by_login(&login, &access).await {
+            Ok(value) => {
+                state
+                    .group_service
+                    .manager_assign(&group_model.id, &value.id)
+                    .await?
+            }
+            _ => warn!("can't find user -> {login}"),
+        }
+    }
+
+    // the managers' reach is part of their sessions
+    state.system_service.increment_auth_epoch().await?;
+
+    let managers = state.group_service.find_managers(&slug).await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::managers",
+            &group_model.slug,
+            audit_summary(&before),
+            audit_summary(&managers),
+        )
+        .await;
+
+    managers.ok_model()
+}
+
+pub async fn group_validity_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<UserValidityModel>,
+) -> Result<StringListModel> {
+    session.permission("group::read").await?;
+    session.permission("user::write").await?;
+
+    let group_model = state.group_service.find_by_slug(&slug).await?;
+
+    let list = state
+        .user_service
+        .set_group_validity(
+            &session.auth_id().await?,
+            &group_model.slug,
+            &payload,
+            &session.get_access().await?,
+        )
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::validity",
+            &group_model.slug,
+            None,
+            audit_summary(&payload),
+        )
+        .await;
+
+    StringListModel { list }.ok_model()
 }
 
 pub async fn group_delete_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("group::delete").await?;
 
-    state.group_service.delete(&slug).await?.ok_ok()
+    let before = state.group_service.find_by_slug(&slug).await?;
+
+    state.group_service.delete(&slug).await?;
+    state.user_service.update_access_levels().await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "group::delete",
+            &slug,
+            audit_summary(&before),
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
 }
 
 pub async fn group_list_delete_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<()> {
     session.permission("group::delete").await?;
 
     for item in payload.list {
+        let before = state.group_service.find_by_slug(&item).await.ok();
+
         match state.group_service.delete(&item).await {
-            Ok(_) => (),
+            Ok(_) => {
+                state
+                    .audit(
+                        &session,
+                        &client,
+                        "group::delete",
+                        &item,
+                        before.and_then(|value| audit_summary(&value)),
+                        None,
+                    )
+                    .await
+            }
             Err(e) => error!("Group delete: {}", e.to_string()),
         }
     }
+    state.user_service.update_access_levels().await?;
+    state.system_service.increment_auth_epoch().await?;
+
     Ok(ApiResponse::Ok)
 }
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use tower_sessions::Session;
+
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::identity_model::IdentityModel;
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::ClientModel;
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::repository::identity_repository::IdentityRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
+use crate::state::AppState;
+
+pub async fn identity_list_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<IdentityModel>> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state
+        .identity_service
+        .find_by_login(&auth.id)
+        .await?
+        .ok_model()
+}
+
+pub async fn identity_delete_handler(
+    Path(id): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state.identity_service.delete(&auth.id, &id).await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "auth::identity_unlink",
+            &[auth.id.as_str(), &id].join("/"),
+            None,
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn user_identity_list_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<IdentityModel>> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .identity_service
+        .find_by_login(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_identity_delete_handler(
+    Path((login, id)): Path<(String, String)>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .identity_service
+        .delete(&user_model.login, &id)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::identity_unlink",
+            &[user_model.login.as_str(), &id].join("/"),
+            None,
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
@@ -1,8 +1,6 @@
 use std::collections::BTreeSet;
 use std::sync::Arc;
 
-use argon2::{Argon2, PasswordHasher};
-use argon2::password_hash::SaltString;
 use axum::extract::State;
 use tower_sessions::Session;
 use tracing::info;
@@ -11,15 +9,19 @@ use mtc_model::auth_model::SignInModel;
 use mtc_model::list_model::StringListModel;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
 use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::repository::schema_repository::SchemaRepositoryTrait;
 use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::service::audit_service::{audit_summary, AuditTrait};
 use crate::service::migration_service::MigrationTrait;
+use crate::service::user_service::PasswordTrait;
 use crate::state::AppState;
 
 pub async fn migration_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<SignInModel>,
 ) -> Result<()> {
     let mut migrations = state.system_service.get_migrations().await?;
@@ -27,15 +29,14 @@ pub async fn migration_handler(
     if !migrations.is_empty() {
         session.permission("administrator").await?;
         session.permission("schema::write").await?;
-    } 
-
-    let salt = SaltString::from_b64(&state.cfg.password_salt).unwrap();
+    } else {
+        // the initial migration creates the administrator with this password
+        state
+            .user_service
+            .password_policy(&payload.login, &payload.password)?;
+    }
 
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(payload.password.as_bytes(), &salt)
-        .expect("Error occurred while encrypted password")
-        .to_string();
+    let password_hash = state.user_service.hash_password(&payload.password)?;
 
     let migration_files = state.migration_service.get_migration_files().await?
         .iter()
@@ -57,6 +58,27 @@ pub async fn migration_handler(
         state.system_service.set_migrations(migrations.clone()).await?;
     }
 
+    // collection tables are created at runtime, so bring their definitions up to date as well
+    if !migration_files.is_empty() {
+        for collection in state.schema_service.get_all_collections().await?.list {
+            state
+                .schema_service
+                .define_collection(&collection.slug)
+                .await?;
+        }
+
+        state
+            .audit(
+                &session,
+                &client,
+                "migration::run",
+                "migrations",
+                None,
+                audit_summary(&migration_files),
+            )
+            .await;
+    }
+
     Ok(ApiResponse::Ok)
 }
 
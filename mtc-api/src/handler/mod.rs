@@ -3,11 +3,19 @@ pub mod role_handler;
 pub mod user_handler;
 pub mod permissions_handler;
 pub mod auth_handler;
+pub mod certificate_handler;
+pub mod identity_handler;
 pub mod group_handler;
 pub mod schema_handler;
 pub mod api_handler;
 pub mod storage_handler;
 pub mod migration_handler;
+pub mod token_handler;
+pub mod totp_handler;
+pub mod session_handler;
+pub mod audit_handler;
+pub mod sign_in_log_handler;
+pub mod person_handler;
 
 pub type Result<T> =
 core::result::Result<
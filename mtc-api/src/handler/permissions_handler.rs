@@ -3,14 +3,18 @@ use std::sync::Arc;
 use axum::extract::State;
 use tower_sessions::Session;
 
+use mtc_model::auth_model::is_permission_rule;
 use mtc_model::list_model::RecordListModel;
 use mtc_model::permission_model::{PermissionDtoModel, PermissionModel};
 
+use crate::error::api_error::ToApiError;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
-use crate::model::response_model::HandlerResult;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::permissions_repository::PermissionsRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
 use crate::state::AppState;
 
 pub async fn permissions_list_handler(
@@ -34,26 +38,43 @@ pub async fn permissions_get_custom(
 pub async fn permissions_create_custom(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<PermissionDtoModel>,
 ) -> Result<()> {
     session.permission("role::write").await?;
 
+    if !is_permission_rule(&payload.slug) {
+        Err("incorrect permission rule".to_bad_request_error())?
+    }
+
+    let slug = payload.slug.clone();
     state
         .permissions_service
         .create_custom(&session.auth_id().await?, payload)
-        .await?.ok_model()
+        .await?;
+
+    state
+        .audit(&session, &client, "permissions::create", &slug, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
 }
 
 pub async fn permissions_delete_custom(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<PermissionDtoModel>,
 ) -> Result<()> {
     session.permission("role::write").await?;
 
+    let slug = payload.slug.clone();
+    state.permissions_service.delete_custom(payload).await?;
+    state.system_service.increment_auth_epoch().await?;
+
     state
-        .permissions_service
-        .delete_custom(payload)
-        .await?
-        .ok_model()
-}
\ No newline at end of file
+        .audit(&session, &client, "permissions::delete", &slug, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{AppendHeaders, IntoResponse, Response};
+use chrono::Utc;
+use tower_sessions::Session;
+
+use mtc_model::field_model::FieldModel;
+use mtc_model::user_details_model::{
+    PersonExportModel, PersonFormatModel, PersonImportModel, PersonImportResultModel, PersonModel,
+};
+
+use crate::error::api_error::ApiError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::HandlerResult;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
+use crate::service::person_service::PersonTrait;
+use crate::state::AppState;
+
+pub async fn person_list_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<PersonModel>> {
+    session.permission("user::read").await?;
+
+    state
+        .user_service
+        .find_persons(&session.get_access().await?)
+        .await?
+        .ok_model()
+}
+
+pub async fn person_profile_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<FieldModel>> {
+    session.permission("user::read").await?;
+
+    state.profile_fields().await?.ok_model()
+}
+
+pub async fn person_import_preview_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    ValidatedPayload(payload): ValidatedPayload<PersonImportModel>,
+) -> Result<PersonImportResultModel> {
+    session.permission("user::write").await?;
+
+    state
+        .person_import(
+            &session.auth_id().await?,
+            &payload,
+            &session.get_access().await?,
+            true,
+        )
+        .await?
+        .ok_model()
+}
+
+pub async fn person_import_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<PersonImportModel>,
+) -> Result<PersonImportResultModel> {
+    session.permission("user::write").await?;
+
+    let result = state
+        .person_import(
+            &session.auth_id().await?,
+            &payload,
+            &session.get_access().await?,
+            false,
+        )
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "person::import",
+            "users",
+            None,
+            serde_json::to_string(&result.updated).ok(),
+        )
+        .await;
+
+    result.ok_model()
+}
+
+pub async fn person_export_handler(
+    Query(query): Query<PersonExportModel>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> core::result::Result<Response, ApiError> {
+    session.permission("user::read").await?;
+
+    let data = state
+        .person_export(&query.format, &session.get_access().await?)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "person::export",
+            "users",
+            None,
+            serde_json::to_string(&query).ok(),
+        )
+        .await;
+
+    let (content_type, extension) = match query.format {
+        PersonFormatModel::Tsv => ("text/tab-separated-values; charset=utf-8", "tsv"),
+        PersonFormatModel::Csv => ("text/csv; charset=utf-8", "csv"),
+        PersonFormatModel::Json => ("application/json", "json"),
+    };
+
+    let headers = AppendHeaders([
+        (header::CONTENT_TYPE, content_type.to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"persons-{}.{}\"",
+                Utc::now().format("%Y%m%d%H%M%S"),
+                extension
+            ),
+        ),
+    ]);
+
+    Ok((headers, Body::from(data)).into_response())
+}
@@ -10,11 +10,13 @@ use mtc_model::role_model::{RoleCreateModel, RoleModel, RoleUpdateModel};
 
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
 use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::permissions_repository::PermissionsRepositoryTrait;
 use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
 use crate::repository::RepositoryPaginate;
+use crate::service::audit_service::{audit_summary, AuditTrait};
 use crate::state::AppState;
 
 pub async fn role_all_handler(
@@ -73,6 +75,7 @@ pub async fn role_create_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<RoleCreateModel>,
 ) -> Result<RoleModel> {
     session.permission("role::write").await?;
@@ -96,6 +99,17 @@ pub async fn role_create_handler(
         }
     }
 
+    state
+        .audit(
+            &session,
+            &client,
+            "role::create",
+            &role_model.slug,
+            None,
+            role_summary(&state, &role_model.slug).await,
+        )
+        .await;
+
     role_model.ok_model()
 }
 
@@ -103,10 +117,13 @@ pub async fn role_update_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<RoleUpdateModel>,
 ) -> Result<RoleModel> {
     session.permission("role::write").await?;
 
+    let before = role_summary(&state, &slug).await;
+
     let role_model = state
         .role_service
         .update(&session.auth_id().await?, &slug, &payload)
@@ -127,6 +144,18 @@ pub async fn role_update_handler(
             }
         }
     }
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "role::update",
+            &role_model.slug,
+            before,
+            role_summary(&state, &role_model.slug).await,
+        )
+        .await;
 
     role_model.ok_model()
 }
@@ -135,25 +164,44 @@ pub async fn role_delete_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("role::delete").await?;
 
-    state.role_service.delete(&slug).await?.ok_ok()
+    let before = role_summary(&state, &slug).await;
+
+    state.role_service.delete(&slug).await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(&session, &client, "role::delete", &slug, before, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
 }
 
 pub async fn role_list_delete_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<()> {
     session.permission("role::delete").await?;
 
     for item in payload.list {
+        let before = role_summary(&state, &item).await;
+
         match state.role_service.delete(&item).await {
-            Ok(_) => (),
+            Ok(_) => {
+                state
+                    .audit(&session, &client, "role::delete", &item, before, None)
+                    .await
+            }
             Err(e) => error!("Role delete: {}", e.to_string()),
         }
     }
+    state.system_service.increment_auth_epoch().await?;
+
     Ok(ApiResponse::Ok)
 }
 
@@ -175,11 +223,13 @@ pub async fn role_set_permissions(
     Path(slug): Path<String>,
     session: Session,
     state: State<Arc<AppState>>,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<StringListModel> {
     session.permission("role::write").await?;
 
     let role_model = state.role_service.find_by_slug(&slug).await?;
+    let before = role_summary(&state, &slug).await;
 
     state.role_service.permissions_drop(&role_model.id).await?;
 
@@ -194,6 +244,18 @@ pub async fn role_set_permissions(
             _ => warn!("can't find permission -> {permission}"),
         }
     }
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "role::permissions",
+            &slug,
+            before,
+            role_summary(&state, &slug).await,
+        )
+        .await;
 
     state
         .permissions_service
@@ -201,3 +263,15 @@ pub async fn role_set_permissions(
         .await?
         .ok_model()
 }
+
+async fn role_summary(state: &Arc<AppState>, slug: &str) -> Option<String> {
+    let mut role_model = state.role_service.find_by_slug(slug).await.ok()?;
+    role_model.permissions = state
+        .permissions_service
+        .find_by_role(slug)
+        .await
+        .ok()
+        .map(|value| value.list);
+
+    audit_summary(&role_model)
+}
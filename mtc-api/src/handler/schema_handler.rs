@@ -4,19 +4,24 @@ use axum::extract::{Path, State};
 use tower_sessions::Session;
 use tracing::error;
 
+use mtc_model::bundle_model::{SchemaBundleDiffModel, SchemaBundleModel};
 use mtc_model::list_model::{RecordListModel, StringListModel};
 use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
 use mtc_model::schema_model::{
-    SchemaCreateModel, SchemaFieldsModel, SchemaModel, SchemaUpdateModel,
+    SchemaCreateModel, SchemaFieldsModel, SchemaMigrationModel, SchemaModel, SchemaUpdateModel,
 };
 
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
+use crate::model::access_model::AudienceModel;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
 use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::api_repository::ApiRepositoryTrait;
 use crate::repository::schema_repository::SchemaRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
 use crate::repository::RepositoryPaginate;
+use crate::service::audit_service::{audit_summary, AuditTrait};
+use crate::service::bundle_service::SchemaBundleTrait;
 use crate::service::storage_service::StorageTrait;
 use crate::state::AppState;
 
@@ -58,6 +63,7 @@ pub async fn schema_create_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<SchemaCreateModel>,
 ) -> Result<SchemaModel> {
     session.permission("schema::write").await?;
@@ -68,10 +74,31 @@ pub async fn schema_create_handler(
         .await?;
 
     if !schema_model.is_collection {
-        let single = state.api_service.find_by_slug("singles", &slug).await?;
+        let single = state
+            .api_service
+            .find_by_slug(
+                "singles",
+                &slug,
+                &AudienceModel {
+                    groups: vec![],
+                    all: true,
+                },
+            )
+            .await?;
         state.storage_service.create_assets(&single.id).await?;
     }
 
+    state
+        .audit(
+            &session,
+            &client,
+            "schema::create",
+            &schema_model.slug,
+            None,
+            audit_summary(&schema_model),
+        )
+        .await;
+
     schema_model.ok_model()
 }
 
@@ -79,30 +106,66 @@ pub async fn schema_delete_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("schema::delete").await?;
 
     let schema_model = state.schema_service.find_by_slug(&slug).await?;
 
     if !schema_model.is_collection {
-        let single = state.api_service.find_by_slug("singles", &slug).await?;
+        let single = state
+            .api_service
+            .find_by_slug(
+                "singles",
+                &slug,
+                &AudienceModel {
+                    groups: vec![],
+                    all: true,
+                },
+            )
+            .await?;
         state.storage_service.delete_assets(&single.id).await?;
     }
     state.schema_service.delete(&schema_model.slug).await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "schema::delete",
+            &schema_model.slug,
+            audit_summary(&schema_model),
+            None,
+        )
+        .await;
+
     schema_model.ok_ok()
 }
 
 pub async fn schema_list_delete_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<()> {
     session.permission("schema::delete").await?;
 
     for item in payload.list {
+        let before = state.schema_service.find_by_slug(&item).await.ok();
+
         match state.schema_service.delete(&item).await {
-            Ok(_) => (),
+            Ok(_) => {
+                state
+                    .audit(
+                        &session,
+                        &client,
+                        "schema::delete",
+                        &item,
+                        before.and_then(|value| audit_summary(&value)),
+                        None,
+                    )
+                    .await
+            }
             Err(e) => error!("Schema delete: {}", e.to_string()),
         }
     }
@@ -113,35 +176,100 @@ pub async fn schema_update_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<SchemaUpdateModel>,
 ) -> Result<SchemaModel> {
     session.permission("schema::write").await?;
 
-    state
+    let before = state.schema_service.find_by_slug(&slug).await?;
+
+    let schema_model = state
         .schema_service
         .update(&session.auth_id().await?, &slug, payload)
-        .await?
-        .ok_model()
+        .await?;
+
+    // the schema permissions come and go with the public access
+    if before.is_public.ne(&schema_model.is_public) {
+        state.system_service.increment_auth_epoch().await?;
+    }
+
+    if !schema_model.is_collection && !schema_model.is_system {
+        let single = state
+            .api_service
+            .find_by_slug(
+                "singles",
+                &slug,
+                &AudienceModel {
+                    groups: vec![],
+                    all: true,
+                },
+            )
+            .await?;
+        state.storage_service.create_assets(&single.id).await?;
+    }
+
+    state
+        .audit(
+            &session,
+            &client,
+            "schema::update",
+            &schema_model.slug,
+            audit_summary(&before),
+            audit_summary(&schema_model),
+        )
+        .await;
+
+    schema_model.ok_model()
 }
 
 pub async fn schema_update_fields_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<SchemaFieldsModel>,
 ) -> Result<SchemaFieldsModel> {
     session.permission("schema::write").await?;
 
+    let before = state.schema_service.find_by_slug(&slug).await?;
+
     let schema_model = state
         .schema_service
         .update_fields(&session.auth_id().await?, &slug, payload)
         .await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "schema::fields",
+            &schema_model.slug,
+            audit_summary(&before.fields),
+            audit_summary(&schema_model.fields),
+        )
+        .await;
+
     Ok(ApiResponse::Data(SchemaFieldsModel {
         fields: schema_model.fields,
+        force: None,
     }))
 }
 
+pub async fn schema_migration_preview_handler(
+    Path(slug): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    ValidatedPayload(payload): ValidatedPayload<SchemaFieldsModel>,
+) -> Result<SchemaMigrationModel> {
+    session.permission("schema::write").await?;
+
+    state
+        .schema_service
+        .migration_preview(&slug, payload)
+        .await?
+        .ok_model()
+}
+
 pub async fn schema_get_fields_handler(
     Path(slug): Path<String>,
     state: State<Arc<AppState>>,
@@ -160,3 +288,51 @@ pub async fn schema_get_all_collections_handler(
 
     state.schema_service.get_all_collections().await?.ok_model()
 }
+
+pub async fn schema_bundle_export_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<SchemaBundleModel> {
+    session.permission("schema::read").await?;
+    session.permission("role::read").await?;
+
+    state.bundle_export(&[]).await?.ok_model()
+}
+
+pub async fn schema_bundle_preview_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    ValidatedPayload(payload): ValidatedPayload<SchemaBundleModel>,
+) -> Result<SchemaBundleDiffModel> {
+    session.permission("schema::write").await?;
+    session.permission("role::write").await?;
+
+    state.bundle_diff(&payload).await?.ok_model()
+}
+
+pub async fn schema_bundle_import_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<SchemaBundleModel>,
+) -> Result<SchemaBundleDiffModel> {
+    session.permission("schema::write").await?;
+    session.permission("role::write").await?;
+
+    let diff = state
+        .bundle_import(&session.auth_id().await?, payload)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "schema::import",
+            "bundle",
+            None,
+            audit_summary(&diff),
+        )
+        .await;
+
+    diff.ok_model()
+}
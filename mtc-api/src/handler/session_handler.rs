@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use tower_sessions::Session;
+
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::session_model::SessionModel;
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::ClientModel;
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
+use crate::state::AppState;
+
+pub async fn session_list_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<SessionModel>> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    let current = session.id().map(|id| id.to_string()).unwrap_or_default();
+
+    let mut sessions = state.session_service.find_by_login(&auth.id).await?;
+    sessions
+        .iter_mut()
+        .for_each(|item| item.current = item.id.eq(&current));
+
+    sessions.ok_model()
+}
+
+pub async fn session_delete_handler(
+    Path(id): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state.session_service.delete(&auth.id, &id).await?;
+
+    state
+        .audit(&session, &client, "auth::session_delete", &auth.id, None, None)
+        .await;
+
+    if session.id().is_some_and(|current| current.to_string().eq(&id)) {
+        session.flush().await?;
+    }
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn session_delete_others_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    let current = session.id().map(|id| id.to_string());
+
+    state
+        .session_service
+        .delete_by_login(&auth.id, current.as_deref())
+        .await?;
+
+    state
+        .audit(&session, &client, "auth::session_delete", &auth.id, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn user_session_list_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<SessionModel>> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .session_service
+        .find_by_login(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_session_delete_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .session_service
+        .delete_by_login(&user_model.login, None)
+        .await?;
+
+    state
+        .audit(&session, &client, "user::sign_out", &user_model.login, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
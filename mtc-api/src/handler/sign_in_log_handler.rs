@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use tower_sessions::Session;
+
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
+use mtc_model::sign_in_log_model::{SignInLogFilterModel, SignInLogModel};
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::response_model::HandlerResult;
+use crate::repository::sign_in_log_repository::SignInLogRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::sign_in_log_service::SIGN_IN_LOG_RECENT;
+use crate::state::AppState;
+
+pub async fn sign_in_log_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<SignInLogModel>> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state
+        .sign_in_log_service
+        .find_recent(&auth.id, SIGN_IN_LOG_RECENT)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_sign_in_log_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<SignInLogModel>> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .sign_in_log_service
+        .find_recent(&user_model.login, SIGN_IN_LOG_RECENT)
+        .await?
+        .ok_model()
+}
+
+pub async fn sign_in_log_list_handler(
+    page: Option<Path<usize>>,
+    Query(filter): Query<SignInLogFilterModel>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<SignInLogModel>> {
+    session.permission("audit::read").await?;
+
+    let page: usize = match page {
+        Some(Path(value)) => value,
+        _ => 1,
+    };
+
+    let pagination = PaginationModel::new(
+        state.sign_in_log_service.get_total(&filter).await?,
+        state.cfg.rows_per_page,
+    )
+    .page(page);
+
+    state
+        .sign_in_log_service
+        .get_page(pagination.from, pagination.per_page, &filter)
+        .await?
+        .ok_page(pagination)
+}
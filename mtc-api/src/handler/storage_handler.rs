@@ -11,7 +11,9 @@ use mtc_model::storage_model::StoragesModel;
 use crate::error::api_error::ApiError;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::ClientModel;
 use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::service::audit_service::AuditTrait;
 use crate::service::storage_service::StorageTrait;
 use crate::state::AppState;
 
@@ -47,17 +49,30 @@ pub async fn storage_upload_handler(
     Path(path): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     mut multipart: Multipart,
 ) -> Result<()> {
     session.permission("storage::write").await?;
 
-    let path = state.storage_service.get_dir_path(&path);
+    let dir_path = state.storage_service.get_dir_path(&path);
 
-    state.storage_service.is_dir_exists_or_create(&path).await?;
+    state.storage_service.is_dir_exists_or_create(&dir_path).await?;
 
     while let Some(field) = multipart.next_field().await? {
         if Some("file") == field.name() && field.file_name().is_some() {
-            state.storage_service.save_file(&path, field).await?
+            let file = field.file_name().unwrap_or_default().to_string();
+            state.storage_service.save_file(&dir_path, field).await?;
+
+            state
+                .audit(
+                    &session,
+                    &client,
+                    "storage::upload",
+                    &[path.as_str(), &file].join("/"),
+                    None,
+                    None,
+                )
+                .await;
         }
     }
 
@@ -68,17 +83,30 @@ pub async fn private_storage_upload_handler(
     Path(path): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     mut multipart: Multipart,
 ) -> Result<()> {
     session.permission("private_storage::write").await?;
 
-    let path = state.storage_service.get_private_dir_path(&path);
+    let dir_path = state.storage_service.get_private_dir_path(&path);
 
-    state.storage_service.is_dir_exists_or_create(&path).await?;
+    state.storage_service.is_dir_exists_or_create(&dir_path).await?;
 
     while let Some(field) = multipart.next_field().await? {
         if Some("file") == field.name() && field.file_name().is_some() {
-            state.storage_service.save_file(&path, field).await?
+            let file = field.file_name().unwrap_or_default().to_string();
+            state.storage_service.save_file(&dir_path, field).await?;
+
+            state
+                .audit(
+                    &session,
+                    &client,
+                    "private_storage::upload",
+                    &[path.as_str(), &file].join("/"),
+                    None,
+                    None,
+                )
+                .await;
         }
     }
 
@@ -89,6 +117,7 @@ pub async fn storage_delete_handler(
     Path((path, file)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("storage::delete").await?;
 
@@ -97,6 +126,17 @@ pub async fn storage_delete_handler(
         .delete_file(&state.storage_service.get_file_path(&path, &file))
         .await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "storage::delete",
+            &[path.as_str(), &file].join("/"),
+            None,
+            None,
+        )
+        .await;
+
     Ok(ApiResponse::Ok)
 }
 
@@ -104,6 +144,7 @@ pub async fn private_storage_delete_handler(
     Path((path, file)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("private_storage::delete").await?;
 
@@ -112,6 +153,17 @@ pub async fn private_storage_delete_handler(
         .delete_file(&state.storage_service.get_private_file_path(&path, &file))
         .await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "private_storage::delete",
+            &[path.as_str(), &file].join("/"),
+            None,
+            None,
+        )
+        .await;
+
     Ok(ApiResponse::Ok)
 }
 
@@ -119,6 +171,7 @@ pub async fn private_storage_get_handler(
     Path((path, file)): Path<(String, String)>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> core::result::Result<Response, ApiError> {
     session.permission("private_storage::read").await?;
 
@@ -127,6 +180,17 @@ pub async fn private_storage_get_handler(
     let bytes = Bytes::from(tokio::fs::read(file_path).await?);
     let body = Body::from(bytes);
 
+    state
+        .audit(
+            &session,
+            &client,
+            "private_storage::read",
+            &[path.as_str(), &file].join("/"),
+            None,
+            None,
+        )
+        .await;
+
     let headers = AppendHeaders([
         (
             header::CONTENT_TYPE,
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use tower_sessions::Session;
+
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::token_model::{TokenCreateModel, TokenModel, TokenSecretModel};
+
+use crate::error::api_error::{ApiError, ToApiError};
+use crate::error::session_error::SessionError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::repository::token_repository::TokenRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::{audit_summary, AuditTrait};
+use crate::service::token_service::TokenTrait;
+use crate::state::AppState;
+
+pub async fn token_list_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<TokenModel>> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() || session.is_token().await? {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state.token_service.find_by_login(&auth.id).await?.ok_model()
+}
+
+pub async fn token_create_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<TokenCreateModel>,
+) -> Result<TokenSecretModel> {
+    let auth = session.credentials().await?;
+    // a leaked token must not be able to manage tokens, least of all to replace itself
+    if !auth.is_auth() || session.is_token().await? {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    if payload
+        .scope
+        .iter()
+        .any(|permission| permission.contains(['*', '!']) || !auth.is_permission(permission))
+    {
+        Err("Token scope exceeds the user's permissions".to_bad_request_error())?
+    }
+
+    let token = state.token_service.generate_token();
+    let model = state
+        .token_service
+        .create(&auth.id, &token, payload)
+        .await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "auth::token_create",
+            &auth.id,
+            None,
+            audit_summary(&model),
+        )
+        .await;
+
+    TokenSecretModel { token, model }.ok_model()
+}
+
+pub async fn token_delete_handler(
+    Path(id): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() || session.is_token().await? {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    state.token_service.delete(&auth.id, &id).await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "auth::token_delete",
+            &[auth.id.as_str(), &id].join("/"),
+            None,
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn user_token_list_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<Vec<TokenModel>> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .token_service
+        .find_by_login(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_token_delete_handler(
+    Path((login, id)): Path<(String, String)>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state.token_service.delete(&user_model.login, &id).await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::token_delete",
+            &[user_model.login.as_str(), &id].join("/"),
+            None,
+            None,
+        )
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
@@ -0,0 +1,311 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use chrono::Utc;
+use tower_sessions::Session;
+
+use mtc_model::auth_model::{AuthModel, AuthModelTrait};
+use mtc_model::totp_model::{TotpCodeModel, TotpRecoveryModel, TotpSetupModel, TotpStatusModel};
+
+use crate::error::api_error::{ApiError, ToApiError};
+use crate::error::session_error::SessionError;
+use crate::handler::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::access_model::AccessModel;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
+use crate::model::response_model::{ApiResponse, HandlerResult};
+use crate::model::totp_model::TwoFactorPendingModel;
+use crate::provider::config_provider::SESSION_TWO_FACTOR_KEY;
+use crate::repository::totp_repository::TotpRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
+use crate::service::auth_service::AuthTrait;
+use crate::service::sign_in_log_service::SignInLogTrait;
+use crate::service::totp_service::{TotpTrait, TOTP_MAX_ATTEMPTS};
+use crate::state::AppState;
+
+pub async fn totp_sign_in_handler(
+    state: State<Arc<AppState>>,
+    client: ClientModel,
+    session: Session,
+    ValidatedPayload(payload): ValidatedPayload<TotpCodeModel>,
+) -> Result<AuthModel> {
+    let login = session
+        .get::<TwoFactorPendingModel>(SESSION_TWO_FACTOR_KEY)
+        .await?
+        .map(|value| value.login);
+
+    let result = totp_sign_in(&state, &client, &session, &payload).await;
+    if let (Some(login), Err(ApiError::SessionError(error))) = (&login, &result) {
+        state.sign_in_log(login, &client, Some(error)).await;
+    }
+
+    result?.ok_model()
+}
+
+async fn totp_sign_in(
+    state: &AppState,
+    client: &ClientModel,
+    session: &Session,
+    payload: &TotpCodeModel,
+) -> crate::error::Result<AuthModel> {
+    let mut pending = pending_sign_in(session).await?;
+    if !pending.enrolled {
+        Err(ApiError::from(SessionError::TwoFactorEnrollment))?
+    }
+
+    let user_model = state
+        .user_service
+        .find_by_login(
+            &pending.login,
+            &AccessModel {
+                users_level: -1,
+                users_all: true,
+                groups: None,
+            },
+        )
+        .await?;
+
+    // the account may have changed since the password step
+    if let Err(e) = state.account_check(&user_model).await {
+        session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+        return Err(e);
+    }
+
+    let totp_model = state.totp_service.find_by_login(&user_model.login).await?;
+    let is_valid = match &totp_model.totp_secret {
+        Some(secret) => {
+            verify_code(state, &user_model.login, secret, &payload.code).await?
+                || state
+                    .totp_service
+                    .use_recovery_code(&user_model.login, &payload.code.trim().to_lowercase())
+                    .await?
+        }
+        None => false,
+    };
+
+    if !is_valid {
+        pending.attempts += 1;
+        match pending.attempts < TOTP_MAX_ATTEMPTS {
+            true => session.insert(SESSION_TWO_FACTOR_KEY, pending).await?,
+            false => {
+                session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+            }
+        }
+
+        state
+            .sign_in_failed(Some(&user_model.login), &client.ip)
+            .await?;
+        Err(ApiError::from(SessionError::InvalidCredentials))?
+    }
+
+    session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+
+    state.user_sign_in(session, &user_model, client).await
+}
+
+pub async fn totp_status_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<TotpStatusModel> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    TotpStatusModel {
+        enabled: state.totp_service.find_by_login(&auth.id).await?.totp_enabled,
+        required: state.totp_service.is_required(&auth.id).await?,
+    }
+    .ok_model()
+}
+
+pub async fn totp_setup_handler(
+    state: State<Arc<AppState>>,
+    client: ClientModel,
+    session: Session,
+) -> Result<TotpSetupModel> {
+    let login = enrollment_login(&session).await?;
+
+    if state.totp_service.find_by_login(&login).await?.totp_enabled {
+        Err("Two-factor authentication is already enabled".to_bad_request_error())?
+    }
+
+    let secret = state.totp_service.generate_secret();
+    state.totp_service.set_secret(&login, &secret).await?;
+
+    state
+        .audit(&session, &client, "auth::totp_setup", &login, None, None)
+        .await;
+
+    state.totp_service.setup_model(&login, &secret)?.ok_model()
+}
+
+pub async fn totp_enable_handler(
+    state: State<Arc<AppState>>,
+    client: ClientModel,
+    session: Session,
+    ValidatedPayload(payload): ValidatedPayload<TotpCodeModel>,
+) -> Result<TotpRecoveryModel> {
+    let login = enrollment_login(&session).await?;
+
+    let totp_model = state.totp_service.find_by_login(&login).await?;
+    if totp_model.totp_enabled {
+        Err("Two-factor authentication is already enabled".to_bad_request_error())?
+    }
+
+    let Some(secret) = totp_model.totp_secret else {
+        return Err("Two-factor authentication is not set up".to_bad_request_error());
+    };
+
+    if !verify_code(&state, &login, &secret, &payload.code).await? {
+        Err(ApiError::from(SessionError::InvalidCredentials))?
+    }
+
+    // enrollment forced by a role completes the pending sign in
+    let pending_user = match session.get_value(SESSION_TWO_FACTOR_KEY).await? {
+        Some(_) => {
+            let user_model = state
+                .user_service
+                .find_by_login(
+                    &login,
+                    &AccessModel {
+                        users_level: -1,
+                        users_all: true,
+                        groups: None,
+                    },
+                )
+                .await?;
+
+            // the account may have changed since the password step
+            if let Err(e) = state.account_check(&user_model).await {
+                session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+                return Err(e);
+            }
+
+            Some(user_model)
+        }
+        None => None,
+    };
+
+    let codes = state.totp_service.generate_recovery_codes();
+    state.totp_service.enable(&login, &codes).await?;
+
+    if let Some(user_model) = pending_user {
+        session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+
+        state
+            .user_sign_in(&session, &user_model, &client)
+            .await?;
+    }
+
+    state
+        .audit(&session, &client, "auth::totp_enable", &login, None, None)
+        .await;
+
+    TotpRecoveryModel { codes }.ok_model()
+}
+
+pub async fn totp_disable_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<TotpCodeModel>,
+) -> Result<()> {
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    if state.totp_service.is_required(&auth.id).await? {
+        Err("Two-factor authentication is required by the user's role".to_bad_request_error())?
+    }
+
+    let totp_model = state.totp_service.find_by_login(&auth.id).await?;
+    let is_valid = match &totp_model.totp_secret {
+        Some(secret) if totp_model.totp_enabled => {
+            verify_code(&state, &auth.id, secret, &payload.code).await?
+        }
+        _ => false,
+    };
+
+    if !is_valid {
+        Err(ApiError::from(SessionError::InvalidCredentials))?
+    }
+
+    state.totp_service.reset(&auth.id).await?;
+
+    state
+        .audit(&session, &client, "auth::totp_disable", &auth.id, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn user_totp_reset_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state.totp_service.reset(&user_model.login).await?;
+
+    state
+        .audit(&session, &client, "user::totp_reset", &user_model.login, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+async fn pending_sign_in(session: &Session) -> crate::error::Result<TwoFactorPendingModel> {
+    let pending = session
+        .get::<TwoFactorPendingModel>(SESSION_TWO_FACTOR_KEY)
+        .await?
+        .ok_or(ApiError::from(SessionError::InvalidSession))?;
+
+    if pending.expires_at < Utc::now().timestamp() {
+        session.remove_value(SESSION_TWO_FACTOR_KEY).await?;
+        Err(ApiError::from(SessionError::InvalidSession))?
+    }
+
+    Ok(pending)
+}
+
+async fn verify_code(
+    state: &AppState,
+    login: &str,
+    secret: &str,
+    code: &str,
+) -> crate::error::Result<bool> {
+    match state.totp_service.code_step(login, secret, code) {
+        Some(step) => state.totp_service.use_step(login, step).await,
+        None => Ok(false),
+    }
+}
+
+/// Signed in users enroll for themselves, otherwise the enrollment must be
+/// demanded by a pending sign in.
+async fn enrollment_login(session: &Session) -> crate::error::Result<String> {
+    if session.get_value(SESSION_TWO_FACTOR_KEY).await?.is_some() {
+        let pending = pending_sign_in(session).await?;
+        if pending.enrolled {
+            Err(ApiError::from(SessionError::TwoFactorRequired))?
+        }
+
+        return Ok(pending.login);
+    }
+
+    let auth = session.credentials().await?;
+    if !auth.is_auth() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    Ok(auth.id)
+}
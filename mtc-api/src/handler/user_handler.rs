@@ -1,26 +1,46 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{AppendHeaders, IntoResponse, Response};
+use chrono::Utc;
 use tower_sessions::Session;
+use serde_json::json;
 use tracing::{error, warn};
 
+use mtc_model::auth_model::AuthModel;
 use mtc_model::list_model::StringListModel;
 use mtc_model::pagination_model::{PaginationBuilder, PaginationModel};
 use mtc_model::user_details_model::UserDetailsStateModel;
-use mtc_model::user_model::{UserCreateModel, UserModel, UserUpdateModel};
+use mtc_model::user_model::{
+    UserCreateModel, UserFilterModel, UserModel, UserProvisionFormatModel, UserProvisionModel,
+    UserUpdateModel,
+};
 
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
 use crate::handler::Result;
 use crate::middleware::auth_middleware::UserSession;
-use crate::model::request_model::ValidatedPayload;
+use crate::model::access_model::AccessModel;
+use crate::model::request_model::{ClientModel, ValidatedPayload};
 use crate::model::response_model::{ApiResponse, HandlerResult};
 use crate::repository::group_repository::GroupRepositoryTrait;
+use crate::repository::lockout_repository::LockoutRepositoryTrait;
 use crate::repository::permissions_repository::PermissionsRepositoryTrait;
 use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
 use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::audit_service::AuditTrait;
+use crate::service::auth_service::AuthTrait;
+use crate::service::person_service::PersonTrait;
+use crate::service::provision_service::ProvisionTrait;
 use crate::state::AppState;
 
 pub async fn user_list_handler(
     page: Option<Path<usize>>,
+    Query(filter): Query<UserFilterModel>,
     state: State<Arc<AppState>>,
     session: Session,
 ) -> Result<Vec<UserModel>> {
@@ -33,14 +53,14 @@ pub async fn user_list_handler(
     };
 
     let pagination = PaginationModel::new(
-        state.user_service.get_total(&access).await?,
+        state.user_service.get_total(&access, &filter).await?,
         state.cfg.rows_per_page,
     )
     .page(page);
 
     state
         .user_service
-        .get_page(pagination.from, pagination.per_page, &access)
+        .get_page(pagination.from, pagination.per_page, &access, &filter)
         .await?
         .ok_page(pagination)
 }
@@ -64,9 +84,16 @@ pub async fn user_create_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
-    ValidatedPayload(payload): ValidatedPayload<UserCreateModel>,
+    client: ClientModel,
+    ValidatedPayload(mut payload): ValidatedPayload<UserCreateModel>,
 ) -> Result<UserModel> {
     session.permission("user::write").await?;
+    let access = session.get_access().await?;
+
+    if access.is_group_scoped() && payload.roles.is_some() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+    payload.groups = scoped_groups(&state, &access, None, payload.groups).await?;
 
     let user_model = state
         .user_service
@@ -91,6 +118,17 @@ pub async fn user_create_handler(
         .update_access_level(&user_model.login, access_level)
         .await?;
 
+    state
+        .audit(
+            &session,
+            &client,
+            "user::create",
+            &user_model.login,
+            None,
+            user_summary(&state, &user_model.login).await,
+        )
+        .await;
+
     user_model.ok_model()
 }
 
@@ -98,19 +136,36 @@ pub async fn user_update_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
-    ValidatedPayload(payload): ValidatedPayload<UserUpdateModel>,
+    client: ClientModel,
+    ValidatedPayload(mut payload): ValidatedPayload<UserUpdateModel>,
 ) -> Result<UserModel> {
     session.permission("user::write").await?;
+    let access = session.get_access().await?;
+
+    let current_model = state.user_service.find_by_login(&login, &access).await?;
+
+    // person details are kept unless the editor sends them, then they follow the profile schema
+    payload.fields = match payload.fields {
+        Some(fields) => Some(state.profile_validate(&fields).await?),
+        None => current_model.fields,
+    };
+    payload.groups = scoped_groups(&state, &access, Some(&login), payload.groups).await?;
+
+    let before = user_summary(&state, &login).await;
 
     let user_model = state
         .user_service
         .update(&session.auth_id().await?, &login, &payload)
         .await?;
-    state.user_service.roles_drop(&user_model.id).await?;
     state.user_service.groups_drop(&user_model.id).await?;
 
-    if let Some(roles) = payload.roles {
-        set_roles(&state, &user_model.id, roles).await?;
+    // roles stay out of reach of delegated group administrators
+    if !access.is_group_scoped() {
+        state.user_service.roles_drop(&user_model.id).await?;
+
+        if let Some(roles) = payload.roles {
+            set_roles(&state, &user_model.id, roles).await?;
+        }
     }
 
     if let Some(groups) = payload.groups {
@@ -127,6 +182,25 @@ pub async fn user_update_handler(
         .update_access_level(&user_model.login, access_level)
         .await?;
 
+    if user_model.blocked {
+        state
+            .session_service
+            .delete_by_login(&user_model.login, None)
+            .await?;
+    }
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::update",
+            &user_model.login,
+            before,
+            user_summary(&state, &user_model.login).await,
+        )
+        .await;
+
     user_model.ok_model()
 }
 
@@ -134,48 +208,148 @@ pub async fn user_delete_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
 ) -> Result<()> {
     session.permission("user::delete").await?;
 
-    state.user_service.delete(&login).await?.ok_ok()
+    state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+    let before = user_summary(&state, &login).await;
+
+    state.session_service.delete_by_login(&login, None).await?;
+    state.user_service.delete(&login).await?;
+
+    state
+        .audit(&session, &client, "user::delete", &login, before, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
+}
+
+pub async fn user_unlock_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<()> {
+    session.permission("user::write").await?;
+
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state.lockout_service.login_reset(&user_model.login).await?;
+
+    state
+        .audit(&session, &client, "user::unlock", &user_model.login, None, None)
+        .await;
+
+    Ok(ApiResponse::Ok)
 }
 
 pub async fn user_list_delete_handler(
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<()> {
     session.permission("user::delete").await?;
+    let access = session.get_access().await?;
 
     for item in payload.list {
+        if let Err(e) = state.user_service.find_by_login(&item, &access).await {
+            error!("User delete: {}", e.to_string());
+            continue;
+        }
+        let before = user_summary(&state, &item).await;
+
+        if let Err(e) = state.session_service.delete_by_login(&item, None).await {
+            error!("User sessions delete: {}", e.to_string())
+        }
         match state.user_service.delete(&item).await {
-            Ok(_) => (),
+            Ok(_) => {
+                state
+                    .audit(&session, &client, "user::delete", &item, before, None)
+                    .await
+            }
             Err(e) => error!("User delete: {}", e.to_string()),
         }
     }
     Ok(ApiResponse::Ok)
 }
 
+pub async fn user_impersonate_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+) -> Result<AuthModel> {
+    session.permission("user::impersonate").await?;
+
+    let auth_model = state.user_impersonate(&session, &login).await?;
+
+    state
+        .audit(&session, &client, "user::impersonate", &auth_model.id, None, None)
+        .await;
+
+    auth_model.ok_model()
+}
+
 pub async fn user_get_roles_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
 ) -> Result<StringListModel> {
     session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .role_service
+        .find_direct_by_user(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_get_inherited_roles_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<StringListModel> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
 
-    state.role_service.find_by_user(&login).await?.ok_model()
+    state
+        .role_service
+        .find_inherited_by_user(&user_model.login)
+        .await?
+        .ok_model()
 }
 
 pub async fn user_set_roles_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<StringListModel> {
     session.permission("user::write").await?;
     let access = session.get_access().await?;
 
+    if access.is_group_scoped() {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
     let user_model = state.user_service.find_by_login(&login, &access).await?;
+    let before = user_summary(&state, &user_model.login).await;
 
     state.user_service.roles_drop(&user_model.id).await?;
 
@@ -190,10 +364,22 @@ pub async fn user_set_roles_handler(
         .user_service
         .update_access_level(&user_model.login, access_level)
         .await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::roles",
+            &user_model.login,
+            before,
+            user_summary(&state, &user_model.login).await,
+        )
+        .await;
 
     state
         .role_service
-        .find_by_user(&user_model.login)
+        .find_direct_by_user(&user_model.login)
         .await?
         .ok_model()
 }
@@ -204,10 +390,32 @@ pub async fn user_get_permissions_handler(
     session: Session,
 ) -> Result<StringListModel> {
     session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
 
     state
         .permissions_service
-        .find_by_user(&login)
+        .find_by_user(&user_model.login)
+        .await?
+        .ok_model()
+}
+
+pub async fn user_get_effective_permissions_handler(
+    Path(login): Path<String>,
+    state: State<Arc<AppState>>,
+    session: Session,
+) -> Result<StringListModel> {
+    session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
+
+    state
+        .permissions_service
+        .find_effective_by_user(&user_model.login)
         .await?
         .ok_model()
 }
@@ -218,30 +426,168 @@ pub async fn user_get_groups_handler(
     session: Session,
 ) -> Result<StringListModel> {
     session.permission("user::read").await?;
+    let user_model = state
+        .user_service
+        .find_by_login(&login, &session.get_access().await?)
+        .await?;
 
-    state.group_service.find_by_user(&login).await?.ok_model()
+    state
+        .group_service
+        .find_by_user(&user_model.login)
+        .await?
+        .ok_model()
 }
 
 pub async fn user_set_groups_handler(
     Path(login): Path<String>,
     state: State<Arc<AppState>>,
     session: Session,
+    client: ClientModel,
     ValidatedPayload(payload): ValidatedPayload<StringListModel>,
 ) -> Result<StringListModel> {
     session.permission("user::write").await?;
     let access = session.get_access().await?;
 
     let user_model = state.user_service.find_by_login(&login, &access).await?;
+    let before = user_summary(&state, &user_model.login).await;
+    let groups = scoped_groups(&state, &access, Some(&user_model.login), Some(payload.list))
+        .await?
+        .unwrap_or_default();
 
     state.user_service.groups_drop(&user_model.id).await?;
 
-    set_groups(&state, &user_model.id, payload.list).await?;
+    set_groups(&state, &user_model.id, groups).await?;
 
-    state.group_service.find_by_user(&login).await?.ok_model()
+    // groups may carry roles, so the access level follows the membership
+    let access_level = state
+        .user_service
+        .get_roles_max_access_level(&user_model.login)
+        .await
+        .unwrap_or(999);
+    state
+        .user_service
+        .update_access_level(&user_model.login, access_level)
+        .await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::groups",
+            &user_model.login,
+            before,
+            user_summary(&state, &user_model.login).await,
+        )
+        .await;
+
+    state
+        .group_service
+        .find_by_user(&user_model.login)
+        .await?
+        .ok_model()
 }
 
 // coroutine
 
+async fn user_summary(state: &Arc<AppState>, login: &str) -> Option<String> {
+    let user_model = state
+        .user_service
+        .find_by_login(
+            login,
+            &AccessModel {
+                users_level: -1,
+                users_all: true,
+                groups: None,
+            },
+        )
+        .await
+        .ok()?;
+
+    Some(
+        json!({
+            "login": user_model.login,
+            "blocked": user_model.blocked,
+            "valid_from": user_model.valid_from,
+            "valid_until": user_model.valid_until,
+            "roles": state.role_service.find_direct_by_user(login).await.unwrap_or_default().list,
+            "groups": state.group_service.find_by_user(login).await.unwrap_or_default().list,
+        })
+        .to_string(),
+    )
+}
+
+pub async fn user_provision_handler(
+    state: State<Arc<AppState>>,
+    session: Session,
+    client: ClientModel,
+    ValidatedPayload(payload): ValidatedPayload<UserProvisionModel>,
+) -> core::result::Result<Response, ApiError> {
+    session.permission("user::write").await?;
+    let access = session.get_access().await?;
+
+    if (access.is_group_scoped() && !payload.roles.is_empty())
+        || !access.is_groups_allowed(&[payload.group.clone()])
+    {
+        Err(ApiError::from(SessionError::AccessForbidden))?
+    }
+
+    let credentials = state
+        .user_provision(&session.auth_id().await?, &payload)
+        .await?;
+    state.system_service.increment_auth_epoch().await?;
+
+    state
+        .audit(
+            &session,
+            &client,
+            "user::provision",
+            &payload.group,
+            None,
+            serde_json::to_string(
+                &credentials
+                    .iter()
+                    .map(|item| item.login.as_str())
+                    .collect::<Vec<&str>>(),
+            )
+            .ok(),
+        )
+        .await;
+
+    let (content_type, extension, body) = match payload.format {
+        UserProvisionFormatModel::Json => return Ok(ApiResponse::Data(credentials).into_response()),
+        UserProvisionFormatModel::Csv => (
+            "text/csv; charset=utf-8",
+            "csv",
+            state.credentials_csv(&credentials, &state.profile_fields().await?),
+        ),
+        UserProvisionFormatModel::Html => (
+            "text/html; charset=utf-8",
+            "html",
+            state.credentials_html(
+                &state.group_service.find_by_slug(&payload.group).await?,
+                &credentials,
+                &state.profile_fields().await?,
+            ),
+        ),
+    };
+
+    let headers = AppendHeaders([
+        (header::CONTENT_TYPE, content_type.to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"credentials-{}-{}.{}\"",
+                payload.group,
+                Utc::now().format("%Y%m%d%H%M%S"),
+                extension
+            ),
+        ),
+    ]);
+
+    Ok((headers, Body::from(body)).into_response())
+}
+
 async fn set_roles(state: &Arc<AppState>, user_id: &str, roles: Vec<String>) -> Result<()> {
     for role in roles {
         match state.role_service.find_by_slug(&role).await {
@@ -253,6 +599,38 @@ async fn set_roles(state: &Arc<AppState>, user_id: &str, roles: Vec<String>) ->
     Ok(ApiResponse::Ok)
 }
 
+/// Delegated group administrators may only place users into the groups they manage,
+/// memberships elsewhere are kept as they are.
+async fn scoped_groups(
+    state: &Arc<AppState>,
+    access: &AccessModel,
+    login: Option<&str>,
+    groups: Option<Vec<String>>,
+) -> core::result::Result<Option<Vec<String>>, ApiError> {
+    let Some(scope) = &access.groups else {
+        return Ok(groups);
+    };
+
+    let groups = groups.unwrap_or_default();
+    if !access.is_groups_allowed(&groups) {
+        return Err(ApiError::from(SessionError::AccessForbidden));
+    }
+
+    let kept = match login {
+        Some(login) => state
+            .group_service
+            .find_by_user(login)
+            .await?
+            .list
+            .into_iter()
+            .filter(|group| !scope.contains(group))
+            .collect(),
+        None => vec![],
+    };
+
+    Ok(Some(groups.into_iter().chain(kept).collect()))
+}
+
 async fn set_groups(state: &Arc<AppState>, user_id: &str, groups: Vec<String>) -> Result<()> {
     for group in groups {
         match state.group_service.find_by_slug(&group).await {
@@ -3,13 +3,11 @@
 
 use std::future::Future;
 use std::net::SocketAddr;
-use std::path::PathBuf;
 use std::process::ExitCode;
 use std::sync::Arc;
 
 use axum::extract::DefaultBodyLimit;
 use axum::Router;
-use axum_server::tls_rustls::RustlsConfig;
 use tower::ServiceBuilder;
 use tower_http::services::{ServeDir, ServeFile};
 use tower_sessions::{ExpiredDeletion, Expiry, SessionManagerLayer};
@@ -24,10 +22,16 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
+use crate::cli::cli;
 use crate::provider::config_provider::{Config, RUNTIME_MAX_BLOCKING_THREADS, RUNTIME_STACK_SIZE};
 use crate::provider::database_provider::DatabaseProvider;
 use crate::provider::redirect_provider::redirect_http_to_https;
+use crate::provider::tls_provider::{tls_config, ClientCertificateAcceptor};
+use crate::repository::audit_repository::AuditRepositoryTrait;
+use crate::repository::sign_in_log_repository::SignInLogRepositoryTrait;
 use crate::routes::routes;
+use crate::service::auth_service::AuthTrait;
+use crate::service::session_service::SESSION_STORE_TABLE;
 use crate::state::AppState;
 
 mod state;
@@ -39,6 +43,7 @@ mod service;
 mod middleware;
 mod routes;
 mod handler;
+mod cli;
 
 fn main() -> ExitCode {
     match with_enough_stack(app()) {
@@ -79,12 +84,42 @@ async fn app() -> Result<(), Box<dyn std::error::Error>> {
     let db = DatabaseProvider::init(&config).await?;
     info!("\x1b[38;5;6mConnection to the database is successful!\x1b[0m");
 
-    let session_store = SurrealSessionStore::new(db.clone(), "sessions".to_string());
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if !args.is_empty() {
+        return cli(&AppState::new(config, db), &args).await;
+    }
+
+    let session_store = SurrealSessionStore::new(db.clone(), SESSION_STORE_TABLE.to_string());
     tokio::task::spawn(session_store.clone().continuously_delete_expired(
         tokio::time::Duration::from_secs(60 * 10),
     ));
     let state = Arc::new(AppState::new(config.clone(), db));
 
+    let audit_state = Arc::clone(&state);
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = audit_state.audit_service.delete_expired().await {
+                error!("Audit log retention: {}", e.to_string())
+            }
+            if let Err(e) = audit_state.sign_in_log_service.delete_expired().await {
+                error!("Sign-in log retention: {}", e.to_string())
+            }
+        }
+    });
+
+    let expiry_state = Arc::clone(&state);
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60 * 10));
+        loop {
+            interval.tick().await;
+            if let Err(e) = expiry_state.block_expired_users().await {
+                error!("Account validity: {}", e.to_string())
+            }
+        }
+    });
+
     let session_service = ServiceBuilder::new().layer(
         SessionManagerLayer::new(session_store)
             .with_name("mtc-api.sid")
@@ -97,13 +132,7 @@ async fn app() -> Result<(), Box<dyn std::error::Error>> {
         config.https_port.clone(),
     )));
 
-    let tls_config = RustlsConfig::from_pem_file(
-        PathBuf::from(&config.cert_path)
-            .join("cert.pem"),
-        PathBuf::from(&config.cert_path)
-            .join("key.pem"),
-    )
-        .await?;
+    let tls_config = tls_config(&config)?;
 
     let fallback_service =
         ServeDir::new(&config.public_path)
@@ -122,8 +151,9 @@ async fn app() -> Result<(), Box<dyn std::error::Error>> {
 
     let https_host: SocketAddr = format!("{}:{}", &config.host, &config.https_port)
         .parse().expect("Unable to parse socket address");
-    axum_server::bind_rustls(https_host, tls_config)
-        .serve(app.into_make_service()).await?;
+    axum_server::bind(https_host)
+        .acceptor(ClientCertificateAcceptor::new(tls_config))
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
@@ -1,7 +1,10 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::async_trait;
-use axum::extract::{Request, State};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, Method};
 use axum::middleware::Next;
 use axum::response::IntoResponse;
 use tower_sessions::Session;
@@ -12,17 +15,65 @@ use mtc_model::list_model::StringListModel;
 use crate::error::api_error::ApiError;
 use crate::error::session_error::SessionError;
 use crate::error::Result;
-use crate::model::access_model::AccessModel;
-use crate::provider::config_provider::{SESSION_ACCESS_KEY, SESSION_USER_KEY};
+use crate::model::access_model::{AccessModel, AudienceModel};
+use crate::provider::config_provider::{
+    AUTH_CHANGED_HEADER, IMPERSONATION_ROUTES, PASSWORD_CHANGE_ROUTES, SESSION_ACCESS_KEY,
+    SESSION_EPOCH_KEY, SESSION_TOKEN_KEY, SESSION_USER_KEY,
+};
+use crate::repository::lockout_repository::LockoutRepositoryTrait;
 use crate::repository::permissions_repository::PermissionsRepositoryTrait;
+use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::repository::token_repository::TokenRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::auth_service::AuthTrait;
+use crate::service::user_service::AccountTrait;
 use crate::state::AppState;
 
 pub async fn middleware_auth_handler(
     state: State<Arc<AppState>>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
     session: Session,
     req: Request,
     next: Next,
 ) -> Result<impl IntoResponse> {
+    let bearer = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|value| value.trim().to_string());
+
+    if let Some(token) = bearer {
+        if !session.is_empty().await {
+            Err(ApiError::from(SessionError::InvalidSession))?
+        }
+
+        session.token(&state, &token, &address).await?;
+        let response = next.run(req).await;
+
+        // token credentials live for a single request and must never reach the session store
+        session.clear().await;
+
+        return Ok(response);
+    }
+
+    // sessions revoked remotely lose their registry entry and fall back to anonymous
+    if let Some(id) = session.id() {
+        if session
+            .credentials()
+            .await
+            .is_ok_and(|auth| auth.is_auth())
+            && state
+                .session_service
+                .touch(&id.to_string())
+                .await?
+                .is_none()
+        {
+            session.flush().await?;
+        }
+    }
+
     if session.is_empty().await {
         let anon_user = session.anonymous(&state).await?;
 
@@ -30,9 +81,44 @@ pub async fn middleware_auth_handler(
             Ok(_) => (),
             _ => Err(ApiError::from(SessionError::InvalidSession))?,
         };
+        session
+            .insert(SESSION_EPOCH_KEY, state.system_service.get_auth_epoch().await?)
+            .await?;
+    }
+
+    let is_refreshed = session.refresh(&state).await?;
+
+    // a session signed in with a password which must be changed may only change it or sign out
+    if session
+        .credentials()
+        .await
+        .is_ok_and(|auth| auth.password_change)
+        && !PASSWORD_CHANGE_ROUTES.contains(&req.uri().path())
+    {
+        Err(ApiError::from(SessionError::PasswordChangeRequired))?
+    }
+
+    // an impersonated session is for looking only, nothing is changed on the user's behalf
+    if session
+        .credentials()
+        .await
+        .is_ok_and(|auth| auth.impersonator.is_some())
+        && req.method() != Method::GET
+        && !IMPERSONATION_ROUTES.contains(&req.uri().path())
+    {
+        Err(ApiError::from(SessionError::ImpersonationReadOnly))?
+    }
+
+    let mut response = next.run(req).await;
+
+    // lets the client know its cached credentials are outdated
+    if is_refreshed {
+        response
+            .headers_mut()
+            .insert(AUTH_CHANGED_HEADER, HeaderValue::from_static("true"));
     }
 
-    Ok(next.run(req).await)
+    Ok(response)
 }
 
 #[async_trait]
@@ -40,13 +126,23 @@ pub trait UserSession {
     async fn sign_in(&self, auth: AuthModel) -> Result<()>;
     async fn credentials(&self) -> Result<AuthModel>;
     async fn anonymous(&self, state: &State<Arc<AppState>>) -> Result<AuthModel>;
+    async fn token(
+        &self,
+        state: &State<Arc<AppState>>,
+        token: &str,
+        address: &SocketAddr,
+    ) -> Result<()>;
+    async fn refresh(&self, state: &State<Arc<AppState>>) -> Result<bool>;
     async fn role(&self, slug: &str) -> Result<()>;
     async fn group(&self, slug: &str) -> Result<()>;
     async fn permission(&self, slug: &str) -> Result<()>;
+    async fn permission_own(&self, slug: &str, own_slugs: &[String], owner: &str) -> Result<()>;
     async fn auth_id(&self) -> Result<String>;
     async fn is_admin(&self) -> Result<bool>;
+    async fn is_token(&self) -> Result<bool>;
     async fn set_access(&self, access: AccessModel) -> Result<()>;
     async fn get_access(&self) -> Result<AccessModel>;
+    async fn audience(&self) -> Result<AudienceModel>;
 }
 
 #[async_trait]
@@ -74,9 +170,99 @@ impl UserSession for Session {
                     list: vec!["content::read".to_string()],
                 })
                 .list,
+            password_change: false,
+            impersonator: None,
         })
     }
 
+    async fn token(
+        &self,
+        state: &State<Arc<AppState>>,
+        token: &str,
+        address: &SocketAddr,
+    ) -> Result<()> {
+        let token_model = match state.token_service.find_by_token(token).await {
+            Ok(value) => value,
+            _ => Err(ApiError::from(SessionError::InvalidCredentials))?,
+        };
+
+        let user_model = state
+            .user_service
+            .find_by_login(
+                &token_model.login,
+                &AccessModel {
+                    users_level: -1,
+                    users_all: true,
+                    groups: None,
+                },
+            )
+            .await?;
+
+        if user_model.blocked {
+            Err(ApiError::from(SessionError::UserBlocked))?
+        }
+
+        if !state.user_service.is_account_valid(&user_model) {
+            Err(ApiError::from(SessionError::AccountInactive))?
+        }
+
+        if state
+            .lockout_service
+            .is_login_locked(&user_model.login)
+            .await?
+        {
+            Err(ApiError::from(SessionError::AccountLocked))?
+        }
+
+        // a token can't change the password, so it waits until the owner does it with a session
+        if state.user_service.is_password_change_required(&user_model) {
+            Err(ApiError::from(SessionError::PasswordChangeRequired))?
+        }
+
+        state
+            .token_service
+            .update_usage(&token_model.id, &address.ip().to_string())
+            .await?;
+
+        // the scope lists concrete permissions, the grants behind them may be rules
+        let mut auth_model = state.user_credentials(&user_model.login).await?;
+        auth_model.permissions = token_model
+            .scope
+            .iter()
+            .filter(|permission| auth_model.is_permission(permission))
+            .cloned()
+            .collect();
+
+        self.set_access(state.user_access(&user_model.login).await?)
+            .await?;
+        self.insert(SESSION_TOKEN_KEY, true).await?;
+        self.sign_in(auth_model).await
+    }
+
+    async fn refresh(&self, state: &State<Arc<AppState>>) -> Result<bool> {
+        let epoch = state.system_service.get_auth_epoch().await?;
+        if self.get::<i64>(SESSION_EPOCH_KEY).await? == Some(epoch) {
+            return Ok(false);
+        }
+
+        let auth = self.credentials().await?;
+        let auth_model = match auth.is_auth() {
+            true if auth.password_change => auth,
+            true => {
+                self.set_access(state.user_access(&auth.id).await?).await?;
+                let mut auth_model = state.user_credentials(&auth.id).await?;
+                auth_model.impersonator = auth.impersonator;
+                auth_model
+            }
+            false => self.anonymous(state).await?,
+        };
+
+        self.sign_in(auth_model).await?;
+        self.insert(SESSION_EPOCH_KEY, epoch).await?;
+
+        Ok(true)
+    }
+
     async fn role(&self, slug: &str) -> Result<()> {
         match self
             .get::<AuthModel>(SESSION_USER_KEY)
@@ -113,6 +299,20 @@ impl UserSession for Session {
         }
     }
 
+    async fn permission_own(&self, slug: &str, own_slugs: &[String], owner: &str) -> Result<()> {
+        let auth = self
+            .get::<AuthModel>(SESSION_USER_KEY)
+            .await?
+            .ok_or(ApiError::from(SessionError::InvalidSession))?;
+
+        match auth.is_permission(slug)
+            || (auth.id.eq(owner) && own_slugs.iter().any(|own_slug| auth.is_permission(own_slug)))
+        {
+            true => Ok(()),
+            _ => Err(ApiError::from(SessionError::AccessForbidden)),
+        }
+    }
+
     async fn auth_id(&self) -> Result<String> {
         Ok(self
             .get::<AuthModel>(SESSION_USER_KEY)
@@ -129,6 +329,10 @@ impl UserSession for Session {
             .is_admin())
     }
 
+    async fn is_token(&self) -> Result<bool> {
+        Ok(self.get::<bool>(SESSION_TOKEN_KEY).await?.unwrap_or(false))
+    }
+
     async fn set_access(&self, access: AccessModel) -> Result<()> {
         Ok(self.insert(SESSION_ACCESS_KEY, access).await?)
     }
@@ -139,4 +343,16 @@ impl UserSession for Session {
             .await?
             .ok_or(ApiError::from(SessionError::InvalidSession))?)
     }
+
+    async fn audience(&self) -> Result<AudienceModel> {
+        let auth = self
+            .get::<AuthModel>(SESSION_USER_KEY)
+            .await?
+            .ok_or(ApiError::from(SessionError::InvalidSession))?;
+
+        Ok(AudienceModel {
+            all: auth.is_admin(),
+            groups: auth.groups,
+        })
+    }
 }
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 pub struct AccessModel {
     pub users_level: i32,
     pub users_all: bool,
+    /// Set for delegated group administrators: the managed groups with all their subgroups.
+    #[serde(default)]
+    pub groups: Option<Vec<String>>,
 }
 
 impl Default for AccessModel {
@@ -11,6 +14,27 @@ impl Default for AccessModel {
         Self {
             users_level: 999,
             users_all: false,
+            groups: None,
         }
     }
 }
+
+impl AccessModel {
+    pub fn is_group_scoped(&self) -> bool {
+        self.groups.is_some()
+    }
+
+    /// A scoped administrator may only place users into the groups they manage, and into one at least.
+    pub fn is_groups_allowed(&self, groups: &[String]) -> bool {
+        match &self.groups {
+            Some(scope) => !groups.is_empty() && groups.iter().all(|group| scope.contains(group)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct AudienceModel {
+    pub groups: Vec<String>,
+    pub all: bool,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AuditEntryModel {
+    pub actor: String,
+    pub action: String,
+    pub target: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub ip: String,
+}
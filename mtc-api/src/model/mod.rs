@@ -1,3 +1,7 @@
 pub mod response_model;
 pub mod request_model;
 pub mod access_model;
+pub mod totp_model;
+pub mod audit_model;
+pub mod sign_in_log_model;
+pub mod oidc_model;
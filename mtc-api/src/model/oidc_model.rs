@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Kept in a private cookie between the redirect to the identity provider and the callback.
+#[derive(Serialize, Deserialize)]
+pub struct OidcPendingModel {
+    pub state: String,
+    pub nonce: String,
+    pub verifier: String,
+    pub link: Option<String>,
+    pub expires_at: i64,
+}
+
+#[derive(Deserialize)]
+pub struct OidcDiscoveryModel {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+pub struct OidcTokenModel {
+    pub id_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct OidcCallbackModel {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct OidcClaimsModel {
+    pub iss: String,
+    pub sub: String,
+    pub nonce: Option<String>,
+    #[serde(flatten)]
+    pub claims: Map<String, Value>,
+}
@@ -1,11 +1,16 @@
+use std::net::SocketAddr;
+
 use axum::{async_trait, Form, Json};
-use axum::extract::{FromRequest, Request};
+use axum::extract::{ConnectInfo, FromRequest, FromRequestParts, Request};
 use axum::extract::rejection::{FormRejection, JsonRejection};
-use axum::http::header::CONTENT_TYPE;
+use axum::http::header::{CONTENT_TYPE, USER_AGENT};
+use axum::http::request::Parts;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use validator::Validate;
 
+use mtc_model::certificate_model::ClientCertificateModel;
+
 use crate::error::api_error::ApiError;
 use crate::error::generic_error::GenericError;
 
@@ -44,6 +49,48 @@ impl<T, S> FromRequest<S> for ValidatedPayload<T>
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct ClientModel {
+    pub ip: String,
+    pub user_agent: String,
+    pub certificate: Option<ClientCertificateModel>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientModel
+    where
+        S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ip = ConnectInfo::<SocketAddr>::from_request_parts(parts, state)
+            .await
+            .map(|ConnectInfo(address)| address.ip().to_string())
+            .unwrap_or_default();
+
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        // only set when the TLS handshake verified a certificate against the configured CA
+        let certificate = parts
+            .extensions
+            .get::<Option<ClientCertificateModel>>()
+            .cloned()
+            .flatten();
+
+        Ok(Self {
+            ip,
+            user_agent,
+            certificate,
+        })
+    }
+}
+
 #[derive(Deserialize)]
 pub struct ApiPageRequest {
     pub api: String,
@@ -2,6 +2,7 @@ use axum::http::StatusCode;
 use axum::Json;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
+use serde_json::Value;
 
 use mtc_model::pagination_model::PaginationModel;
 
@@ -63,11 +64,17 @@ pub struct ApiErrorResponse {
     message: Option<String>,
     #[serde(rename = "code")]
     status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<Value>,
 }
 
 impl ApiErrorResponse {
     pub fn send(status: u16, message: Option<String>) -> Response {
-        ApiErrorResponse { message, status }.into_response()
+        ApiErrorResponse { message, status, details: None }.into_response()
+    }
+
+    pub fn send_details(status: u16, message: Option<String>, details: Value) -> Response {
+        ApiErrorResponse { message, status, details: Some(details) }.into_response()
     }
 }
 
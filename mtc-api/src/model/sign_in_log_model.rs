@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SignInLogEntryModel {
+    pub login: String,
+    pub success: bool,
+    pub reason: Option<String>,
+    pub ip: String,
+    pub user_agent: String,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Deserialize)]
+pub struct TotpModel {
+    pub totp_secret: Option<String>,
+    #[serde(default)]
+    pub totp_enabled: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TwoFactorPendingModel {
+    pub login: String,
+    pub enrolled: bool,
+    pub attempts: u8,
+    pub expires_at: i64,
+}
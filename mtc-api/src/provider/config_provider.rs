@@ -11,6 +11,40 @@ pub struct Config {
     pub front_end_url: String,
 
     pub password_salt: String,
+    pub password_memory_cost: u32,
+    pub password_time_cost: u32,
+    pub password_parallelism: u32,
+    pub password_min_length: usize,
+    pub password_min_classes: usize,
+    pub password_expiration: i64,
+
+    pub login_max_attempts: i32,
+    pub login_ip_max_attempts: i32,
+    pub login_attempts_window: i64,
+    pub login_lockout: i64,
+    pub login_delay: u64,
+    pub login_max_delay: u64,
+
+    pub audit_retention: i64,
+    pub sign_in_retention: i64,
+
+    pub mtls_ca_path: String,
+    pub mtls_cn_login: bool,
+    pub mtls_password_level: i32,
+
+    pub oidc_issuer: String,
+    pub oidc_name: String,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: String,
+    pub oidc_redirect_url: String,
+    pub oidc_scopes: String,
+    pub oidc_login_claim: String,
+    pub oidc_roles_claim: String,
+    pub oidc_groups_claim: String,
+    pub oidc_role_map: Vec<(String, String)>,
+    pub oidc_group_map: Vec<(String, String)>,
+    pub oidc_create_users: bool,
+    pub oidc_link_by_login: bool,
 
     pub public_path: String,
     pub storage_path: String,
@@ -38,6 +72,19 @@ pub static RUNTIME_MAX_BLOCKING_THREADS: usize = 512;
 
 pub const SESSION_USER_KEY: &str = "user";
 pub const SESSION_ACCESS_KEY: &str = "access";
+pub const SESSION_TWO_FACTOR_KEY: &str = "two_factor";
+pub const SESSION_EPOCH_KEY: &str = "auth_epoch";
+pub const SESSION_TOKEN_KEY: &str = "token";
+
+pub const PASSWORD_CHANGE_ROUTES: [&str; 3] = ["/auth", "/auth/change", "/health"];
+pub const IMPERSONATION_ROUTES: [&str; 3] = ["/auth", "/auth/impersonate", "/health"];
+
+pub const AUTH_CHANGED_HEADER: &str = "x-auth-changed";
+
+pub const OIDC_PENDING_COOKIE: &str = "mtc-api.oidc";
+pub const OIDC_PENDING_PATH: &str = "/api/auth/oidc/callback";
+pub const OIDC_RESULT_COOKIE: &str = "mtc-api.oidc_result";
+pub const OIDC_RESULT_PATH: &str = "/api/auth/oidc";
 
 impl Config {
     pub fn init() -> Config {
@@ -48,6 +95,100 @@ impl Config {
             http_port: get_env("HTTP_PORT"),
             https_port: get_env("HTTPS_PORT"),
             password_salt: get_env("PASSWORD_SALT"),
+            password_memory_cost: get_env_or("PASSWORD_MEMORY_COST", "19456")
+                .trim()
+                .parse::<u32>()
+                .unwrap_or(19456),
+            password_time_cost: get_env_or("PASSWORD_TIME_COST", "2")
+                .trim()
+                .parse::<u32>()
+                .unwrap_or(2),
+            password_parallelism: get_env_or("PASSWORD_PARALLELISM", "1")
+                .trim()
+                .parse::<u32>()
+                .unwrap_or(1),
+            password_min_length: get_env_or("PASSWORD_MIN_LENGTH", "8")
+                .trim()
+                .parse::<usize>()
+                .unwrap_or(8),
+            password_min_classes: get_env_or("PASSWORD_MIN_CLASSES", "3")
+                .trim()
+                .parse::<usize>()
+                .unwrap_or(3),
+            password_expiration: get_env_or("PASSWORD_EXPIRATION_IN_DAYS", "0")
+                .trim()
+                .parse::<i64>()
+                .unwrap_or(0),
+            login_max_attempts: get_env_or("LOGIN_MAX_ATTEMPTS", "5")
+                .trim()
+                .parse::<i32>()
+                .unwrap_or(5),
+            login_ip_max_attempts: get_env_or("LOGIN_IP_MAX_ATTEMPTS", "20")
+                .trim()
+                .parse::<i32>()
+                .unwrap_or(20),
+            login_attempts_window: get_env_or("LOGIN_ATTEMPTS_WINDOW_IN_MINUTES", "15")
+                .trim()
+                .parse::<i64>()
+                .unwrap_or(15),
+            login_lockout: get_env_or("LOGIN_LOCKOUT_IN_MINUTES", "15")
+                .trim()
+                .parse::<i64>()
+                .unwrap_or(15),
+            login_delay: get_env_or("LOGIN_DELAY_IN_MS", "250")
+                .trim()
+                .parse::<u64>()
+                .unwrap_or(250),
+            login_max_delay: get_env_or("LOGIN_MAX_DELAY_IN_MS", "4000")
+                .trim()
+                .parse::<u64>()
+                .unwrap_or(4000),
+            audit_retention: get_env_or("AUDIT_RETENTION_IN_DAYS", "365")
+                .trim()
+                .parse::<i64>()
+                .unwrap_or(365),
+            sign_in_retention: get_env_or("SIGN_IN_RETENTION_IN_DAYS", "90")
+                .trim()
+                .parse::<i64>()
+                .unwrap_or(90),
+            mtls_ca_path: get_env_or("MTLS_CA_PATH", "").trim().to_string(),
+            mtls_cn_login: get_env_or("MTLS_CN_LOGIN", "false")
+                .trim()
+                .parse::<bool>()
+                .unwrap_or(false),
+            mtls_password_level: get_env_or("MTLS_PASSWORD_ACCESS_LEVEL", "-1")
+                .trim()
+                .parse::<i32>()
+                .unwrap_or(-1),
+            oidc_issuer: get_env_or("OIDC_ISSUER", "")
+                .trim()
+                .trim_end_matches('/')
+                .to_string(),
+            oidc_name: get_env_or("OIDC_NAME", "SSO"),
+            oidc_client_id: get_env_or("OIDC_CLIENT_ID", ""),
+            oidc_client_secret: get_env_or("OIDC_CLIENT_SECRET", ""),
+            oidc_redirect_url: get_env_or(
+                "OIDC_REDIRECT_URL",
+                &format!(
+                    "https://{}:{}/api/auth/oidc/callback",
+                    get_env("FRONT_END_URL"),
+                    get_env("HTTPS_PORT")
+                ),
+            ),
+            oidc_scopes: get_env_or("OIDC_SCOPES", "openid profile"),
+            oidc_login_claim: get_env_or("OIDC_LOGIN_CLAIM", "preferred_username"),
+            oidc_roles_claim: get_env_or("OIDC_ROLES_CLAIM", "roles"),
+            oidc_groups_claim: get_env_or("OIDC_GROUPS_CLAIM", "groups"),
+            oidc_role_map: get_env_map("OIDC_ROLE_MAP"),
+            oidc_group_map: get_env_map("OIDC_GROUP_MAP"),
+            oidc_create_users: get_env_or("OIDC_CREATE_USERS", "false")
+                .trim()
+                .parse::<bool>()
+                .unwrap_or(false),
+            oidc_link_by_login: get_env_or("OIDC_LINK_BY_LOGIN", "false")
+                .trim()
+                .parse::<bool>()
+                .unwrap_or(false),
             db_path: get_env("DB_PATH"),
             db_namespace: get_env("DB_NAMESPACE"),
             db_name: get_env("DB_NAME"),
@@ -80,3 +221,46 @@ fn get_env(name: &str) -> String {
         .map_err(|_| error!("ENV missing: {name}"))
         .unwrap()
 }
+
+fn get_env_or(name: &str, default: &str) -> String {
+    env::var(name).unwrap_or(default.to_string())
+}
+
+/// Reads `claim_value=slug` pairs separated by commas, malformed pairs are skipped.
+fn get_env_map(name: &str) -> Vec<(String, String)> {
+    get_env_or(name, "")
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(claim, slug)| (claim.trim().to_string(), slug.trim().to_string()))
+        .filter(|(claim, slug)| !claim.is_empty() && !slug.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_map_pairs() {
+        env::set_var(
+            "MTC_TEST_ENV_MAP",
+            " admins = administrator ,editors=writer,broken,=empty,missing=, a=b=c ",
+        );
+
+        assert_eq!(
+            get_env_map("MTC_TEST_ENV_MAP"),
+            vec![
+                ("admins".to_string(), "administrator".to_string()),
+                ("editors".to_string(), "writer".to_string()),
+                ("a".to_string(), "b=c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn env_map_missing() {
+        env::remove_var("MTC_TEST_ENV_MAP_MISSING");
+
+        assert!(get_env_map("MTC_TEST_ENV_MAP_MISSING").is_empty());
+    }
+}
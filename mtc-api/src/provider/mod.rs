@@ -1,3 +1,3 @@
 pub mod config_provider;
 pub mod database_provider;
-pub mod redirect_provider;
\ No newline at end of file
+pub mod redirect_provider;pub mod tls_provider;
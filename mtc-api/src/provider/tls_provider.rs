@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::future::Future;
+use std::io;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::middleware::AddExtension;
+use axum::Extension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
+use tracing::log::info;
+
+use mtc_model::certificate_model::ClientCertificateModel;
+
+use crate::provider::config_provider::Config;
+
+/// The server certificate from `CERT_PATH`, with optional client certificates checked
+/// against the `MTLS_CA_PATH` bundle.
+pub fn tls_config(config: &Config) -> io::Result<RustlsConfig> {
+    let cert_path = PathBuf::from(&config.cert_path);
+    let certs = read_certs(&cert_path.join("cert.pem"))?;
+    let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(
+        cert_path.join("key.pem"),
+    )?))?
+    .ok_or(io::Error::new(io::ErrorKind::InvalidData, "private key is missing"))?;
+
+    // the identity provider client links another crypto backend, so the server one is named explicitly
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    let builder = ServerConfig::builder();
+    let builder = match config.mtls_ca_path.is_empty() {
+        true => builder.with_no_client_auth(),
+        false => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(Path::new(&config.mtls_ca_path))? {
+                roots.add(cert).map_err(invalid_data)?;
+            }
+
+            info!("\x1b[38;5;6mClient certificates are accepted from: \x1b[38;5;13m{}\x1b[0m", &config.mtls_ca_path);
+
+            // a certificate is offered, not demanded, the password sign in keeps working
+            builder.with_client_cert_verifier(
+                WebPkiClientVerifier::builder(Arc::new(roots))
+                    .allow_unauthenticated()
+                    .build()
+                    .map_err(invalid_data)?,
+            )
+        }
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(invalid_data)?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(RustlsConfig::from_config(Arc::new(server_config)))
+}
+
+/// Terminates TLS like `RustlsAcceptor` and hands the verified client certificate,
+/// if any, to every request of the connection.
+#[derive(Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        Self {
+            inner: RustlsAcceptor::new(config),
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, Option<ClientCertificateModel>>;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, Self::Service)>> + Send>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let certificate = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| client_certificate(cert.as_ref()));
+
+            Ok((stream, Extension(certificate).layer(service)))
+        })
+    }
+}
+
+fn client_certificate(der: &[u8]) -> Option<ClientCertificateModel> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+
+    Some(ClientCertificateModel {
+        subject: cert.subject().to_string(),
+        common_name: cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|value| value.as_str().ok())
+            .map(|value| value.to_string()),
+        serial: cert
+            .raw_serial()
+            .iter()
+            .map(|value| format!("{value:02X}"))
+            .collect(),
+    })
+}
+
+fn read_certs(path: &Path) -> io::Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    rustls_pemfile::certs(&mut BufReader::new(File::open(path)?)).collect()
+}
+
+fn invalid_data<E: std::error::Error + Send + Sync + 'static>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
@@ -5,12 +5,25 @@ use mtc_model::pagination_model::CountModel;
 
 use crate::error::db_error::DbError;
 use crate::error::Result;
+use crate::model::access_model::AudienceModel;
 use crate::service::api_service::ApiService;
 
+static SCHEMA_GROUPS_SQL: &str = r#"
+    LET $schema_groups = array::first((SELECT VALUE groups FROM schemas WHERE slug=$schema));
+    "#;
+
+static AUDIENCE_SQL: &str = r#" AND ($schema_groups = NONE OR $schema_groups = [] OR $schema_groups ANYINSIDE $groups)
+    AND (groups = NONE OR groups = [] OR groups ANYINSIDE $groups)"#;
+
 #[async_trait]
 pub trait ApiRepositoryTrait {
     async fn find(&self, table: &str, id: &str) -> Result<ApiModel>;
-    async fn find_by_slug(&self, table: &str, slug: &str) -> Result<ApiModel>;
+    async fn find_by_slug(
+        &self,
+        table: &str,
+        slug: &str,
+        audience: &AudienceModel,
+    ) -> Result<ApiModel>;
     async fn create(
         &self,
         auth: &str,
@@ -31,9 +44,9 @@ pub trait ApiRepositoryTrait {
         table: &str,
         start: usize,
         limit: usize,
-        is_admin: bool,
+        audience: &AudienceModel,
     ) -> Result<Vec<ApiModel>>;
-    async fn get_total(&self, table: &str, is_admin: bool) -> Result<usize>;
+    async fn get_total(&self, table: &str, audience: &AudienceModel) -> Result<usize>;
     async fn get_all_items(&self, table: &str) -> Result<Vec<ApiListItemModel>>;
 }
 
@@ -53,17 +66,33 @@ impl ApiRepositoryTrait for ApiService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
-    async fn find_by_slug(&self, table: &str, slug: &str) -> Result<ApiModel> {
+    async fn find_by_slug(
+        &self,
+        table: &str,
+        slug: &str,
+        audience: &AudienceModel,
+    ) -> Result<ApiModel> {
+        let mut audience_sql = "";
+        if !audience.all {
+            audience_sql = AUDIENCE_SQL;
+        }
+
         self.db
             .query(
-                r#"
-                SELECT * FROM type::table($table) WHERE slug=$slug;
-            "#,
+                [
+                    SCHEMA_GROUPS_SQL,
+                    r#"SELECT * FROM type::table($table) WHERE slug=$slug"#,
+                    audience_sql,
+                    ";",
+                ]
+                .concat(),
             )
             .bind(("table", table))
             .bind(("slug", slug))
+            .bind(("schema", if table.eq("singles") { slug } else { table }))
+            .bind(("groups", &audience.groups))
             .await?
-            .take::<Option<ApiModel>>(0)?
+            .take::<Option<ApiModel>>(1)?
             .ok_or(DbError::EntryNotFound.into())
     }
 
@@ -82,6 +111,7 @@ impl ApiRepositoryTrait for ApiService {
 	                title: $title,
 	                published: $published,
 	                fields: $fields,
+	                groups: $groups,
 	                created_by: $auth_id,
 	                updated_by: $auth_id
                 };
@@ -93,6 +123,7 @@ impl ApiRepositoryTrait for ApiService {
             .bind(("title", model.title))
             .bind(("published", model.published))
             .bind(("fields", model.fields))
+            .bind(("groups", model.groups))
             .await?
             .take::<Option<ApiModel>>(0)?
             .ok_or(DbError::EntryAlreadyExists.into())
@@ -112,6 +143,7 @@ impl ApiRepositoryTrait for ApiService {
                     title: $title,
                     published: $published,
                     fields: $fields,
+                    groups: $groups,
                     updated_by: $auth_id
                 } WHERE slug=$slug;
             "#,
@@ -122,6 +154,7 @@ impl ApiRepositoryTrait for ApiService {
             .bind(("title", model.title))
             .bind(("published", model.published))
             .bind(("fields", model.fields))
+            .bind(("groups", model.groups))
             .await?
             .take::<Option<ApiModel>>(0)?
             .ok_or(DbError::EntryUpdate.into())
@@ -146,33 +179,55 @@ impl ApiRepositoryTrait for ApiService {
         table: &str,
         start: usize,
         limit: usize,
-        is_admin: bool,
+        audience: &AudienceModel,
     ) -> Result<Vec<ApiModel>> {
         Ok(self
             .db
-            .query(match is_admin {
-                true => r#"SELECT * FROM type::table($table) LIMIT $limit START $start;"#,
-                false => r#"SELECT * FROM type::table($table) WHERE published = true LIMIT $limit START $start;"#,
+            .query(match audience.all {
+                true => [
+                    SCHEMA_GROUPS_SQL,
+                    r#"SELECT * FROM type::table($table) LIMIT $limit START $start;"#,
+                ]
+                .concat(),
+                false => [
+                    SCHEMA_GROUPS_SQL,
+                    r#"SELECT * FROM type::table($table) WHERE published = true"#,
+                    AUDIENCE_SQL,
+                    r#" LIMIT $limit START $start;"#,
+                ]
+                .concat(),
             })
             .bind(("table", table))
+            .bind(("schema", table))
+            .bind(("groups", &audience.groups))
             .bind(("start", start - 1))
             .bind(("limit", limit))
             .await?
-            .take::<Vec<ApiModel>>(0)?)
+            .take::<Vec<ApiModel>>(1)?)
     }
 
-    async fn get_total(&self, table: &str, is_admin: bool) -> Result<usize> {
+    async fn get_total(&self, table: &str, audience: &AudienceModel) -> Result<usize> {
         match self
             .db
-            .query(match is_admin {
-                true => r#"SELECT count() FROM type::table($table) GROUP ALL;"#,
-                false => {
-                    r#"SELECT count() FROM type::table($table) WHERE published = true GROUP ALL;"#
-                }
+            .query(match audience.all {
+                true => [
+                    SCHEMA_GROUPS_SQL,
+                    r#"SELECT count() FROM type::table($table) GROUP ALL;"#,
+                ]
+                .concat(),
+                false => [
+                    SCHEMA_GROUPS_SQL,
+                    r#"SELECT count() FROM type::table($table) WHERE published = true"#,
+                    AUDIENCE_SQL,
+                    r#" GROUP ALL;"#,
+                ]
+                .concat(),
             })
             .bind(("table", table))
+            .bind(("schema", table))
+            .bind(("groups", &audience.groups))
             .await?
-            .take::<Option<CountModel>>(0)?
+            .take::<Option<CountModel>>(1)?
         {
             Some(value) => Ok(value.count),
             _ => Ok(0usize),
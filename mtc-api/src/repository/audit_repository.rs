@@ -0,0 +1,117 @@
+use axum::async_trait;
+use chrono::{TimeDelta, Utc};
+use surrealdb::sql::Datetime;
+
+use mtc_model::audit_model::{AuditFilterModel, AuditModel};
+use mtc_model::pagination_model::CountModel;
+
+use crate::error::Result;
+use crate::repository::date_bound;
+use crate::model::audit_model::AuditEntryModel;
+use crate::service::audit_service::AuditService;
+
+const AUDIT_FILTER: &str = r#"
+    (!$actor OR actor = $actor)
+    AND (!$action OR string::starts_with(action, $action))
+    AND (!$target OR string::contains(target, $target))
+    AND (!$date_from OR created_at >= $date_from)
+    AND (!$date_to OR created_at < $date_to)
+"#;
+
+#[async_trait]
+pub trait AuditRepositoryTrait {
+    async fn create(&self, entry: AuditEntryModel) -> Result<()>;
+    async fn get_total(&self, filter: &AuditFilterModel) -> Result<usize>;
+    async fn get_page(
+        &self,
+        start: usize,
+        limit: usize,
+        filter: &AuditFilterModel,
+    ) -> Result<Vec<AuditModel>>;
+    async fn find_all(&self, filter: &AuditFilterModel) -> Result<Vec<AuditModel>>;
+    async fn delete_expired(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl AuditRepositoryTrait for AuditService {
+    async fn create(&self, entry: AuditEntryModel) -> Result<()> {
+        self.db
+            .query(r#"CREATE audit_log CONTENT $entry;"#)
+            .bind(("entry", entry))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_total(&self, filter: &AuditFilterModel) -> Result<usize> {
+        let result = self
+            .db
+            .query(["SELECT count() FROM audit_log WHERE", AUDIT_FILTER, "GROUP ALL;"].concat())
+            .bind(("actor", &filter.actor))
+            .bind(("action", &filter.action))
+            .bind(("target", &filter.target))
+            .bind(("date_from", date_bound(&filter.date_from, 0)))
+            .bind(("date_to", date_bound(&filter.date_to, 1)))
+            .await?
+            .take::<Option<CountModel>>(0)?;
+
+        Ok(result.map(|value| value.count).unwrap_or_default())
+    }
+
+    async fn get_page(
+        &self,
+        start: usize,
+        limit: usize,
+        filter: &AuditFilterModel,
+    ) -> Result<Vec<AuditModel>> {
+        Ok(self
+            .db
+            .query(
+                [
+                    "SELECT * FROM audit_log WHERE",
+                    AUDIT_FILTER,
+                    "ORDER BY created_at DESC LIMIT $limit START $start;",
+                ]
+                .concat(),
+            )
+            .bind(("actor", &filter.actor))
+            .bind(("action", &filter.action))
+            .bind(("target", &filter.target))
+            .bind(("date_from", date_bound(&filter.date_from, 0)))
+            .bind(("date_to", date_bound(&filter.date_to, 1)))
+            .bind(("start", start - 1))
+            .bind(("limit", limit))
+            .await?
+            .take::<Vec<AuditModel>>(0)?)
+    }
+
+    async fn find_all(&self, filter: &AuditFilterModel) -> Result<Vec<AuditModel>> {
+        Ok(self
+            .db
+            .query(["SELECT * FROM audit_log WHERE", AUDIT_FILTER, "ORDER BY created_at DESC;"].concat())
+            .bind(("actor", &filter.actor))
+            .bind(("action", &filter.action))
+            .bind(("target", &filter.target))
+            .bind(("date_from", date_bound(&filter.date_from, 0)))
+            .bind(("date_to", date_bound(&filter.date_to, 1)))
+            .await?
+            .take::<Vec<AuditModel>>(0)?)
+    }
+
+    async fn delete_expired(&self) -> Result<()> {
+        // zero retention keeps the log forever
+        if self.cfg.audit_retention <= 0 {
+            return Ok(());
+        }
+
+        self.db
+            .query(r#"DELETE audit_log WHERE created_at < $expired_at;"#)
+            .bind((
+                "expired_at",
+                Datetime::from(Utc::now() - TimeDelta::days(self.cfg.audit_retention)),
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
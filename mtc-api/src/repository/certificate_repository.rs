@@ -0,0 +1,94 @@
+use axum::async_trait;
+
+use mtc_model::certificate_model::{
+    CertificateCreateModel, CertificateKindModel, CertificateModel, ClientCertificateModel,
+};
+
+use crate::error::db_error::DbError;
+use crate::error::Result;
+use crate::service::certificate_service::CertificateService;
+
+#[async_trait]
+pub trait CertificateRepositoryTrait {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<CertificateModel>>;
+    async fn find_login(&self, certificate: &ClientCertificateModel) -> Result<Option<String>>;
+    async fn create(
+        &self,
+        auth: &str,
+        login: &str,
+        model: CertificateCreateModel,
+    ) -> Result<CertificateModel>;
+    async fn delete(&self, login: &str, id: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl CertificateRepositoryTrait for CertificateService {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<CertificateModel>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT * FROM user_certificates WHERE login=$login ORDER BY created_at DESC;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Vec<CertificateModel>>(0)?)
+    }
+
+    async fn find_login(&self, certificate: &ClientCertificateModel) -> Result<Option<String>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT VALUE login FROM user_certificates
+                WHERE (kind = 'subject' AND value = $subject) OR (kind = 'serial' AND value = $serial)
+                LIMIT 1;
+            "#,
+            )
+            .bind(("subject", CertificateKindModel::Subject.normalize(&certificate.subject)))
+            .bind(("serial", CertificateKindModel::Serial.normalize(&certificate.serial)))
+            .await?
+            .take::<Option<String>>(0)?)
+    }
+
+    async fn create(
+        &self,
+        auth: &str,
+        login: &str,
+        model: CertificateCreateModel,
+    ) -> Result<CertificateModel> {
+        self.db
+            .query(
+                r#"
+            CREATE user_certificates CONTENT {
+                login: $login,
+                kind: $kind,
+                value: $value,
+                created_by: $auth
+            };
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("kind", model.kind))
+            .bind(("value", model.kind.normalize(&model.value)))
+            .bind(("auth", auth))
+            .await?
+            .take::<Option<CertificateModel>>(0)?
+            .ok_or(DbError::EntryAlreadyExists.into())
+    }
+
+    async fn delete(&self, login: &str, id: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            DELETE type::thing('user_certificates', $id) WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("id", id))
+            .await?;
+
+        Ok(())
+    }
+}
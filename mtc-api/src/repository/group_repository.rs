@@ -17,9 +17,17 @@ pub trait GroupRepositoryTrait {
     async fn all(&self) -> Result<RecordListModel>;
     async fn find_by_slug(&self, slug: &str) -> Result<GroupModel>;
     async fn find_by_user(&self, login: &str) -> Result<StringListModel>;
+    async fn find_managed_by_user(&self, login: &str) -> Result<StringListModel>;
+    async fn find_managers(&self, slug: &str) -> Result<StringListModel>;
+    async fn nodes(&self) -> Result<Vec<GroupNodeModel>>;
+    async fn set_parent(&self, auth: &str, slug: &str, parent: Option<String>) -> Result<GroupModel>;
     async fn create(&self, auth: &str, slug: &str, model: GroupCreateModel) -> Result<GroupModel>;
     async fn update(&self, auth: &str, slug: &str, model: GroupUpdateModel) -> Result<GroupModel>;
     async fn delete(&self, slug: &str) -> Result<()>;
+    async fn role_assign(&self, group_id: &str, role_id: &str) -> Result<()>;
+    async fn roles_drop(&self, group_id: &str) -> Result<()>;
+    async fn manager_assign(&self, group_id: &str, user_id: &str) -> Result<()>;
+    async fn managers_drop(&self, group_id: &str) -> Result<()>;
 }
 
 #[async_trait]
@@ -61,6 +69,56 @@ impl GroupRepositoryTrait for GroupService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    async fn find_managed_by_user(&self, login: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT array::sort(array::distinct(->group_managers->groups.slug)) as list FROM users WHERE login=$login
+            "#)
+            .bind(("login", login))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn find_managers(&self, slug: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT array::sort(array::distinct(<-group_managers<-users.login)) as list FROM groups WHERE slug=$slug
+            "#)
+            .bind(("slug", slug))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn nodes(&self) -> Result<Vec<GroupNodeModel>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+                SELECT slug, title, parent FROM groups ORDER BY title;
+                "#,
+            )
+            .await?
+            .take::<Vec<GroupNodeModel>>(0)?)
+    }
+
+    async fn set_parent(&self, auth: &str, slug: &str, parent: Option<String>) -> Result<GroupModel> {
+        self.db
+            .query(
+                r#"
+                UPDATE groups MERGE {
+	                parent: $parent,
+	                updated_by: $auth_id
+                } WHERE slug=$slug;
+                "#,
+            )
+            .bind(("auth_id", auth))
+            .bind(("slug", slug))
+            .bind(("parent", parent))
+            .await?
+            .take::<Option<GroupModel>>(0)?
+            .ok_or(DbError::EntryUpdate.into())
+    }
+
     async fn create(&self, auth: &str, slug: &str, model: GroupCreateModel) -> Result<GroupModel> {
         self.db
             .query(
@@ -68,6 +126,7 @@ impl GroupRepositoryTrait for GroupService {
                 CREATE groups CONTENT {
 	                slug: $slug,
 	                title: $title,
+	                parent: $parent,
 	                created_by: $auth_id,
 	                updated_by: $auth_id
                 };
@@ -76,6 +135,7 @@ impl GroupRepositoryTrait for GroupService {
             .bind(("auth_id", auth))
             .bind(("slug", slug))
             .bind(("title", model.title))
+            .bind(("parent", model.parent))
             .await?
             .take::<Option<GroupModel>>(0)?
             .ok_or(DbError::EntryNotFound.into())
@@ -103,7 +163,13 @@ impl GroupRepositoryTrait for GroupService {
         self.db
             .query(
                 r#"
+                BEGIN TRANSACTION;
+
+                LET $parent = (SELECT VALUE parent FROM groups WHERE slug=$slug)[0];
+                UPDATE groups SET parent = $parent WHERE parent=$slug;
                 DELETE FROM groups WHERE slug=$slug;
+
+                COMMIT TRANSACTION;
                 "#,
             )
             .bind(("slug", slug))
@@ -111,4 +177,68 @@ impl GroupRepositoryTrait for GroupService {
 
         Ok(())
     }
+
+    async fn role_assign(&self, group_id: &str, role_id: &str) -> Result<()> {
+        match self
+            .db
+            .query(format!(
+                r#"
+                RELATE groups:{}->group_roles->roles:{};
+                "#,
+                group_id, role_id
+            ))
+            .await
+        {
+            Ok(..) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn roles_drop(&self, group_id: &str) -> Result<()> {
+        match self
+            .db
+            .query(
+                r#"
+                DELETE type::thing('groups', $group_id)->group_roles;
+                "#,
+            )
+            .bind(("group_id", group_id))
+            .await
+        {
+            Ok(..) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn manager_assign(&self, group_id: &str, user_id: &str) -> Result<()> {
+        match self
+            .db
+            .query(format!(
+                r#"
+                RELATE users:{}->group_managers->groups:{};
+                "#,
+                user_id, group_id
+            ))
+            .await
+        {
+            Ok(..) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn managers_drop(&self, group_id: &str) -> Result<()> {
+        match self
+            .db
+            .query(
+                r#"
+                DELETE type::thing('groups', $group_id)<-group_managers;
+                "#,
+            )
+            .bind(("group_id", group_id))
+            .await
+        {
+            Ok(..) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
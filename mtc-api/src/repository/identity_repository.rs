@@ -0,0 +1,78 @@
+use axum::async_trait;
+
+use mtc_model::identity_model::IdentityModel;
+
+use crate::error::db_error::DbError;
+use crate::error::Result;
+use crate::service::identity_service::IdentityService;
+
+#[async_trait]
+pub trait IdentityRepositoryTrait {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<IdentityModel>>;
+    async fn find_login(&self, issuer: &str, subject: &str) -> Result<Option<String>>;
+    async fn create(&self, login: &str, issuer: &str, subject: &str) -> Result<IdentityModel>;
+    async fn delete(&self, login: &str, id: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl IdentityRepositoryTrait for IdentityService {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<IdentityModel>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT * FROM user_identities WHERE login=$login ORDER BY created_at DESC;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Vec<IdentityModel>>(0)?)
+    }
+
+    async fn find_login(&self, issuer: &str, subject: &str) -> Result<Option<String>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT VALUE login FROM user_identities WHERE issuer=$issuer AND subject=$subject LIMIT 1;
+            "#,
+            )
+            .bind(("issuer", issuer))
+            .bind(("subject", subject))
+            .await?
+            .take::<Option<String>>(0)?)
+    }
+
+    async fn create(&self, login: &str, issuer: &str, subject: &str) -> Result<IdentityModel> {
+        self.db
+            .query(
+                r#"
+            CREATE user_identities CONTENT {
+                login: $login,
+                issuer: $issuer,
+                subject: $subject
+            };
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("issuer", issuer))
+            .bind(("subject", subject))
+            .await?
+            .take::<Option<IdentityModel>>(0)?
+            .ok_or(DbError::EntryAlreadyExists.into())
+    }
+
+    async fn delete(&self, login: &str, id: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            DELETE type::thing('user_identities', $id) WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("id", id))
+            .await?;
+
+        Ok(())
+    }
+}
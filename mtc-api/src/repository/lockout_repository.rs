@@ -0,0 +1,119 @@
+use axum::async_trait;
+use chrono::{TimeDelta, Utc};
+use surrealdb::sql::Datetime;
+
+use crate::error::Result;
+use crate::service::lockout_service::LockoutService;
+
+#[async_trait]
+pub trait LockoutRepositoryTrait {
+    async fn is_login_locked(&self, login: &str) -> Result<bool>;
+    async fn is_ip_locked(&self, ip: &str) -> Result<bool>;
+    async fn login_failed(&self, login: &str) -> Result<i32>;
+    async fn ip_failed(&self, ip: &str) -> Result<i32>;
+    async fn login_reset(&self, login: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl LockoutRepositoryTrait for LockoutService {
+    async fn is_login_locked(&self, login: &str) -> Result<bool> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT VALUE locked_until > time::now() FROM users WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Option<bool>>(0)?
+            .unwrap_or(false))
+    }
+
+    async fn is_ip_locked(&self, ip: &str) -> Result<bool> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT VALUE locked_until > time::now() FROM type::thing('sign_in_attempts', $ip);
+            "#,
+            )
+            .bind(("ip", ip))
+            .await?
+            .take::<Option<bool>>(0)?
+            .unwrap_or(false))
+    }
+
+    async fn login_failed(&self, login: &str) -> Result<i32> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            UPDATE users SET
+                failed_count = IF failed_last != NONE AND failed_last > $window_start
+                    THEN failed_count + 1 ELSE 1 END,
+                failed_last = time::now()
+            WHERE login=$login RETURN VALUE failed_count;
+            UPDATE users SET locked_until = $locked_until
+                WHERE login=$login AND failed_count >= $max_attempts;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("window_start", self.window_start()))
+            .bind(("locked_until", self.locked_until()))
+            .bind(("max_attempts", self.cfg.login_max_attempts))
+            .await?
+            .take::<Option<i32>>(0)?
+            .unwrap_or(0))
+    }
+
+    async fn ip_failed(&self, ip: &str) -> Result<i32> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            UPDATE type::thing('sign_in_attempts', $ip) SET
+                failed_count = IF failed_last != NONE AND failed_last > $window_start
+                    THEN failed_count + 1 ELSE 1 END,
+                failed_last = time::now()
+            RETURN VALUE failed_count;
+            UPDATE type::thing('sign_in_attempts', $ip) SET locked_until = $locked_until
+                WHERE failed_count >= $max_attempts;
+            "#,
+            )
+            .bind(("ip", ip))
+            .bind(("window_start", self.window_start()))
+            .bind(("locked_until", self.locked_until()))
+            .bind(("max_attempts", self.cfg.login_ip_max_attempts))
+            .await?
+            .take::<Option<i32>>(0)?
+            .unwrap_or(0))
+    }
+
+    async fn login_reset(&self, login: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            UPDATE users MERGE {
+                failed_count: 0,
+                failed_last: NONE,
+                locked_until: NONE
+            } WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl LockoutService {
+    fn window_start(&self) -> Datetime {
+        Datetime::from(Utc::now() - TimeDelta::minutes(self.cfg.login_attempts_window))
+    }
+
+    fn locked_until(&self) -> Datetime {
+        Datetime::from(Utc::now() + TimeDelta::minutes(self.cfg.login_lockout))
+    }
+}
@@ -1,4 +1,6 @@
 use axum::async_trait;
+use chrono::{NaiveDate, TimeDelta};
+use surrealdb::sql::Datetime;
 
 pub mod role_repository;
 pub mod user_repository;
@@ -7,6 +9,14 @@ pub mod group_repository;
 pub mod schema_repository;
 pub mod api_repository;
 pub mod system_repository;
+pub mod token_repository;
+pub mod totp_repository;
+pub mod lockout_repository;
+pub mod session_repository;
+pub mod audit_repository;
+pub mod sign_in_log_repository;
+pub mod certificate_repository;
+pub mod identity_repository;
 
 #[async_trait]
 pub trait RepositoryPaginate<T> {
@@ -44,3 +54,12 @@ macro_rules! repository_paginate {
         }
     };
 }
+
+/// Dates come from the filter form as `YYYY-MM-DD`, the upper bound is shifted a day
+/// forward to include the whole day.
+pub fn date_bound(date: &Option<String>, days: i64) -> Option<Datetime> {
+    date.as_deref()
+        .and_then(|value| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok())
+        .and_then(|value| value.and_hms_opt(0, 0, 0))
+        .map(|value| Datetime::from(value.and_utc() + TimeDelta::days(days)))
+}
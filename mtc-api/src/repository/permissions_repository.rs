@@ -14,6 +14,7 @@ pub trait PermissionsRepositoryTrait {
     async fn find_by_slug(&self, slug: &str) -> Result<PermissionModel>;
     async fn find_by_role(&self, role: &str) -> Result<StringListModel>;
     async fn find_by_user(&self, role: &str) -> Result<StringListModel>;
+    async fn find_effective_by_user(&self, login: &str) -> Result<StringListModel>;
     async fn get_custom(&self) -> Result<Vec<PermissionModel>>;
     async fn create_custom(&self, auth: &str, model: PermissionDtoModel) -> Result<()>;
     async fn delete_custom(&self, model: PermissionDtoModel) -> Result<()>;
@@ -60,7 +61,9 @@ impl PermissionsRepositoryTrait for PermissionsService {
 
     async fn find_by_user(&self, login: &str) -> Result<StringListModel> {
         self.db.query(r#"
-            SELECT array::sort(array::distinct(->user_roles->roles->role_permissions->permissions.slug)) as list
+            SELECT array::sort(array::distinct(array::flatten(
+                (SELECT VALUE ->role_permissions->permissions.slug FROM fn::user_roles($login))
+            ))) as list
             FROM users WHERE login=$login
             "#)
             .bind(("login", login))
@@ -69,6 +72,17 @@ impl PermissionsRepositoryTrait for PermissionsService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    /// Resolves the wildcard and deny rules of the user into the concrete permissions.
+    async fn find_effective_by_user(&self, login: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT fn::user_permissions($login) as list FROM users WHERE login=$login
+            "#)
+            .bind(("login", login))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
     async fn get_custom(&self) -> Result<Vec<PermissionModel>> {
         Ok(self.db.query(r#"
             SELECT * FROM permissions WHERE is_custom = true;
@@ -94,6 +108,8 @@ impl PermissionsRepositoryTrait for PermissionsService {
             .take::<Option<PermissionModel>>(0)?;
         
         match permission {
+            // a deny rule granted to the administrator would lock everybody out
+            Some(value) if value.slug.starts_with('!') => (),
             Some(value) => {
                 self.db
                     .query(format!(
@@ -16,10 +16,14 @@ pub trait RoleRepositoryTrait {
     async fn all(&self) -> Result<RecordListModel>;
     async fn find_by_slug(&self, slug: &str) -> Result<RoleModel>;
     async fn find_by_user(&self, login: &str) -> Result<StringListModel>;
+    async fn find_direct_by_user(&self, login: &str) -> Result<StringListModel>;
+    async fn find_inherited_by_user(&self, login: &str) -> Result<StringListModel>;
+    async fn find_by_group(&self, slug: &str) -> Result<StringListModel>;
     async fn create(&self, auth: &str, slug: &str, model: &RoleCreateModel) -> Result<RoleModel>;
     async fn update(&self, auth: &str, slug: &str, model: &RoleUpdateModel) -> Result<RoleModel>;
     async fn delete(&self, slug: &str) -> Result<()>;
     async fn permission_assign(&self, role_id: &str, permission_id: &str) -> Result<()>;
+    async fn permission_unassign(&self, role_id: &str, permission_id: &str) -> Result<()>;
     async fn permissions_drop(&self, role_id: &str) -> Result<()>;
 }
 
@@ -52,7 +56,18 @@ impl RoleRepositoryTrait for RoleService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    /// Roles assigned directly together with the ones inherited through the groups.
     async fn find_by_user(&self, login: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT array::sort((SELECT VALUE slug FROM fn::user_roles($login))) as list FROM users WHERE login=$login
+            "#)
+            .bind(("login", login))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn find_direct_by_user(&self, login: &str) -> Result<StringListModel> {
         self.db.query(r#"
             SELECT array::sort(array::distinct(->user_roles->roles.slug)) as list FROM users WHERE login=$login
             "#)
@@ -62,6 +77,26 @@ impl RoleRepositoryTrait for RoleService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    async fn find_inherited_by_user(&self, login: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT array::sort((SELECT VALUE slug FROM fn::user_inherited_roles($login))) as list FROM users WHERE login=$login
+            "#)
+            .bind(("login", login))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn find_by_group(&self, slug: &str) -> Result<StringListModel> {
+        self.db.query(r#"
+            SELECT array::sort(array::distinct(->group_roles->roles.slug)) as list FROM groups WHERE slug=$slug
+            "#)
+            .bind(("slug", slug))
+            .await?
+            .take::<Option<StringListModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
     async fn create(&self, auth: &str, slug: &str, model: &RoleCreateModel) -> Result<RoleModel> {
         self.db
             .query(
@@ -71,6 +106,7 @@ impl RoleRepositoryTrait for RoleService {
 	                title: $title,
 	                user_access_level: $user_access_level,
 	                user_access_all: $user_access_all,
+	                two_factor: $two_factor,
 	                created_by: $auth_id,
 	                updated_by: $auth_id
                 };
@@ -81,6 +117,7 @@ impl RoleRepositoryTrait for RoleService {
             .bind(("title", model.title.clone()))
             .bind(("user_access_level", model.user_access_level))
             .bind(("user_access_all", model.user_access_all))
+            .bind(("two_factor", model.two_factor))
             .await?
             .take::<Option<RoleModel>>(0)?
             .ok_or(DbError::EntryAlreadyExists.into())
@@ -94,6 +131,7 @@ impl RoleRepositoryTrait for RoleService {
 	                title: $title,
 	                user_access_level: $user_access_level,
 	                user_access_all: $user_access_all,
+	                two_factor: $two_factor,
 	                updated_by: $auth_id
                 } WHERE slug=$slug;
                 "#,
@@ -103,6 +141,7 @@ impl RoleRepositoryTrait for RoleService {
             .bind(("title", model.title.clone()))
             .bind(("user_access_level", model.user_access_level))
             .bind(("user_access_all", model.user_access_all))
+            .bind(("two_factor", model.two_factor))
             .await?
             .take::<Option<RoleModel>>(0)?
             .ok_or(DbError::EntryUpdate.into())
@@ -137,6 +176,21 @@ impl RoleRepositoryTrait for RoleService {
         }
     }
 
+    async fn permission_unassign(&self, role_id: &str, permission_id: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+                DELETE type::thing('roles', $role_id)->role_permissions
+                    WHERE out=type::thing('permissions', $permission_id);
+                "#,
+            )
+            .bind(("role_id", role_id))
+            .bind(("permission_id", permission_id))
+            .await?;
+
+        Ok(())
+    }
+
     async fn permissions_drop(&self, role_id: &str) -> Result<()> {
         self.db
             .query(
@@ -1,20 +1,120 @@
 use axum::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
 
+use mtc_model::field_model::FieldModel;
 use mtc_model::list_model::RecordListModel;
 use mtc_model::record_model::RecordModel;
 use mtc_model::schema_model::{
-    SchemaCreateModel, SchemaFieldsModel, SchemaModel, SchemaUpdateModel,
+    FieldMigrationItemModel, SchemaCreateModel, SchemaFieldsModel, SchemaMigrationModel,
+    SchemaModel, SchemaUpdateModel,
 };
 
 use crate::error::api_error::ApiError;
 use crate::error::db_error::DbError;
+use crate::error::generic_error::GenericError;
 use crate::error::Result;
 use crate::repository::RepositoryPaginate;
 use crate::repository_paginate;
-use crate::service::schema_service::SchemaService;
+use crate::service::schema_service::{FieldMigrationTrait, SchemaService};
 
 repository_paginate!(SchemaService, SchemaModel, "schemas");
 
+#[derive(Deserialize)]
+struct FieldsItemModel {
+    slug: String,
+    title: String,
+    fields: Option<Value>,
+}
+
+impl SchemaService {
+    async fn get_fields_items(&self, schema: &SchemaModel) -> Result<Vec<FieldsItemModel>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT slug, title, fields FROM type::table($table) WHERE $table != 'singles' OR slug=$slug;
+            "#,
+            )
+            .bind(("table", match schema.is_collection {
+                true => schema.slug.as_str(),
+                false => "singles",
+            }))
+            .bind(("slug", &schema.slug))
+            .await?
+            .take::<Vec<FieldsItemModel>>(0)?)
+    }
+
+    /// The items whose values change with the new fields,
+    /// refused while some values can't be converted unless the caller insists.
+    async fn get_migrated_items(
+        &self,
+        schema: &SchemaModel,
+        new_fields: &[FieldModel],
+        force: bool,
+    ) -> Result<Vec<Value>> {
+        let old_fields = schema.fields.clone().unwrap_or_default();
+
+        let mut items = vec![];
+        let mut failed = vec![];
+        if !self.fields_diff(&old_fields, new_fields).is_empty() {
+            for item in self.get_fields_items(schema).await? {
+                let Some(fields) = item.fields else {
+                    continue;
+                };
+                let (migrated, errors) = self.migrate_fields(&old_fields, new_fields, &fields);
+                if !errors.is_empty() {
+                    failed.push(FieldMigrationItemModel {
+                        slug: item.slug.clone(),
+                        title: item.title,
+                        errors,
+                    });
+                }
+                if migrated.ne(&fields) {
+                    items.push(json!({ "slug": item.slug, "fields": migrated }));
+                }
+            }
+        }
+
+        if !failed.is_empty() && !force {
+            Err(ApiError::from(DbError::FieldMigration(failed)))?
+        }
+
+        Ok(items)
+    }
+
+    /// Everything an update may refuse, the new fields and the migrated items when nothing is.
+    async fn update_check(
+        &self,
+        schema: &SchemaModel,
+        model: &SchemaUpdateModel,
+    ) -> Result<(Vec<FieldModel>, Vec<Value>)> {
+        let is_public = model.is_public.unwrap_or(schema.is_public);
+        let is_collection = model.is_collection.unwrap_or(schema.is_collection);
+
+        if schema.is_system
+            && (is_public.ne(&schema.is_public) || is_collection.ne(&schema.is_collection))
+        {
+            Err(ApiError::from(DbError::EntryUpdate))?
+        }
+
+        let new_fields = field_ids(model.fields.clone().unwrap_or_default());
+        let items = self
+            .get_migrated_items(schema, &new_fields, model.force.unwrap_or(false))
+            .await?;
+
+        // a collection becomes a single only while it holds one item at most
+        if schema.is_collection
+            && !is_collection
+            && self.get_fields_items(schema).await?.len() > 1
+        {
+            Err(ApiError::from(GenericError::ConflictError))?
+        }
+
+        Ok((new_fields, items))
+    }
+}
+
 #[async_trait]
 pub trait SchemaRepositoryTrait {
     async fn find_by_slug(&self, slug: &str) -> Result<SchemaModel>;
@@ -22,6 +122,7 @@ pub trait SchemaRepositoryTrait {
     async fn create(&self, auth: &str, slug: &str, model: SchemaCreateModel)
         -> Result<SchemaModel>;
     async fn delete(&self, slug: &str) -> Result<()>;
+    async fn can_update(&self, slug: &str, model: &SchemaUpdateModel) -> Result<()>;
     async fn update(&self, auth: &str, slug: &str, model: SchemaUpdateModel)
         -> Result<SchemaModel>;
     async fn update_fields(
@@ -31,7 +132,16 @@ pub trait SchemaRepositoryTrait {
         model: SchemaFieldsModel,
     ) -> Result<SchemaModel>;
     async fn get_fields(&self, slug: &str) -> Result<SchemaFieldsModel>;
+    async fn migration_preview(
+        &self,
+        slug: &str,
+        model: SchemaFieldsModel,
+    ) -> Result<SchemaMigrationModel>;
     async fn get_all_collections(&self) -> Result<RecordListModel>;
+    async fn get_all(&self) -> Result<Vec<SchemaModel>>;
+    async fn define_collection(&self, slug: &str) -> Result<()>;
+    async fn create_permissions(&self, auth: &str, slug: &str, is_public: bool) -> Result<()>;
+    async fn delete_permissions(&self, slug: &str) -> Result<()>;
 }
 
 #[async_trait]
@@ -75,90 +185,36 @@ impl SchemaRepositoryTrait for SchemaService {
     ) -> Result<SchemaModel> {
         self.can_create(slug).await?;
 
-        let sql = r#"
-            BEGIN TRANSACTION;
-
+        let result: Option<SchemaModel> = self
+            .db
+            .query(
+                r#"
             CREATE schemas CONTENT {
 	            slug: $slug,
 	            title: $title,
 	            fields: $fields,
+	            groups: $groups,
 	            is_collection: $is_collection,
 	            is_public: $is_public,
 	            created_by: $auth_id,
 	            updated_by: $auth_id
             };
-        "#;
-
-        let result: Option<SchemaModel> = self
-            .db
-            .query(match model.is_public {
-                true => [sql, "COMMIT TRANSACTION;"].concat(),
-                false => [
-                    sql,
-                    r#"
-                    CREATE permissions CONTENT {
-                        id: $permission_read_id,
-                        slug: $permission_read,
-                        created_by: $auth_id
-                    };
-
-                    CREATE permissions CONTENT {
-                        id: $permission_write_id,
-                        slug: $permission_write,
-                        created_by: $auth_id
-                    };
-
-                    CREATE permissions CONTENT {
-                        id: $permission_delete_id,
-                        slug: $permission_delete,
-                        created_by: $auth_id
-                    };
-
-                    COMMIT TRANSACTION;
-                "#,
-                ]
-                .concat(),
-            })
+            "#,
+            )
             .bind(("auth_id", auth))
             .bind(("slug", slug))
             .bind(("title", &model.title))
             .bind(("fields", &model.fields))
+            .bind(("groups", &model.groups))
             .bind(("is_collection", &model.is_collection))
             .bind(("is_public", &model.is_public))
-            .bind(("permission_read_id", format!("{}_read", slug)))
-            .bind(("permission_read", format!("{}::read", slug)))
-            .bind(("permission_write_id", format!("{}_write", slug)))
-            .bind(("permission_write", format!("{}::write", slug)))
-            .bind(("permission_delete_id", format!("{}_delete", slug)))
-            .bind(("permission_delete", format!("{}::delete", slug)))
             .await?
             .take(0)?;
 
         match result {
             Some(value) => {
                 if model.is_collection {
-                    self.db
-                        .query(format!(
-                            r#"
-                        BEGIN TRANSACTION;
-
-                        DEFINE TABLE {0} SCHEMAFULL;
-                        DEFINE FIELD slug ON TABLE {0} TYPE string;
-                        DEFINE FIELD title ON TABLE {0} TYPE string;
-                        DEFINE FIELD fields ON TABLE {0} FLEXIBLE TYPE option<object>;
-                        DEFINE FIELD published ON TABLE {0} TYPE bool DEFAULT false;
-                        DEFINE FIELD created_at ON TABLE {0} TYPE datetime DEFAULT time::now();
-                        DEFINE FIELD updated_at ON TABLE {0} TYPE datetime VALUE time::now();
-                        DEFINE FIELD created_by ON TABLE {0} TYPE string;
-                        DEFINE FIELD updated_by ON TABLE {0} TYPE string;
-                        DEFINE INDEX idx_{0}_update ON TABLE {0} COLUMNS updated_at;
-                        DEFINE INDEX idx_{0}_slug ON TABLE {0} COLUMNS slug UNIQUE;
-
-                        COMMIT TRANSACTION;
-                        "#,
-                            slug
-                        ))
-                        .await?;
+                    self.define_collection(slug).await?;
                 } else {
                     self.db
                         .query(
@@ -176,22 +232,7 @@ impl SchemaRepositoryTrait for SchemaService {
                         .bind(("slug", slug))
                         .await?;
                 }
-                if !model.is_public {
-                    self.db
-                        .query(format!(
-                            r#"
-                        BEGIN TRANSACTION;
-
-                        RELATE roles:administrator->role_permissions->permissions:{0}_read;
-                        RELATE roles:administrator->role_permissions->permissions:{0}_write;
-                        RELATE roles:administrator->role_permissions->permissions:{0}_delete;
-
-                        COMMIT TRANSACTION;
-                        "#,
-                            slug
-                        ))
-                        .await?;
-                }
+                self.create_permissions(auth, slug, model.is_public).await?;
                 Ok(value)
             }
             _ => Err(DbError::EntryAlreadyExists.into()),
@@ -213,19 +254,14 @@ impl SchemaRepositoryTrait for SchemaService {
             DELETE FROM schemas WHERE slug=$slug;
             DELETE FROM singles WHERE slug=$slug;
 
-            DELETE FROM permissions WHERE slug=$permission_read;
-            DELETE FROM permissions WHERE slug=$permission_write;
-            DELETE FROM permissions WHERE slug=$permission_delete;
-
             COMMIT TRANSACTION;
             "#,
             )
             .bind(("slug", &model.slug))
-            .bind(("permission_read", format!("{}::read", &model.slug)))
-            .bind(("permission_write", format!("{}::write", &model.slug)))
-            .bind(("permission_delete", format!("{}::delete", &model.slug)))
             .await?;
 
+        self.delete_permissions(&model.slug).await?;
+
         if model.is_collection {
             self.db
                 .query(format!(
@@ -240,28 +276,81 @@ impl SchemaRepositoryTrait for SchemaService {
         Ok(())
     }
 
+    async fn can_update(&self, slug: &str, model: &SchemaUpdateModel) -> Result<()> {
+        self.update_check(&self.find_by_slug(slug).await?, model)
+            .await
+            .map(|_| ())
+    }
+
     async fn update(
         &self,
         auth: &str,
         slug: &str,
         model: SchemaUpdateModel,
     ) -> Result<SchemaModel> {
-        self.db
-            .query(
-                r#"
+        let schema_model = self.find_by_slug(slug).await?;
+        let is_public = model.is_public.unwrap_or(schema_model.is_public);
+        let is_collection = model.is_collection.unwrap_or(schema_model.is_collection);
+
+        // everything is checked before the single transaction below, so a refused change leaves no trace
+        let (new_fields, items) = self.update_check(&schema_model, &model).await?;
+
+        let mut sql = vec!["BEGIN TRANSACTION;".to_string(), ITEMS_SQL.to_string()];
+
+        match (schema_model.is_public, is_public) {
+            (false, true) => sql.push(PERMISSIONS_DELETE_SQL.to_string()),
+            (true, false) => sql.push(PERMISSIONS_CREATE_SQL.to_string()),
+            _ => (),
+        }
+
+        match (schema_model.is_collection, is_collection) {
+            (false, true) => {
+                sql.push(collection_sql(slug));
+                sql.push(TO_COLLECTION_SQL.to_string());
+            }
+            (true, false) => sql.push(to_single_sql(slug)),
+            _ => (),
+        }
+
+        sql.push(
+            r#"
             UPDATE schemas MERGE {
                 title: $title,
                 fields: $fields,
+                groups: $groups,
+                is_public: $is_public,
                 updated_by: $auth_id
             } WHERE slug=$slug;
-            "#,
-            )
+
+            COMMIT TRANSACTION;
+            "#
+            .to_string(),
+        );
+
+        let mut response = self
+            .db
+            .query(sql.concat())
             .bind(("auth_id", auth))
             .bind(("slug", slug))
+            .bind(("table", match schema_model.is_collection {
+                true => slug,
+                false => "singles",
+            }))
+            .bind(("items", items))
+            // the own permissions stay with the schema whether it's public or not
+            .bind(("permissions", permission_slugs(slug, &SCHEMA_ACTIONS)))
             .bind(("title", model.title))
-            .bind(("fields", model.fields))
-            .await?
-            .take::<Option<SchemaModel>>(0)?
+            .bind(("fields", match new_fields.is_empty() {
+                true => None,
+                false => Some(new_fields),
+            }))
+            .bind(("groups", model.groups))
+            .bind(("is_public", is_public))
+            .await?;
+
+        let last = response.num_statements() - 1;
+        response
+            .take::<Option<SchemaModel>>(last)?
             .ok_or(DbError::EntryUpdate.into())
     }
 
@@ -271,23 +360,74 @@ impl SchemaRepositoryTrait for SchemaService {
         slug: &str,
         model: SchemaFieldsModel,
     ) -> Result<SchemaModel> {
+        let schema_model = self.find_by_slug(slug).await?;
+        let new_fields = field_ids(model.fields.unwrap_or_default());
+        let items = self
+            .get_migrated_items(&schema_model, &new_fields, model.force.unwrap_or(false))
+            .await?;
+
         self.db
             .query(
-                r#"
+                [
+                    "BEGIN TRANSACTION;",
+                    ITEMS_SQL,
+                    r#"
             UPDATE schemas MERGE {
                 fields: $fields,
                 updated_by: $auth_id
             } WHERE slug=$slug;
+
+            COMMIT TRANSACTION;
             "#,
+                ]
+                .concat(),
             )
             .bind(("auth_id", auth))
             .bind(("slug", slug))
-            .bind(("fields", model.fields))
+            .bind(("table", match schema_model.is_collection {
+                true => slug,
+                false => "singles",
+            }))
+            .bind(("items", items))
+            .bind(("fields", match new_fields.is_empty() {
+                true => None,
+                false => Some(new_fields),
+            }))
             .await?
-            .take::<Option<SchemaModel>>(0)?
+            .take::<Option<SchemaModel>>(1)?
             .ok_or(DbError::EntryUpdate.into())
     }
 
+    async fn migration_preview(
+        &self,
+        slug: &str,
+        model: SchemaFieldsModel,
+    ) -> Result<SchemaMigrationModel> {
+        let schema_model = self.find_by_slug(slug).await?;
+        let old_fields = schema_model.fields.clone().unwrap_or_default();
+        let new_fields = model.fields.unwrap_or_default();
+        let changes = self.fields_diff(&old_fields, &new_fields);
+
+        let mut items = vec![];
+        if !changes.is_empty() {
+            for item in self.get_fields_items(&schema_model).await? {
+                let Some(fields) = item.fields else {
+                    continue;
+                };
+                let (migrated, errors) = self.migrate_fields(&old_fields, &new_fields, &fields);
+                if migrated.ne(&fields) || !errors.is_empty() {
+                    items.push(FieldMigrationItemModel {
+                        slug: item.slug,
+                        title: item.title,
+                        errors,
+                    });
+                }
+            }
+        }
+
+        Ok(SchemaMigrationModel { changes, items })
+    }
+
     async fn get_fields(&self, slug: &str) -> Result<SchemaFieldsModel> {
         let result: Option<SchemaModel> = self
             .db
@@ -303,6 +443,7 @@ impl SchemaRepositoryTrait for SchemaService {
         match result {
             Some(schema_model) => Ok(SchemaFieldsModel {
                 fields: schema_model.fields,
+                force: None,
             }),
             _ => Err(ApiError::from(DbError::EntryNotFound)),
         }
@@ -317,4 +458,190 @@ impl SchemaRepositoryTrait for SchemaService {
             .take::<Vec<RecordModel>>(0)?
         })
     }
+
+    async fn get_all(&self) -> Result<Vec<SchemaModel>> {
+        Ok(self
+            .db
+            .query(r#"SELECT * FROM schemas WHERE is_system = false ORDER BY slug;"#)
+            .await?
+            .take::<Vec<SchemaModel>>(0)?)
+    }
+
+    async fn define_collection(&self, slug: &str) -> Result<()> {
+        self.db
+            .query(
+                [
+                    "BEGIN TRANSACTION;",
+                    &collection_sql(slug),
+                    "COMMIT TRANSACTION;",
+                ]
+                .concat(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_permissions(&self, auth: &str, slug: &str, is_public: bool) -> Result<()> {
+        let mut actions = SCHEMA_OWN_ACTIONS.to_vec();
+        if !is_public {
+            actions.extend(SCHEMA_ACTIONS);
+        }
+
+        self.db
+            .query(
+                [
+                    "BEGIN TRANSACTION;",
+                    PERMISSIONS_CREATE_SQL,
+                    "COMMIT TRANSACTION;",
+                ]
+                .concat(),
+            )
+            .bind(("auth_id", auth))
+            .bind(("permissions", permission_slugs(slug, &actions)))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_permissions(&self, slug: &str) -> Result<()> {
+        self.db
+            .query(
+                [
+                    "BEGIN TRANSACTION;",
+                    PERMISSIONS_DELETE_SQL,
+                    "COMMIT TRANSACTION;",
+                ]
+                .concat(),
+            )
+            .bind((
+                "permissions",
+                permission_slugs(slug, &[&SCHEMA_ACTIONS[..], &SCHEMA_OWN_ACTIONS[..]].concat()),
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Every schema has the own permissions, the rest only while it isn't public.
+const SCHEMA_ACTIONS: [&str; 3] = ["read", "write", "delete"];
+const SCHEMA_OWN_ACTIONS: [&str; 2] = ["write_own", "delete_own"];
+
+const ITEMS_SQL: &str = r#"
+            FOR $item IN $items {
+                UPDATE type::table($table) SET fields = $item.fields, updated_by = $auth_id WHERE slug=$item.slug;
+            };
+            "#;
+
+const PERMISSIONS_CREATE_SQL: &str = r#"
+            FOR $permission IN $permissions {
+                LET $id = type::thing('permissions', string::replace($permission, '::', '_'));
+
+                CREATE $id CONTENT {
+                    slug: $permission,
+                    created_by: $auth_id
+                };
+                RELATE roles:administrator->role_permissions->$id;
+            };
+            "#;
+
+const PERMISSIONS_DELETE_SQL: &str = r#"
+            DELETE FROM role_permissions WHERE out.slug INSIDE $permissions;
+            DELETE FROM permissions WHERE slug INSIDE $permissions;
+            "#;
+
+const TO_COLLECTION_SQL: &str = r#"
+            LET $single = array::first((SELECT * FROM singles WHERE slug=$slug));
+
+            IF $single != NONE {
+                CREATE type::thing($slug, record::id($single.id)) CONTENT {
+                    slug: $single.slug,
+                    title: $single.title,
+                    fields: $single.fields,
+                    published: $single.published,
+                    groups: $single.groups,
+                    created_at: $single.created_at,
+                    created_by: $single.created_by,
+                    updated_by: $auth_id
+                };
+            };
+
+            DELETE FROM singles WHERE slug=$slug;
+            UPDATE schemas MERGE {
+                is_collection: true,
+                updated_by: $auth_id
+            } WHERE slug=$slug;
+            "#;
+
+fn field_ids(mut fields: Vec<FieldModel>) -> Vec<FieldModel> {
+    fields
+        .iter_mut()
+        .filter(|field| field.id.is_empty())
+        .for_each(|field| field.id = field.slug.clone());
+
+    fields
+}
+
+fn permission_slugs(slug: &str, actions: &[&str]) -> Vec<String> {
+    actions
+        .iter()
+        .map(|action| [slug, "::", action].concat())
+        .collect()
+}
+
+/// A collection becomes a single with its only item, or an empty one.
+fn to_single_sql(slug: &str) -> String {
+    format!(
+        r#"
+            LET $single_item = array::first((SELECT * FROM type::table($slug)));
+
+            IF $single_item != NONE {{
+                CREATE type::thing('singles', record::id($single_item.id)) CONTENT {{
+                    slug: $slug,
+                    title: $single_item.title,
+                    fields: $single_item.fields,
+                    published: $single_item.published,
+                    groups: $single_item.groups,
+                    created_at: $single_item.created_at,
+                    created_by: $single_item.created_by,
+                    updated_by: $auth_id
+                }};
+            }} ELSE {{
+                CREATE singles CONTENT {{
+                    slug: $slug,
+                    title: (SELECT VALUE title FROM schemas WHERE slug=$slug)[0],
+                    created_by: $auth_id,
+                    updated_by: $auth_id
+                }};
+            }};
+
+            REMOVE TABLE IF EXISTS {0};
+            UPDATE schemas MERGE {{
+                is_collection: false,
+                updated_by: $auth_id
+            }} WHERE slug=$slug;
+            "#,
+        slug
+    )
+}
+
+fn collection_sql(slug: &str) -> String {
+    format!(
+        r#"
+            DEFINE TABLE {0} SCHEMAFULL;
+            DEFINE FIELD slug ON TABLE {0} TYPE string;
+            DEFINE FIELD title ON TABLE {0} TYPE string;
+            DEFINE FIELD fields ON TABLE {0} FLEXIBLE TYPE option<object>;
+            DEFINE FIELD published ON TABLE {0} TYPE bool DEFAULT false;
+            DEFINE FIELD groups ON TABLE {0} TYPE option<array<string>>;
+            DEFINE FIELD created_at ON TABLE {0} TYPE datetime DEFAULT time::now();
+            DEFINE FIELD updated_at ON TABLE {0} TYPE datetime VALUE time::now();
+            DEFINE FIELD created_by ON TABLE {0} TYPE string;
+            DEFINE FIELD updated_by ON TABLE {0} TYPE string;
+            DEFINE INDEX idx_{0}_update ON TABLE {0} COLUMNS updated_at;
+            DEFINE INDEX idx_{0}_slug ON TABLE {0} COLUMNS slug UNIQUE;
+            "#,
+        slug
+    )
 }
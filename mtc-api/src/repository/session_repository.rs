@@ -0,0 +1,115 @@
+use axum::async_trait;
+
+use mtc_model::session_model::SessionModel;
+
+use crate::error::Result;
+use crate::model::request_model::ClientModel;
+use crate::service::session_service::{SessionService, SESSION_STORE_TABLE};
+
+#[async_trait]
+pub trait SessionRepositoryTrait {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<SessionModel>>;
+    async fn register(&self, id: &str, login: &str, client: &ClientModel) -> Result<()>;
+    async fn touch(&self, id: &str) -> Result<Option<String>>;
+    async fn delete(&self, login: &str, id: &str) -> Result<()>;
+    async fn delete_by_login(&self, login: &str, except: Option<&str>) -> Result<()>;
+}
+
+#[async_trait]
+impl SessionRepositoryTrait for SessionService {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<SessionModel>> {
+        // entries whose session has already expired from the store are dropped first
+        Ok(self
+            .db
+            .query(
+                r#"
+            DELETE user_sessions WHERE login=$login
+                AND type::thing($store, meta::id(id)).id = NONE;
+            SELECT * FROM user_sessions WHERE login=$login ORDER BY last_seen DESC;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("store", SESSION_STORE_TABLE))
+            .await?
+            .take::<Vec<SessionModel>>(1)?)
+    }
+
+    async fn register(&self, id: &str, login: &str, client: &ClientModel) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            CREATE type::thing('user_sessions', $id) CONTENT {
+                login: $login,
+                ip: $ip,
+                user_agent: $user_agent
+            };
+            "#,
+            )
+            .bind(("id", id))
+            .bind(("login", login))
+            .bind(("ip", client.ip.clone()))
+            .bind(("user_agent", client.user_agent.clone()))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn touch(&self, id: &str) -> Result<Option<String>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            IF (SELECT VALUE id FROM type::thing('user_sessions', $id)) THEN
+                (UPDATE type::thing('user_sessions', $id) SET last_seen = time::now() RETURN VALUE login)
+            END;
+            "#,
+            )
+            .bind(("id", id))
+            .await?
+            .take::<Option<String>>(0)?)
+    }
+
+    async fn delete(&self, login: &str, id: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            BEGIN TRANSACTION;
+
+            IF (SELECT VALUE id FROM type::thing('user_sessions', $id) WHERE login=$login) THEN
+                (DELETE type::thing($store, $id))
+            END;
+            DELETE type::thing('user_sessions', $id) WHERE login=$login;
+
+            COMMIT TRANSACTION;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("id", id))
+            .bind(("store", SESSION_STORE_TABLE))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_by_login(&self, login: &str, except: Option<&str>) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            BEGIN TRANSACTION;
+
+            FOR $session IN (SELECT VALUE meta::id(id) FROM user_sessions WHERE login=$login AND meta::id(id) != $except) {
+                DELETE type::thing($store, $session);
+                DELETE type::thing('user_sessions', $session);
+            };
+
+            COMMIT TRANSACTION;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("except", except.unwrap_or_default()))
+            .bind(("store", SESSION_STORE_TABLE))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use axum::async_trait;
+use chrono::{TimeDelta, Utc};
+use surrealdb::sql::Datetime;
+
+use mtc_model::pagination_model::CountModel;
+use mtc_model::sign_in_log_model::{SignInLogFilterModel, SignInLogModel};
+
+use crate::error::Result;
+use crate::model::sign_in_log_model::SignInLogEntryModel;
+use crate::repository::date_bound;
+use crate::service::sign_in_log_service::SignInLogService;
+
+const SIGN_IN_LOG_FILTER: &str = r#"
+    (!$login OR login = $login)
+    AND ($success = NONE OR success = $success)
+    AND (!$date_from OR created_at >= $date_from)
+    AND (!$date_to OR created_at < $date_to)
+"#;
+
+#[async_trait]
+pub trait SignInLogRepositoryTrait {
+    async fn create(&self, entry: SignInLogEntryModel) -> Result<()>;
+    async fn get_total(&self, filter: &SignInLogFilterModel) -> Result<usize>;
+    async fn get_page(
+        &self,
+        start: usize,
+        limit: usize,
+        filter: &SignInLogFilterModel,
+    ) -> Result<Vec<SignInLogModel>>;
+    async fn find_recent(&self, login: &str, limit: usize) -> Result<Vec<SignInLogModel>>;
+    async fn delete_expired(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl SignInLogRepositoryTrait for SignInLogService {
+    async fn create(&self, entry: SignInLogEntryModel) -> Result<()> {
+        self.db
+            .query(r#"CREATE sign_in_log CONTENT $entry;"#)
+            .bind(("entry", entry))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_total(&self, filter: &SignInLogFilterModel) -> Result<usize> {
+        let result = self
+            .db
+            .query(["SELECT count() FROM sign_in_log WHERE", SIGN_IN_LOG_FILTER, "GROUP ALL;"].concat())
+            .bind(("login", &filter.login))
+            .bind(("success", filter.success))
+            .bind(("date_from", date_bound(&filter.date_from, 0)))
+            .bind(("date_to", date_bound(&filter.date_to, 1)))
+            .await?
+            .take::<Option<CountModel>>(0)?;
+
+        Ok(result.map(|value| value.count).unwrap_or_default())
+    }
+
+    async fn get_page(
+        &self,
+        start: usize,
+        limit: usize,
+        filter: &SignInLogFilterModel,
+    ) -> Result<Vec<SignInLogModel>> {
+        Ok(self
+            .db
+            .query(
+                [
+                    "SELECT * FROM sign_in_log WHERE",
+                    SIGN_IN_LOG_FILTER,
+                    "ORDER BY created_at DESC LIMIT $limit START $start;",
+                ]
+                .concat(),
+            )
+            .bind(("login", &filter.login))
+            .bind(("success", filter.success))
+            .bind(("date_from", date_bound(&filter.date_from, 0)))
+            .bind(("date_to", date_bound(&filter.date_to, 1)))
+            .bind(("start", start - 1))
+            .bind(("limit", limit))
+            .await?
+            .take::<Vec<SignInLogModel>>(0)?)
+    }
+
+    async fn find_recent(&self, login: &str, limit: usize) -> Result<Vec<SignInLogModel>> {
+        Ok(self
+            .db
+            .query(r#"SELECT * FROM sign_in_log WHERE login = $login ORDER BY created_at DESC LIMIT $limit;"#)
+            .bind(("login", login.to_string()))
+            .bind(("limit", limit))
+            .await?
+            .take::<Vec<SignInLogModel>>(0)?)
+    }
+
+    async fn delete_expired(&self) -> Result<()> {
+        // zero retention keeps the history forever
+        if self.cfg.sign_in_retention <= 0 {
+            return Ok(());
+        }
+
+        self.db
+            .query(r#"DELETE sign_in_log WHERE created_at < $expired_at;"#)
+            .bind((
+                "expired_at",
+                Datetime::from(Utc::now() - TimeDelta::days(self.cfg.sign_in_retention)),
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
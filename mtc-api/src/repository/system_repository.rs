@@ -9,6 +9,8 @@ use crate::service::system_service::SystemService;
 pub trait SystemRepositoryTrait {
     async fn get_migrations(&self) -> Result<BTreeSet<String>>;
     async fn set_migrations(&self, migrations: BTreeSet<String>) -> Result<()>;
+    async fn get_auth_epoch(&self) -> Result<i64>;
+    async fn increment_auth_epoch(&self) -> Result<()>;
 }
 
 #[async_trait]
@@ -35,4 +37,21 @@ impl SystemRepositoryTrait for SystemService {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn get_auth_epoch(&self) -> Result<i64> {
+        Ok(self
+            .db
+            .query(r#"SELECT VALUE c_value from mtc_system WHERE c_key = 'auth_epoch';"#)
+            .await?
+            .take::<Option<i64>>(0)?
+            .unwrap_or_default())
+    }
+
+    async fn increment_auth_epoch(&self) -> Result<()> {
+        self.db
+            .query(r#"UPDATE mtc_system SET c_value += 1 WHERE c_key = 'auth_epoch';"#)
+            .await?;
+
+        Ok(())
+    }
+}
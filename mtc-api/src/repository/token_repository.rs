@@ -0,0 +1,106 @@
+use axum::async_trait;
+
+use mtc_model::token_model::{TokenCreateModel, TokenModel};
+
+use crate::error::db_error::DbError;
+use crate::error::Result;
+use crate::service::token_service::TokenService;
+
+#[async_trait]
+pub trait TokenRepositoryTrait {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<TokenModel>>;
+    async fn find_by_token(&self, token: &str) -> Result<TokenModel>;
+    async fn create(&self, login: &str, token: &str, model: TokenCreateModel)
+        -> Result<TokenModel>;
+    async fn delete(&self, login: &str, id: &str) -> Result<()>;
+    async fn update_usage(&self, id: &str, ip: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl TokenRepositoryTrait for TokenService {
+    async fn find_by_login(&self, login: &str) -> Result<Vec<TokenModel>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+            SELECT * FROM tokens WHERE login=$login ORDER BY created_at DESC;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Vec<TokenModel>>(0)?)
+    }
+
+    async fn find_by_token(&self, token: &str) -> Result<TokenModel> {
+        self.db
+            .query(
+                r#"
+            SELECT * FROM tokens WHERE hash=crypto::sha256($token)
+                AND (expires_at = NONE OR expires_at > time::now());
+            "#,
+            )
+            .bind(("token", token))
+            .await?
+            .take::<Option<TokenModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn create(
+        &self,
+        login: &str,
+        token: &str,
+        model: TokenCreateModel,
+    ) -> Result<TokenModel> {
+        self.db
+            .query(
+                r#"
+            CREATE tokens CONTENT {
+                login: $login,
+                title: $title,
+                hash: crypto::sha256($token),
+                scope: $scope,
+                expires_at: $expires_at
+            };
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("token", token))
+            .bind(("title", model.title))
+            .bind(("scope", model.scope))
+            .bind(("expires_at", model.expires_at))
+            .await?
+            .take::<Option<TokenModel>>(0)?
+            .ok_or(DbError::EntryAlreadyExists.into())
+    }
+
+    async fn delete(&self, login: &str, id: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            DELETE type::thing('tokens', $id) WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("id", id))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_usage(&self, id: &str, ip: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            UPDATE type::thing('tokens', $id) MERGE {
+                last_used_at: time::now(),
+                last_used_ip: $ip
+            };
+            "#,
+            )
+            .bind(("id", id))
+            .bind(("ip", ip))
+            .await?;
+
+        Ok(())
+    }
+}
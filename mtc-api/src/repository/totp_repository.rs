@@ -0,0 +1,148 @@
+use axum::async_trait;
+
+use crate::error::db_error::DbError;
+use crate::error::Result;
+use crate::model::totp_model::TotpModel;
+use crate::service::totp_service::TotpService;
+
+#[async_trait]
+pub trait TotpRepositoryTrait {
+    async fn find_by_login(&self, login: &str) -> Result<TotpModel>;
+    async fn is_required(&self, login: &str) -> Result<bool>;
+    async fn set_secret(&self, login: &str, secret: &str) -> Result<()>;
+    async fn enable(&self, login: &str, codes: &[String]) -> Result<()>;
+    async fn use_recovery_code(&self, login: &str, code: &str) -> Result<bool>;
+    async fn use_step(&self, login: &str, step: u64) -> Result<bool>;
+    async fn reset(&self, login: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl TotpRepositoryTrait for TotpService {
+    async fn find_by_login(&self, login: &str) -> Result<TotpModel> {
+        self.db
+            .query(
+                r#"
+            SELECT totp_secret, totp_enabled FROM users WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Option<TotpModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    async fn is_required(&self, login: &str) -> Result<bool> {
+        let required = self
+            .db
+            .query(
+                r#"
+            SELECT VALUE array::distinct((SELECT VALUE two_factor FROM fn::user_roles($login))) FROM users WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .await?
+            .take::<Option<Vec<Option<bool>>>>(0)?;
+
+        Ok(required
+            .unwrap_or_default()
+            .iter()
+            .any(|value| value.unwrap_or(false)))
+    }
+
+    async fn set_secret(&self, login: &str, secret: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            UPDATE users MERGE {
+                totp_secret: $secret,
+                totp_enabled: false,
+                totp_recovery: []
+            } WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("secret", secret))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn enable(&self, login: &str, codes: &[String]) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            BEGIN TRANSACTION;
+
+            UPDATE users MERGE {
+                totp_enabled: true,
+                totp_recovery: []
+            } WHERE login=$login;
+
+            FOR $code IN $codes {
+                UPDATE users SET totp_recovery += crypto::sha256($code) WHERE login=$login;
+            };
+
+            COMMIT TRANSACTION;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("codes", codes.to_vec()))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn use_recovery_code(&self, login: &str, code: &str) -> Result<bool> {
+        // the code is removed on match, so every recovery code works only once
+        Ok(self
+            .db
+            .query(
+                r#"
+            UPDATE users SET totp_recovery -= crypto::sha256($code)
+                WHERE login=$login AND totp_enabled = true AND totp_recovery CONTAINS crypto::sha256($code)
+                RETURN VALUE login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("code", code))
+            .await?
+            .take::<Option<String>>(0)?
+            .is_some())
+    }
+
+    async fn use_step(&self, login: &str, step: u64) -> Result<bool> {
+        // a code is refused once its step or a later one was accepted, so it can't be replayed
+        Ok(self
+            .db
+            .query(
+                r#"
+            UPDATE users SET totp_last_step = $step
+                WHERE login=$login AND (totp_last_step = NONE OR totp_last_step < $step)
+                RETURN VALUE login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("step", step))
+            .await?
+            .take::<Option<String>>(0)?
+            .is_some())
+    }
+
+    async fn reset(&self, login: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+            UPDATE users MERGE {
+                totp_secret: NONE,
+                totp_enabled: false,
+                totp_recovery: [],
+                totp_last_step: NONE
+            } WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .await?;
+
+        Ok(())
+    }
+}
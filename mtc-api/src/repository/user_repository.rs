@@ -1,16 +1,27 @@
-use argon2::password_hash::SaltString;
-use argon2::{Argon2, PasswordHasher};
 use axum::async_trait;
+use serde_json::Value;
 use surrealdb::sql::Datetime;
-use mtc_model::user_details_model::UserDetailsStateModel;
-use mtc_model::user_model::{UserCreateModel, UserModel, UserUpdateModel};
+use mtc_model::user_details_model::{PersonModel, UserDetailsStateModel};
+use mtc_model::user_model::{
+    UserCreateModel, UserFilterModel, UserModel, UserUpdateModel, UserValidityModel,
+};
 
-use crate::error::api_error::ApiError;
 use crate::error::db_error::DbError;
-use crate::error::session_error::SessionError;
 use crate::error::Result;
 use crate::model::access_model::AccessModel;
-use crate::service::user_service::UserService;
+use crate::repository::date_bound;
+use crate::service::user_service::{PasswordTrait, UserService};
+
+const USERS_FILTER: &str = r#"
+    AND (!$login OR string::contains(string::lowercase(login), string::lowercase($login)))
+    AND ($blocked = NONE OR blocked = $blocked)
+    AND (!$role OR $role INSIDE fn::user_roles(login).slug)
+    AND (!$group OR $group INSIDE fn::group_lineage(->user_groups->groups.slug))
+    AND (!$last_access_from OR last_access >= $last_access_from)
+    AND (!$last_access_to OR last_access < $last_access_to)
+    AND ($access_count_min = NONE OR access_count >= $access_count_min)
+    AND ($access_count_max = NONE OR access_count <= $access_count_max)
+"#;
 
 #[async_trait]
 pub trait UserRepositoryTrait {
@@ -19,10 +30,12 @@ pub trait UserRepositoryTrait {
         start: usize,
         limit: usize,
         access: &AccessModel,
+        filter: &UserFilterModel,
     ) -> Result<Vec<UserModel>>;
-    async fn get_total(&self, access: &AccessModel) -> Result<usize>;
+    async fn get_total(&self, access: &AccessModel, filter: &UserFilterModel) -> Result<usize>;
     async fn find_by_login(&self, login: &str, access: &AccessModel) -> Result<UserModel>;
     async fn create(&self, auth: &str, login: &str, model: &UserCreateModel) -> Result<UserModel>;
+    async fn create_external(&self, auth: &str, login: &str) -> Result<UserModel>;
     async fn update(&self, auth: &str, login: &str, model: &UserUpdateModel) -> Result<UserModel>;
     async fn delete(&self, login: &str) -> Result<()>;
     async fn role_assign(&self, user_id: &str, role_id: &str) -> Result<()>;
@@ -31,13 +44,36 @@ pub trait UserRepositoryTrait {
     async fn groups_drop(&self, user_id: &str) -> Result<()>;
     async fn block(&self, auth: &str, login: &str) -> Result<()>;
     async fn unblock(&self, auth: &str, login: &str) -> Result<()>;
+    async fn set_group_validity(
+        &self,
+        auth: &str,
+        group: &str,
+        model: &UserValidityModel,
+        access: &AccessModel,
+    ) -> Result<Vec<String>>;
+    async fn block_expired(&self) -> Result<Vec<String>>;
     async fn change_password(&self, login: &str, password: &str) -> Result<UserModel>;
+    async fn rehash_password(&self, login: &str, password: &str) -> Result<()>;
     async fn update_access(&self, login: &str, access_count: i32) -> Result<()>;
     async fn get_roles_min_access_level(&self, login: &str) -> Result<i32>;
     async fn get_roles_max_access_level(&self, login: &str) -> Result<i32>;
     async fn update_access_level(&self, login: &str, access_level: i32) -> Result<()>;
+    async fn update_access_levels(&self) -> Result<()>;
     async fn get_roles_access_all(&self, login: &str) -> Result<bool>;
     async fn get_users_state(&self, user_list: Vec<String>, access: &AccessModel) -> Result<Vec<UserDetailsStateModel>>;
+    async fn find_persons(&self, access: &AccessModel) -> Result<Vec<PersonModel>>;
+    async fn update_fields(&self, auth: &str, login: &str, fields: &Value) -> Result<()>;
+    async fn find_logins(&self, logins: &[String]) -> Result<Vec<String>>;
+    async fn find_logins_by_prefix(&self, prefix: &str) -> Result<Vec<String>>;
+    async fn provision(
+        &self,
+        auth: &str,
+        users: Vec<Value>,
+        group_id: &str,
+        role_ids: Vec<String>,
+        access_level: i32,
+        model: &UserValidityModel,
+    ) -> Result<()>;
 }
 
 #[async_trait]
@@ -47,6 +83,7 @@ impl UserRepositoryTrait for UserService {
         start: usize,
         limit: usize,
         access: &AccessModel,
+        filter: &UserFilterModel,
     ) -> Result<Vec<UserModel>> {
         let mut blocked_sql = "";
         if !access.users_all {
@@ -59,19 +96,31 @@ impl UserRepositoryTrait for UserService {
                     r#"
             SELECT * FROM users WHERE access_level > $access_level "#,
                     blocked_sql,
-                    r#"ORDER BY login LIMIT $limit START $start;"#,
+                    groups_sql(access),
+                    USERS_FILTER,
+                    sort_sql(&filter.sort),
+                    r#"LIMIT $limit START $start;"#,
                 ]
                 .concat(),
             )
             .bind(("start", start - 1))
             .bind(("limit", limit))
             .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone().unwrap_or_default()))
+            .bind(("login", &filter.login))
+            .bind(("blocked", filter.blocked))
+            .bind(("role", &filter.role))
+            .bind(("group", &filter.group))
+            .bind(("last_access_from", date_bound(&filter.last_access_from, 0)))
+            .bind(("last_access_to", date_bound(&filter.last_access_to, 1)))
+            .bind(("access_count_min", filter.access_count_min))
+            .bind(("access_count_max", filter.access_count_max))
             .await?;
         let result: Vec<UserModel> = result.take(0)?;
         Ok(result)
     }
 
-    async fn get_total(&self, access: &AccessModel) -> Result<usize> {
+    async fn get_total(&self, access: &AccessModel, filter: &UserFilterModel) -> Result<usize> {
         let mut blocked_sql = "";
         if !access.users_all {
             blocked_sql = "AND blocked = false ";
@@ -83,11 +132,22 @@ impl UserRepositoryTrait for UserService {
                     r#"
             SELECT count() FROM users WHERE access_level > $access_level "#,
                     blocked_sql,
+                    groups_sql(access),
+                    USERS_FILTER,
                     r#"GROUP ALL;"#,
                 ]
                 .concat(),
             )
             .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone().unwrap_or_default()))
+            .bind(("login", &filter.login))
+            .bind(("blocked", filter.blocked))
+            .bind(("role", &filter.role))
+            .bind(("group", &filter.group))
+            .bind(("last_access_from", date_bound(&filter.last_access_from, 0)))
+            .bind(("last_access_to", date_bound(&filter.last_access_to, 1)))
+            .bind(("access_count_min", filter.access_count_min))
+            .bind(("access_count_max", filter.access_count_max))
             .await?;
         let result: Option<mtc_model::pagination_model::CountModel> = result.take(0)?;
         match result {
@@ -107,28 +167,21 @@ impl UserRepositoryTrait for UserService {
                 [
                     r#"SELECT * FROM users WHERE login=$login AND access_level > $access_level"#,
                     blocked_sql,
+                    groups_sql(access),
                 ]
                 .concat(),
             )
             .bind(("login", login.to_string()))
             .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone().unwrap_or_default()))
             .await?
             .take::<Option<UserModel>>(0)?
             .ok_or(DbError::EntryNotFound.into())
     }
 
     async fn create(&self, auth: &str, login: &str, model: &UserCreateModel) -> Result<UserModel> {
-        let password = model.password.as_bytes();
-        let salt = match SaltString::from_b64(&self.cfg.password_salt) {
-            Ok(value) => value,
-            _ => Err(ApiError::from(SessionError::PasswordHash))?,
-        };
-
-        let argon2 = Argon2::default();
-        let password_hash = match argon2.hash_password(password, &salt) {
-            Ok(value) => value.to_string(),
-            _ => Err(ApiError::from(SessionError::PasswordHash))?,
-        };
+        self.password_policy(login, &model.password)?;
+        let password_hash = self.hash_password(&model.password)?;
 
         self.db
             .query(
@@ -136,7 +189,11 @@ impl UserRepositoryTrait for UserService {
                 CREATE users CONTENT {
 	                login: $login,
 	                password: $password,
+	                must_change_password: true,
+	                password_changed_at: time::now(),
 	                blocked: $blocked,
+	                valid_from: $valid_from,
+	                valid_until: $valid_until,
 	                created_by: $auth_id,
 	                updated_by: $auth_id
                 };
@@ -146,31 +203,57 @@ impl UserRepositoryTrait for UserService {
             .bind(("login", login.to_uppercase()))
             .bind(("password", password_hash))
             .bind(("blocked", model.blocked))
+            .bind(("valid_from", model.valid_from.clone()))
+            .bind(("valid_until", model.valid_until.clone()))
             .await?
             .take::<Option<UserModel>>(0)?
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    /// Accounts made on the first single sign-on get a password nobody knows and never expires,
+    /// an administrator sets a real one when the account also has to work offline.
+    async fn create_external(&self, auth: &str, login: &str) -> Result<UserModel> {
+        let password_hash = self.hash_password(&self.generate_password(login))?;
+
+        self.db
+            .query(
+                r#"
+                CREATE users CONTENT {
+	                login: $login,
+	                password: $password,
+	                must_change_password: false,
+	                blocked: false,
+	                created_by: $auth_id,
+	                updated_by: $auth_id
+                };
+                "#,
+            )
+            .bind(("auth_id", auth))
+            .bind(("login", login.to_uppercase()))
+            .bind(("password", password_hash))
+            .await?
+            .take::<Option<UserModel>>(0)?
+            .ok_or(DbError::EntryNotFound.into())
+    }
+
+    /// A block set by the expiry job is lifted once the validity is extended, one set by hand stays.
     async fn update(&self, auth: &str, login: &str, model: &UserUpdateModel) -> Result<UserModel> {
         match &model.password {
             Some(value) => {
-                let password = value.as_bytes();
-                let salt = match SaltString::from_b64(&self.cfg.password_salt) {
-                    Ok(value) => value,
-                    _ => Err(ApiError::from(SessionError::PasswordHash))?,
-                };
+                self.password_policy(login, value)?;
+                let password_hash = self.hash_password(value)?;
 
-                let argon2 = Argon2::default();
-                let password_hash = match argon2.hash_password(password, &salt) {
-                    Ok(value) => value.to_string(),
-                    _ => Err(ApiError::from(SessionError::PasswordHash))?,
-                };
                 self.db
                     .query(
                         r#"
                         UPDATE users MERGE {
                             password: $password,
-                            blocked: $blocked,
+                            must_change_password: true,
+                            password_changed_at: time::now(),
+                            blocked: $blocked AND (blocked_reason != 'expired' OR ($valid_until != NONE AND $valid_until <= time::now())),
+                            blocked_reason: IF $blocked AND $valid_until != NONE AND $valid_until <= time::now() THEN blocked_reason ELSE NONE END,
+                            valid_from: $valid_from,
+                            valid_until: $valid_until,
 	                        fields: $fields,
 	                        updated_by: $auth_id
                         } WHERE login=$login;
@@ -180,6 +263,8 @@ impl UserRepositoryTrait for UserService {
                     .bind(("login", login))
                     .bind(("password", password_hash))
                     .bind(("blocked", model.blocked))
+                    .bind(("valid_from", model.valid_from.clone()))
+                    .bind(("valid_until", model.valid_until.clone()))
                     .bind(("fields", model.fields.clone()))
                     .await?
                     .take::<Option<UserModel>>(0)?
@@ -190,7 +275,10 @@ impl UserRepositoryTrait for UserService {
                 .query(
                     r#"
                     UPDATE users MERGE {
-                        blocked: $blocked,
+                        blocked: $blocked AND (blocked_reason != 'expired' OR ($valid_until != NONE AND $valid_until <= time::now())),
+                        blocked_reason: IF $blocked AND $valid_until != NONE AND $valid_until <= time::now() THEN blocked_reason ELSE NONE END,
+                        valid_from: $valid_from,
+                        valid_until: $valid_until,
 	                    fields: $fields,
 	                    updated_by: $auth_id
                     } WHERE login=$login;
@@ -199,6 +287,8 @@ impl UserRepositoryTrait for UserService {
                 .bind(("auth_id", auth))
                 .bind(("login", login))
                 .bind(("blocked", model.blocked))
+                .bind(("valid_from", model.valid_from.clone()))
+                .bind(("valid_until", model.valid_until.clone()))
                 .bind(("fields", model.fields.clone()))
                 .await?
                 .take::<Option<UserModel>>(0)?
@@ -292,6 +382,7 @@ impl UserRepositoryTrait for UserService {
                 r#"
                     UPDATE users MERGE {
 	                    blocked: true,
+	                    blocked_reason: NONE,
 	                    updated_by: $auth_id
                     } WHERE login=$login;
                     "#,
@@ -312,6 +403,7 @@ impl UserRepositoryTrait for UserService {
                 r#"
                     UPDATE users MERGE {
 	                    blocked: false,
+	                    blocked_reason: NONE,
 	                    updated_by: $auth_id
                     } WHERE login=$login;
                     "#,
@@ -325,24 +417,69 @@ impl UserRepositoryTrait for UserService {
         }
     }
 
+    /// Covers the members of the subgroups too, the ones blocked by the expiry job are
+    /// unblocked when the new period has not ended yet.
+    async fn set_group_validity(
+        &self,
+        auth: &str,
+        group: &str,
+        model: &UserValidityModel,
+        access: &AccessModel,
+    ) -> Result<Vec<String>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+                UPDATE users MERGE {
+                    blocked: blocked AND (blocked_reason != 'expired' OR ($valid_until != NONE AND $valid_until <= time::now())),
+                    blocked_reason: IF $valid_until = NONE OR $valid_until > time::now() THEN NONE ELSE blocked_reason END,
+                    valid_from: $valid_from,
+                    valid_until: $valid_until,
+                    updated_by: $auth_id
+                } WHERE $group INSIDE fn::group_lineage(->user_groups->groups.slug)
+                    AND access_level > $access_level
+                    AND ($access_groups = NONE OR ->user_groups->groups.slug ANYINSIDE $access_groups)
+                RETURN VALUE login;
+                "#,
+            )
+            .bind(("auth_id", auth))
+            .bind(("group", group))
+            .bind(("valid_from", model.valid_from.clone()))
+            .bind(("valid_until", model.valid_until.clone()))
+            .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone()))
+            .await?
+            .take::<Vec<String>>(0)?)
+    }
+
+    async fn block_expired(&self) -> Result<Vec<String>> {
+        Ok(self
+            .db
+            .query(
+                r#"
+                UPDATE users MERGE {
+                    blocked: true,
+                    blocked_reason: 'expired',
+                    updated_by: 'system'
+                } WHERE blocked = false AND valid_until != NONE AND valid_until < time::now()
+                RETURN VALUE login;
+                "#,
+            )
+            .await?
+            .take::<Vec<String>>(0)?)
+    }
+
     async fn change_password(&self, login: &str, password: &str) -> Result<UserModel> {
-        let password = password.as_bytes();
-        let salt = match SaltString::from_b64(&self.cfg.password_salt) {
-            Ok(value) => value,
-            _ => Err(ApiError::from(SessionError::PasswordHash))?,
-        };
-
-        let argon2 = Argon2::default();
-        let password_hash = match argon2.hash_password(password, &salt) {
-            Ok(value) => value.to_string(),
-            _ => Err(ApiError::from(SessionError::PasswordHash))?,
-        };
+        self.password_policy(login, password)?;
+        let password_hash = self.hash_password(password)?;
 
         self.db
             .query(
                 r#"
                 UPDATE users MERGE {
                     password: $password,
+                    must_change_password: false,
+                    password_changed_at: time::now(),
                 } WHERE login=$login;
             "#,
             )
@@ -353,6 +490,22 @@ impl UserRepositoryTrait for UserService {
             .ok_or(DbError::EntryNotFound.into())
     }
 
+    async fn rehash_password(&self, login: &str, password: &str) -> Result<()> {
+        self.db
+            .query(
+                r#"
+                UPDATE users MERGE {
+                    password: $password
+                } WHERE login=$login;
+            "#,
+            )
+            .bind(("login", login))
+            .bind(("password", self.hash_password(password)?))
+            .await?;
+
+        Ok(())
+    }
+
     async fn update_access(&self, login: &str, access_count: i32) -> Result<()> {
         self.db
             .query(
@@ -373,7 +526,7 @@ impl UserRepositoryTrait for UserService {
 
     async fn get_roles_min_access_level(&self, login: &str) -> Result<i32> {
         Ok(self.db.query(r#"
-            SELECT VALUE math::min(->user_roles->roles.user_access_level) FROM users WHERE login=$login
+            SELECT VALUE math::min((SELECT VALUE user_access_level FROM fn::user_roles($login))) FROM users WHERE login=$login
             "#)
             .bind(("login", login))
             .await?
@@ -383,7 +536,7 @@ impl UserRepositoryTrait for UserService {
 
     async fn get_roles_max_access_level(&self, login: &str) -> Result<i32> {
         Ok(self.db.query(r#"
-            SELECT VALUE math::max(->user_roles->roles.user_access_level) FROM users WHERE login=$login
+            SELECT VALUE math::max((SELECT VALUE user_access_level FROM fn::user_roles($login))) FROM users WHERE login=$login
             "#)
             .bind(("login", login))
             .await?
@@ -407,9 +560,24 @@ impl UserRepositoryTrait for UserService {
         Ok(())
     }
 
+    /// Recalculates the access level of every user, after the roles of a group have changed.
+    async fn update_access_levels(&self) -> Result<()> {
+        self.db
+            .query(
+                r#"
+                UPDATE users SET
+                    access_level = math::max((SELECT VALUE user_access_level FROM fn::user_roles(login))) ?? 999;
+            "#,
+            )
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
     async fn get_roles_access_all(&self, login: &str) -> Result<bool> {
         let access = self.db.query(r#"
-            SELECT VALUE array::distinct(->user_roles->roles.user_access_all) FROM users WHERE login=$login
+            SELECT VALUE array::distinct((SELECT VALUE user_access_all FROM fn::user_roles($login))) FROM users WHERE login=$login
             "#)
             .bind(("login", login))
             .await?
@@ -426,15 +594,151 @@ impl UserRepositoryTrait for UserService {
         self.db
             .query(
                 [
-                    r#"SELECT login, blocked, access_count, last_access FROM users WHERE login in $users AND access_level > $access_level"#,
+                    r#"SELECT login, blocked, access_count, last_access, failed_count, locked_until FROM users WHERE login in $users AND access_level > $access_level"#,
                     blocked_sql,
+                    groups_sql(access),
                 ]
                     .concat(),
             )
             .bind(("users", user_list))
             .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone().unwrap_or_default()))
             .await?
             .take::<Option<Vec<UserDetailsStateModel>>>(0)?
             .ok_or(DbError::EntryNotFound.into())
     }
+
+    async fn find_persons(&self, access: &AccessModel) -> Result<Vec<PersonModel>> {
+        let mut blocked_sql = "";
+        if !access.users_all {
+            blocked_sql = "AND blocked = false ";
+        }
+
+        Ok(self
+            .db
+            .query(
+                [
+                    r#"SELECT login, fields FROM users WHERE access_level > $access_level "#,
+                    blocked_sql,
+                    groups_sql(access),
+                    r#"ORDER BY login;"#,
+                ]
+                .concat(),
+            )
+            .bind(("access_level", access.users_level))
+            .bind(("access_groups", access.groups.clone().unwrap_or_default()))
+            .await?
+            .take::<Vec<PersonModel>>(0)?)
+    }
+
+    async fn update_fields(&self, auth: &str, login: &str, fields: &Value) -> Result<()> {
+        self.db
+            .query(
+                r#"
+                UPDATE users MERGE {
+                    fields: $fields,
+                    updated_by: $auth_id
+                } WHERE login=$login;
+                "#,
+            )
+            .bind(("auth_id", auth))
+            .bind(("login", login))
+            .bind(("fields", fields.clone()))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_logins(&self, logins: &[String]) -> Result<Vec<String>> {
+        Ok(self
+            .db
+            .query(r#"SELECT VALUE login FROM users WHERE login INSIDE $logins;"#)
+            .bind(("logins", logins))
+            .await?
+            .take::<Vec<String>>(0)?)
+    }
+
+    async fn find_logins_by_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .db
+            .query(r#"SELECT VALUE login FROM users WHERE string::starts_with(login, $prefix);"#)
+            .bind(("prefix", prefix))
+            .await?
+            .take::<Vec<String>>(0)?)
+    }
+
+    async fn provision(
+        &self,
+        auth: &str,
+        users: Vec<Value>,
+        group_id: &str,
+        role_ids: Vec<String>,
+        access_level: i32,
+        model: &UserValidityModel,
+    ) -> Result<()> {
+        self.db
+            .query(
+                r#"
+                BEGIN TRANSACTION;
+
+                LET $group = type::thing('groups', $group_id);
+                FOR $user IN $users {
+                    LET $user_id = (CREATE users CONTENT {
+                        login: $user.login,
+                        password: $user.password,
+                        must_change_password: true,
+                        password_changed_at: time::now(),
+                        blocked: false,
+                        access_level: $access_level,
+                        valid_from: $valid_from,
+                        valid_until: $valid_until,
+                        fields: $user.fields,
+                        created_by: $auth_id,
+                        updated_by: $auth_id
+                    } RETURN VALUE id)[0];
+
+                    RELATE $user_id->user_groups->$group;
+                    FOR $role_id IN $role_ids {
+                        LET $role = type::thing('roles', $role_id);
+                        RELATE $user_id->user_roles->$role;
+                    };
+                };
+
+                COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(("auth_id", auth))
+            .bind(("users", users))
+            .bind(("group_id", group_id))
+            .bind(("role_ids", role_ids))
+            .bind(("access_level", access_level))
+            .bind(("valid_from", model.valid_from.clone()))
+            .bind(("valid_until", model.valid_until.clone()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+/// Delegated group administrators only reach the members of the groups they manage.
+fn groups_sql(access: &AccessModel) -> &'static str {
+    match access.groups {
+        Some(_) => " AND ->user_groups->groups.slug ANYINSIDE $access_groups ",
+        None => "",
+    }
+}
+
+/// Sorting can't be bound as a parameter, so only the known keys are turned into SQL.
+fn sort_sql(sort: &Option<String>) -> &'static str {
+    match sort.as_deref() {
+        Some("login_desc") => " ORDER BY login DESC ",
+        Some("last_access") => " ORDER BY last_access, login ",
+        Some("last_access_desc") => " ORDER BY last_access DESC, login ",
+        Some("access_count") => " ORDER BY access_count, login ",
+        Some("access_count_desc") => " ORDER BY access_count DESC, login ",
+        Some("created_at") => " ORDER BY created_at, login ",
+        Some("created_at_desc") => " ORDER BY created_at DESC, login ",
+        _ => " ORDER BY login ",
+    }
 }
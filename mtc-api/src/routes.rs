@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use axum::http::header::{CONTENT_TYPE, COOKIE};
-use axum::http::HeaderValue;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, COOKIE};
+use axum::http::{HeaderName, HeaderValue};
 use axum::middleware::from_fn_with_state;
 use axum::Router;
 use axum::routing::{delete, get, post};
@@ -10,16 +10,25 @@ use tower_http::cors::CorsLayer;
 use tracing::log::info;
 
 use crate::handler::api_handler::*;
+use crate::handler::audit_handler::*;
 use crate::handler::auth_handler::*;
+use crate::handler::certificate_handler::*;
 use crate::handler::group_handler::*;
 use crate::handler::health_handler::*;
+use crate::handler::identity_handler::*;
 use crate::handler::migration_handler::*;
+use crate::handler::person_handler::*;
 use crate::handler::permissions_handler::*;
 use crate::handler::role_handler::*;
 use crate::handler::schema_handler::*;
 use crate::handler::storage_handler::*;
+use crate::handler::session_handler::*;
+use crate::handler::sign_in_log_handler::*;
+use crate::handler::token_handler::*;
+use crate::handler::totp_handler::*;
 use crate::handler::user_handler::*;
 use crate::middleware::auth_middleware::middleware_auth_handler;
+use crate::provider::config_provider::AUTH_CHANGED_HEADER;
 use crate::state::AppState;
 
 pub fn routes(
@@ -30,7 +39,8 @@ pub fn routes(
     info!("\x1b[38;5;6mFront end CORS allowed URL: \x1b[38;5;13m{front_end_url}\x1b[0m");
     let cors_layer = CorsLayer::new()
         .allow_origin(front_end_url.parse::<HeaderValue>().unwrap())
-        .allow_headers([CONTENT_TYPE, COOKIE])
+        .allow_headers([CONTENT_TYPE, COOKIE, AUTHORIZATION])
+        .expose_headers([HeaderName::from_static(AUTH_CHANGED_HEADER)])
         .allow_methods([
             axum::http::Method::GET,
             axum::http::Method::POST,
@@ -51,26 +61,53 @@ pub fn routes(
         .route("/storage/:path/:file", delete(storage_delete_handler))
         .route("/storage/:path", get(storage_get_dir_handler).post(storage_upload_handler))
         
+        .route("/schema/:slug/fields/preview", post(schema_migration_preview_handler))
         .route("/schema/:slug/fields", get(schema_get_fields_handler).post(schema_update_fields_handler))
         .route("/schema/:slug", post(schema_create_handler).get(schema_get_handler).patch(schema_update_handler).delete(schema_delete_handler))
+        .route("/schema/bundle/preview", post(schema_bundle_preview_handler))
+        .route("/schema/bundle", get(schema_bundle_export_handler).post(schema_bundle_import_handler))
         .route("/schema/collections", get(schema_get_all_collections_handler))
         .route("/schema/list/:page", get(schema_list_handler))
         .route("/schema/list", get(schema_list_handler).delete(schema_list_delete_handler))
 
         .route("/users/state", get(users_get_state))
-            
-        //todo: additional user fields
+        .route("/users/provision", post(user_provision_handler))
+
+        .route("/persons/import/preview", post(person_import_preview_handler))
+        .route("/persons/import", post(person_import_handler))
+        .route("/persons/export", get(person_export_handler))
+        .route("/persons/profile", get(person_profile_handler))
+        .route("/persons", get(person_list_handler))
+
+        .route("/user/:login/lockout", delete(user_unlock_handler))
+        .route("/user/:login/impersonate", post(user_impersonate_handler))
+        .route("/user/:login/totp", delete(user_totp_reset_handler))
+        .route("/user/:login/sessions", get(user_session_list_handler).delete(user_session_delete_handler))
+        .route("/user/:login/sign_ins", get(user_sign_in_log_handler))
+        .route("/user/:login/certificates/:id", delete(user_certificate_delete_handler))
+        .route("/user/:login/certificates", get(user_certificate_list_handler).post(user_certificate_create_handler))
+        .route("/user/:login/identities/:id", delete(user_identity_delete_handler))
+        .route("/user/:login/identities", get(user_identity_list_handler))
+        .route("/user/:login/tokens/:id", delete(user_token_delete_handler))
+        .route("/user/:login/tokens", get(user_token_list_handler))
         .route("/user/:login/permissions", get(user_get_permissions_handler))
+        .route("/user/:login/permissions/effective", get(user_get_effective_permissions_handler))
         .route("/user/:login/groups", get(user_get_groups_handler).post(user_set_groups_handler))
         .route("/user/:login/roles", get(user_get_roles_handler).post(user_set_roles_handler))
+        .route("/user/:login/roles/inherited", get(user_get_inherited_roles_handler))
         .route("/user/:login", post(user_create_handler).get(user_get_handler).patch(user_update_handler).delete(user_delete_handler))
         .route("/user/list/:page", get(user_list_handler))
         .route("/user/list", get(user_list_handler).delete(user_list_delete_handler))
 
+        .route("/group/:slug/validity", post(group_validity_handler))
+        .route("/group/:slug/parent", post(group_parent_handler))
+        .route("/group/:slug/roles", get(group_get_roles_handler).post(group_set_roles_handler))
+        .route("/group/:slug/managers", get(group_get_managers_handler).post(group_set_managers_handler))
         .route("/group/:slug", get(group_get_handler).post(group_create_handler).patch(group_update_handler).delete(group_delete_handler))
         .route("/group/list/:page", get(group_list_handler))
         .route("/group/list", get(group_list_handler).delete(group_list_delete_handler))
         .route("/group/all", get(group_all_handler))
+        .route("/group/tree", get(group_tree_handler))
 
         .route("/role/:slug/permissions", get(role_get_permissions).post(role_set_permissions))
         .route("/role/:slug", get(role_get_handler).post(role_create_handler).patch(role_update_handler).delete(role_delete_handler))
@@ -81,9 +118,31 @@ pub fn routes(
         .route("/permissions/custom", get(permissions_get_custom).post(permissions_create_custom).delete(permissions_delete_custom))
         .route("/permissions", get(permissions_list_handler))
 
+        .route("/auth/totp/setup", post(totp_setup_handler))
+        .route("/auth/totp/enable", post(totp_enable_handler))
+        .route("/auth/totp", get(totp_status_handler).post(totp_sign_in_handler).delete(totp_disable_handler))
+        .route("/auth/sessions/:id", delete(session_delete_handler))
+        .route("/auth/sessions", get(session_list_handler).delete(session_delete_others_handler))
+        .route("/auth/sign_ins", get(sign_in_log_handler))
+        .route("/auth/certificate", get(certificate_get_handler).post(certificate_sign_in_handler))
+        .route("/auth/oidc/sign_in", get(oidc_sign_in_handler))
+        .route("/auth/oidc/link", get(oidc_link_handler))
+        .route("/auth/oidc/callback", get(oidc_callback_handler))
+        .route("/auth/oidc", get(oidc_provider_handler))
+        .route("/auth/identities/:id", delete(identity_delete_handler))
+        .route("/auth/identities", get(identity_list_handler))
+        .route("/auth/tokens/:id", delete(token_delete_handler))
+        .route("/auth/tokens", get(token_list_handler).post(token_create_handler))
         .route("/auth/change", post(change_password_handler))
+        .route("/auth/impersonate", delete(impersonation_end_handler))
         .route("/auth", get(get_credentials_handler).post(sign_in_handler).delete(sign_out_handler))
 
+        .route("/audit/export", get(audit_export_handler))
+        .route("/audit/list/:page", get(audit_list_handler))
+        .route("/audit/list", get(audit_list_handler))
+        .route("/audit/sign_ins/:page", get(sign_in_log_list_handler))
+        .route("/audit/sign_ins", get(sign_in_log_list_handler))
+
         .route("/migration", get(get_migrations_handler).post(migration_handler))
         .route("/health", get(health_handler))
 
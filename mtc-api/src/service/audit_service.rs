@@ -0,0 +1,110 @@
+use axum::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use tower_sessions::Session;
+use tracing::error;
+
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::audit_model::AuditEntryModel;
+use crate::model::request_model::ClientModel;
+use crate::repository::audit_repository::AuditRepositoryTrait;
+use crate::state::AppState;
+
+crate::impl_service!(AuditService);
+
+pub const AUDIT_CSV_HEADER: &str = "created_at,actor,action,target,ip,before,after";
+
+static AUDIT_SECRET_KEYS: &[&str] = &[
+    "password",
+    "old_password",
+    "new_password",
+    "totp_secret",
+    "totp_recovery",
+    "token",
+    "code",
+];
+
+/// Compact JSON of a model for the before/after columns, secrets are never written.
+pub fn audit_summary<T: Serialize>(value: &T) -> Option<String> {
+    let mut value = serde_json::to_value(value).ok()?;
+
+    if let Value::Object(map) = &mut value {
+        AUDIT_SECRET_KEYS.iter().for_each(|key| {
+            map.remove(*key);
+        });
+    }
+
+    match value {
+        Value::Null => None,
+        value => Some(value.to_string()),
+    }
+}
+
+#[async_trait]
+pub trait AuditTrait {
+    async fn audit(
+        &self,
+        session: &Session,
+        client: &ClientModel,
+        action: &str,
+        target: &str,
+        before: Option<String>,
+        after: Option<String>,
+    );
+    async fn audit_as(
+        &self,
+        actor: &str,
+        client: &ClientModel,
+        action: &str,
+        target: &str,
+        before: Option<String>,
+        after: Option<String>,
+    );
+}
+
+#[async_trait]
+impl AuditTrait for AppState {
+    async fn audit(
+        &self,
+        session: &Session,
+        client: &ClientModel,
+        action: &str,
+        target: &str,
+        before: Option<String>,
+        after: Option<String>,
+    ) {
+        // while impersonating, the administrator stays the one who acts
+        let actor = match session.credentials().await {
+            Ok(auth) => auth.impersonator.unwrap_or(auth.id),
+            _ => String::new(),
+        };
+
+        self.audit_as(&actor, client, action, target, before, after)
+            .await
+    }
+
+    /// For requests that don't carry the session of the one who acts.
+    async fn audit_as(
+        &self,
+        actor: &str,
+        client: &ClientModel,
+        action: &str,
+        target: &str,
+        before: Option<String>,
+        after: Option<String>,
+    ) {
+        let entry = AuditEntryModel {
+            actor: actor.to_string(),
+            action: action.to_string(),
+            target: target.to_string(),
+            before,
+            after,
+            ip: client.ip.clone(),
+        };
+
+        // the change itself is already done, a failed entry must not turn it into an error
+        if let Err(e) = self.audit_service.create(entry).await {
+            error!("Audit log: {}", e.to_string())
+        }
+    }
+}
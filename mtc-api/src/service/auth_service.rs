@@ -0,0 +1,293 @@
+use axum::async_trait;
+use tower_sessions::cookie::time::Duration;
+use tower_sessions::{Expiry, Session};
+
+use mtc_model::auth_model::{AuthModel, AuthModelTrait};
+use mtc_model::list_model::StringListModel;
+use mtc_model::user_model::UserModel;
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::error::Result;
+use crate::middleware::auth_middleware::UserSession;
+use crate::model::access_model::AccessModel;
+use crate::model::audit_model::AuditEntryModel;
+use crate::model::request_model::ClientModel;
+use crate::provider::config_provider::SESSION_EPOCH_KEY;
+use crate::repository::audit_repository::AuditRepositoryTrait;
+use crate::repository::group_repository::GroupRepositoryTrait;
+use crate::repository::lockout_repository::LockoutRepositoryTrait;
+use crate::repository::permissions_repository::PermissionsRepositoryTrait;
+use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::group_service::GroupTreeTrait;
+use crate::service::lockout_service::LockoutTrait;
+use crate::service::sign_in_log_service::SignInLogTrait;
+use crate::service::user_service::{AccountTrait, PasswordTrait};
+use crate::state::AppState;
+
+#[async_trait]
+pub trait AuthTrait {
+    async fn user_credentials(&self, login: &str) -> Result<AuthModel>;
+    async fn user_access(&self, login: &str) -> Result<AccessModel>;
+    async fn user_scope(&self, login: &str) -> Result<Option<Vec<String>>>;
+    async fn user_sign_in(
+        &self,
+        session: &Session,
+        user_model: &UserModel,
+        client: &ClientModel,
+    ) -> Result<AuthModel>;
+    async fn sign_in_failed(&self, login: Option<&str>, ip: &str) -> Result<()>;
+    async fn account_check(&self, user_model: &UserModel) -> Result<()>;
+    async fn user_impersonate(&self, session: &Session, login: &str) -> Result<AuthModel>;
+    async fn impersonation_end(&self, session: &Session) -> Result<AuthModel>;
+    async fn block_expired_users(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl AuthTrait for AppState {
+    async fn user_credentials(&self, login: &str) -> Result<AuthModel> {
+        let mut permissions = self
+            .permissions_service
+            .find_by_user(login)
+            .await
+            .unwrap_or(StringListModel {
+                list: vec!["content::read".to_string()],
+            })
+            .list;
+
+        // delegated group administrators manage their own people without a role granting it
+        if self.user_scope(login).await?.is_some() {
+            permissions.extend(["user::read".to_string(), "user::write".to_string()]);
+        }
+
+        // membership is inherited upwards, managers also see the groups below their own
+        let groups = self
+            .group_service
+            .find_by_user(login)
+            .await
+            .unwrap_or_default()
+            .list;
+        let groups = self
+            .group_service
+            .expand(
+                groups,
+                permissions.iter().any(|value| value == "group::descendants"),
+            )
+            .await?;
+
+        Ok(AuthModel {
+            id: login.to_string(),
+            roles: self
+                .role_service
+                .find_by_user(login)
+                .await
+                .unwrap_or_default()
+                .list,
+            groups,
+            permissions,
+            password_change: false,
+            impersonator: None,
+        })
+    }
+
+    async fn user_access(&self, login: &str) -> Result<AccessModel> {
+        let groups = self.user_scope(login).await?;
+        let mut users_level = self
+            .user_service
+            .get_roles_min_access_level(login)
+            .await
+            .unwrap_or(999);
+        // a delegated administrator without roles still reaches the members without roles
+        if groups.is_some() {
+            users_level = users_level.min(998);
+        }
+
+        Ok(AccessModel {
+            users_level,
+            users_all: self
+                .user_service
+                .get_roles_access_all(login)
+                .await
+                .unwrap_or(false),
+            groups,
+        })
+    }
+
+    /// The groups a delegated administrator is limited to, none when the roles already reach every user.
+    async fn user_scope(&self, login: &str) -> Result<Option<Vec<String>>> {
+        let managed = self
+            .group_service
+            .find_managed_by_user(login)
+            .await
+            .unwrap_or_default()
+            .list;
+        if managed.is_empty() {
+            return Ok(None);
+        }
+
+        let rules = AuthModel {
+            permissions: self
+                .permissions_service
+                .find_by_user(login)
+                .await
+                .unwrap_or_default()
+                .list,
+            ..Default::default()
+        };
+        if rules.is_permission("user::read") {
+            return Ok(None);
+        }
+
+        Ok(Some(self.group_service.descendants(managed).await?))
+    }
+
+    async fn user_sign_in(
+        &self,
+        session: &Session,
+        user_model: &UserModel,
+        client: &ClientModel,
+    ) -> Result<AuthModel> {
+        // until the password is changed the session carries no roles or permissions at all
+        let auth_model = match self.user_service.is_password_change_required(user_model) {
+            true => AuthModel {
+                id: user_model.login.clone(),
+                roles: vec![],
+                groups: vec![],
+                permissions: vec![],
+                password_change: true,
+                impersonator: None,
+            },
+            false => self.user_credentials(&user_model.login).await?,
+        };
+
+        if &auth_model.id != "anonymous" {
+            session.set_expiry(Some(Expiry::OnInactivity(Duration::minutes(
+                self.cfg.session_expiration,
+            ))));
+        }
+
+        self.user_service
+            .update_access(&user_model.login, user_model.access_count + 1)
+            .await?;
+        self.lockout_service.login_reset(&user_model.login).await?;
+
+        session
+            .set_access(self.user_access(&user_model.login).await?)
+            .await?;
+        session.sign_in(auth_model.clone()).await?;
+        session
+            .insert(SESSION_EPOCH_KEY, self.system_service.get_auth_epoch().await?)
+            .await?;
+
+        // a fresh id is issued on every sign in and saved right away, so it can be listed and revoked
+        session.cycle_id().await?;
+        session.save().await?;
+        if let Some(id) = session.id() {
+            self.session_service
+                .register(&id.to_string(), &user_model.login, client)
+                .await?;
+        }
+        self.sign_in_log(&user_model.login, client, None).await;
+
+        Ok(auth_model)
+    }
+
+    /// Swaps the session credentials for the user's, remembering who is looking.
+    async fn user_impersonate(&self, session: &Session, login: &str) -> Result<AuthModel> {
+        let auth = session.credentials().await?;
+        let access = session.get_access().await?;
+        if auth.impersonator.is_some() || auth.id.eq(login) {
+            Err(ApiError::from(SessionError::AccessForbidden))?
+        }
+
+        // only full administrators may look through the eyes of their peers
+        let user_model = self.user_service.find_by_login(login, &access).await?;
+        if !access.users_all && user_model.access_level <= access.users_level {
+            Err(ApiError::from(SessionError::AccessForbidden))?
+        }
+
+        // an account that can't sign in can't be entered through impersonation either
+        self.account_check(&user_model).await?;
+
+        let mut auth_model = self.user_credentials(&user_model.login).await?;
+        auth_model.impersonator = Some(auth.id);
+
+        session
+            .set_access(self.user_access(&user_model.login).await?)
+            .await?;
+        session.sign_in(auth_model.clone()).await?;
+
+        Ok(auth_model)
+    }
+
+    async fn impersonation_end(&self, session: &Session) -> Result<AuthModel> {
+        let Some(login) = session.credentials().await?.impersonator else {
+            return Err(ApiError::from(SessionError::AccessForbidden));
+        };
+
+        let auth_model = self.user_credentials(&login).await?;
+        session.set_access(self.user_access(&login).await?).await?;
+        session.sign_in(auth_model.clone()).await?;
+
+        Ok(auth_model)
+    }
+
+    async fn sign_in_failed(&self, login: Option<&str>, ip: &str) -> Result<()> {
+        let ip_failed_count = self.lockout_service.ip_failed(ip).await?;
+        let login_failed_count = match login {
+            Some(login) => self.lockout_service.login_failed(login).await?,
+            None => 0,
+        };
+
+        // slow down guessing before answering, the longer the more failures are in a row
+        tokio::time::sleep(
+            self.lockout_service
+                .failure_delay(ip_failed_count.max(login_failed_count)),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Refuses accounts that are blocked, out of their validity or locked after failed sign ins.
+    async fn account_check(&self, user_model: &UserModel) -> Result<()> {
+        if user_model.blocked {
+            Err(ApiError::from(SessionError::UserBlocked))?
+        }
+
+        if !self.user_service.is_account_valid(user_model) {
+            Err(ApiError::from(SessionError::AccountInactive))?
+        }
+
+        if self
+            .lockout_service
+            .is_login_locked(&user_model.login)
+            .await?
+        {
+            Err(ApiError::from(SessionError::AccountLocked))?
+        }
+
+        Ok(())
+    }
+
+    async fn block_expired_users(&self) -> Result<()> {
+        for login in self.user_service.block_expired().await? {
+            self.session_service.delete_by_login(&login, None).await?;
+            self.audit_service
+                .create(AuditEntryModel {
+                    actor: "system".to_string(),
+                    action: "user::expire".to_string(),
+                    target: login,
+                    before: None,
+                    after: None,
+                    ip: "".to_string(),
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+}
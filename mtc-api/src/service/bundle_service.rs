@@ -0,0 +1,339 @@
+use axum::async_trait;
+use tracing::error;
+
+use mtc_model::auth_model::is_permission_rule;
+use mtc_model::bundle_model::{
+    PermissionGrantModel, SchemaBundleDiffModel, SchemaBundleItemModel, SchemaBundleModel,
+    SCHEMA_BUNDLE_VERSION,
+};
+use mtc_model::permission_model::PermissionDtoModel;
+use mtc_model::schema_model::{SchemaCreateModel, SchemaModel, SchemaUpdateModel};
+
+use crate::error::api_error::ToApiError;
+use crate::error::Result;
+use crate::model::access_model::AudienceModel;
+use crate::repository::api_repository::ApiRepositoryTrait;
+use crate::repository::permissions_repository::PermissionsRepositoryTrait;
+use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::schema_repository::SchemaRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::service::storage_service::StorageTrait;
+use crate::state::AppState;
+
+static SCHEMA_ACTIONS: [&str; 5] = ["read", "write", "delete", "write_own", "delete_own"];
+
+// Bundles span schemas, permissions and roles, so they are served by the whole state
+#[async_trait]
+pub trait SchemaBundleTrait {
+    async fn bundle_export(&self, slugs: &[String]) -> Result<SchemaBundleModel>;
+    async fn bundle_diff(&self, bundle: &SchemaBundleModel) -> Result<SchemaBundleDiffModel>;
+    async fn bundle_import(
+        &self,
+        auth: &str,
+        bundle: SchemaBundleModel,
+    ) -> Result<SchemaBundleDiffModel>;
+}
+
+#[async_trait]
+impl SchemaBundleTrait for AppState {
+    async fn bundle_export(&self, slugs: &[String]) -> Result<SchemaBundleModel> {
+        let schemas = self
+            .schema_service
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|schema| slugs.is_empty() || slugs.contains(&schema.slug))
+            .map(|schema| SchemaBundleItemModel {
+                slug: schema.slug,
+                title: schema.title,
+                is_collection: schema.is_collection,
+                is_public: schema.is_public,
+                fields: schema.fields,
+                groups: schema.groups,
+            })
+            .collect::<Vec<SchemaBundleItemModel>>();
+
+        let permissions = self
+            .permissions_service
+            .get_custom()
+            .await?
+            .into_iter()
+            .map(|permission| permission.slug)
+            .collect::<Vec<String>>();
+
+        let mut bundle = SchemaBundleModel {
+            schemas,
+            permissions,
+            ..Default::default()
+        };
+
+        let bundle_permissions = bundle_permissions(&bundle);
+        for role in self.role_service.all().await?.list {
+            for permission in self.permissions_service.find_by_role(&role.slug).await?.list {
+                if bundle_permissions.contains(&permission) {
+                    bundle.grants.push(PermissionGrantModel {
+                        role: role.slug.clone(),
+                        permission,
+                    })
+                }
+            }
+        }
+
+        Ok(bundle)
+    }
+
+    async fn bundle_diff(&self, bundle: &SchemaBundleModel) -> Result<SchemaBundleDiffModel> {
+        if bundle.version > SCHEMA_BUNDLE_VERSION {
+            Err("Unsupported schema bundle version".to_bad_request_error())?
+        }
+
+        // a broken rule would be stored and granted as it is
+        if bundle
+            .permissions
+            .iter()
+            .chain(bundle.grants.iter().map(|grant| &grant.permission))
+            .any(|permission| !is_permission_rule(permission))
+        {
+            Err("incorrect permission rule".to_bad_request_error())?
+        }
+
+        let mut diff = SchemaBundleDiffModel::default();
+
+        for item in &bundle.schemas {
+            match self.schema_service.find_by_slug(&item.slug).await {
+                Ok(schema) => {
+                    if schema.is_system {
+                        Err("System schema can't be imported".to_conflict_error())?
+                    }
+                    if schema.title.ne(&item.title)
+                        || schema.is_collection.ne(&item.is_collection)
+                        || schema.is_public.ne(&item.is_public)
+                        || schema.fields.ne(&item.fields)
+                        || schema.groups.ne(&item.groups)
+                    {
+                        self.schema_service
+                            .can_update(&item.slug, &update_model(item))
+                            .await?;
+                        diff.update.push(item.slug.clone())
+                    }
+                }
+                _ => diff.create.push(item.slug.clone()),
+            }
+        }
+
+        for permission in &bundle.permissions {
+            if self
+                .permissions_service
+                .find_by_slug(permission)
+                .await
+                .is_err()
+            {
+                diff.permissions.push(permission.clone())
+            }
+        }
+
+        for grant in &bundle.grants {
+            match self.permissions_service.find_by_role(&grant.role).await {
+                Ok(granted) => {
+                    if !granted.list.contains(&grant.permission) {
+                        diff.grants.push(grant.clone())
+                    }
+                }
+                _ => {
+                    if !diff.missing_roles.contains(&grant.role) {
+                        diff.missing_roles.push(grant.role.clone())
+                    }
+                }
+            }
+        }
+
+        Ok(diff)
+    }
+
+    async fn bundle_import(
+        &self,
+        auth: &str,
+        bundle: SchemaBundleModel,
+    ) -> Result<SchemaBundleDiffModel> {
+        // everything the import may refuse is found by the diff, before anything is written
+        let diff = self.bundle_diff(&bundle).await?;
+
+        let mut steps = vec![];
+        if let Err(e) = bundle_apply(self, auth, &bundle, &diff, &mut steps).await {
+            bundle_rollback(self, auth, steps).await;
+            return Err(e);
+        }
+
+        for item in bundle.schemas.iter().filter(|item| {
+            !item.is_collection
+                && (diff.create.contains(&item.slug) || diff.update.contains(&item.slug))
+        }) {
+            let single = self
+                .api_service
+                .find_by_slug(
+                    "singles",
+                    &item.slug,
+                    &AudienceModel {
+                        groups: vec![],
+                        all: true,
+                    },
+                )
+                .await?;
+            self.storage_service.create_assets(&single.id).await?;
+        }
+
+        // schema permissions and grants change what the signed in users may do
+        if !diff.is_empty() {
+            self.system_service.increment_auth_epoch().await?;
+        }
+
+        Ok(diff)
+    }
+}
+
+/// A change already written by an import, undone when a later one fails.
+enum BundleStep {
+    Permission(String),
+    Created(String),
+    Updated(SchemaModel),
+    Granted(String, String),
+}
+
+async fn bundle_apply(
+    state: &AppState,
+    auth: &str,
+    bundle: &SchemaBundleModel,
+    diff: &SchemaBundleDiffModel,
+    steps: &mut Vec<BundleStep>,
+) -> Result<()> {
+    for permission in &diff.permissions {
+        state
+            .permissions_service
+            .create_custom(
+                auth,
+                PermissionDtoModel {
+                    slug: permission.clone(),
+                },
+            )
+            .await?;
+        steps.push(BundleStep::Permission(permission.clone()));
+    }
+
+    for item in &bundle.schemas {
+        if diff.create.contains(&item.slug) {
+            state
+                .schema_service
+                .create(
+                    auth,
+                    &item.slug,
+                    SchemaCreateModel {
+                        title: item.title.clone(),
+                        is_collection: item.is_collection,
+                        is_public: item.is_public,
+                        fields: item.fields.clone(),
+                        groups: item.groups.clone(),
+                    },
+                )
+                .await?;
+            steps.push(BundleStep::Created(item.slug.clone()));
+        } else if diff.update.contains(&item.slug) {
+            let before = state.schema_service.find_by_slug(&item.slug).await?;
+            state
+                .schema_service
+                .update(auth, &item.slug, update_model(item))
+                .await?;
+            steps.push(BundleStep::Updated(before));
+        }
+    }
+
+    // schemas created above already granted their permissions to the administrator
+    for grant in &bundle.grants {
+        let Ok(role) = state.role_service.find_by_slug(&grant.role).await else {
+            continue;
+        };
+        let granted = state.permissions_service.find_by_role(&grant.role).await?;
+        if granted.list.contains(&grant.permission) {
+            continue;
+        }
+        if let Ok(permission) = state
+            .permissions_service
+            .find_by_slug(&grant.permission)
+            .await
+        {
+            state
+                .role_service
+                .permission_assign(&role.id, &permission.id)
+                .await?;
+            steps.push(BundleStep::Granted(role.id, permission.id));
+        }
+    }
+
+    Ok(())
+}
+
+async fn bundle_rollback(state: &AppState, auth: &str, steps: Vec<BundleStep>) {
+    for step in steps.into_iter().rev() {
+        let result = match step {
+            BundleStep::Permission(slug) => {
+                state
+                    .permissions_service
+                    .delete_custom(PermissionDtoModel { slug })
+                    .await
+            }
+            BundleStep::Created(slug) => state.schema_service.delete(&slug).await,
+            BundleStep::Updated(schema) => state
+                .schema_service
+                .update(
+                    auth,
+                    &schema.slug,
+                    SchemaUpdateModel {
+                        title: schema.title.clone(),
+                        is_collection: Some(schema.is_collection),
+                        is_public: Some(schema.is_public),
+                        fields: schema.fields.clone(),
+                        groups: schema.groups.clone(),
+                        force: Some(true),
+                    },
+                )
+                .await
+                .map(|_| ()),
+            BundleStep::Granted(role_id, permission_id) => {
+                state
+                    .role_service
+                    .permission_unassign(&role_id, &permission_id)
+                    .await
+            }
+        };
+
+        if let Err(e) = result {
+            error!("Schema bundle rollback: {}", e.to_string())
+        }
+    }
+}
+
+fn update_model(item: &SchemaBundleItemModel) -> SchemaUpdateModel {
+    SchemaUpdateModel {
+        title: item.title.clone(),
+        is_collection: Some(item.is_collection),
+        is_public: Some(item.is_public),
+        fields: item.fields.clone(),
+        groups: item.groups.clone(),
+        force: None,
+    }
+}
+
+fn bundle_permissions(bundle: &SchemaBundleModel) -> Vec<String> {
+    let mut permissions = bundle.permissions.clone();
+
+    // public schemas keep only their own permissions
+    for schema in &bundle.schemas {
+        for action in SCHEMA_ACTIONS
+            .into_iter()
+            .filter(|action| !schema.is_public || action.ends_with("_own"))
+        {
+            permissions.push([&schema.slug, "::", action].concat())
+        }
+    }
+
+    permissions
+}
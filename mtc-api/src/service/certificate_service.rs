@@ -0,0 +1,28 @@
+use axum::async_trait;
+
+use mtc_model::certificate_model::ClientCertificateModel;
+
+use crate::error::Result;
+use crate::repository::certificate_repository::CertificateRepositoryTrait;
+
+crate::impl_service!(CertificateService);
+
+#[async_trait]
+pub trait CertificateTrait {
+    async fn certificate_login(&self, certificate: &ClientCertificateModel) -> Result<Option<String>>;
+}
+
+#[async_trait]
+impl CertificateTrait for CertificateService {
+    /// Explicit bindings win, the common name is taken as the login only when configured.
+    async fn certificate_login(&self, certificate: &ClientCertificateModel) -> Result<Option<String>> {
+        if let Some(login) = self.find_login(certificate).await? {
+            return Ok(Some(login));
+        }
+
+        Ok(match self.cfg.mtls_cn_login {
+            true => certificate.common_name.clone(),
+            false => None,
+        })
+    }
+}
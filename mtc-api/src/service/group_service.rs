@@ -1 +1,136 @@
-crate::impl_service!(GroupService);
\ No newline at end of file
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::async_trait;
+
+use mtc_model::group_model::{GroupNodeModel, GroupTreeModel};
+
+use crate::error::db_error::DbError;
+use crate::error::Result;
+use crate::repository::group_repository::GroupRepositoryTrait;
+
+crate::impl_service!(GroupService);
+
+#[async_trait]
+pub trait GroupTreeTrait {
+    async fn tree(&self) -> Result<Vec<GroupTreeModel>>;
+    async fn expand(&self, groups: Vec<String>, is_descendants: bool) -> Result<Vec<String>>;
+    async fn descendants(&self, groups: Vec<String>) -> Result<Vec<String>>;
+    async fn check_parent(&self, slug: &str, parent: Option<&str>) -> Result<()>;
+}
+
+#[async_trait]
+impl GroupTreeTrait for GroupService {
+    async fn tree(&self) -> Result<Vec<GroupTreeModel>> {
+        let nodes = self.nodes().await?;
+        let slugs = nodes
+            .iter()
+            .map(|node| node.slug.as_str())
+            .collect::<BTreeSet<&str>>();
+
+        let mut children = BTreeMap::<Option<&str>, Vec<&GroupNodeModel>>::new();
+        for node in &nodes {
+            // a parent that has gone missing leaves the group at the root
+            let parent = node
+                .parent
+                .as_deref()
+                .filter(|parent| slugs.contains(parent));
+            children.entry(parent).or_default().push(node);
+        }
+
+        Ok(tree_level(&children, None, &mut BTreeSet::new()))
+    }
+
+    /// Adds every ancestor of the given groups, and every descendant when asked to.
+    async fn expand(&self, groups: Vec<String>, is_descendants: bool) -> Result<Vec<String>> {
+        let nodes = self.nodes().await?;
+        let parents = nodes
+            .iter()
+            .filter_map(|node| Some((node.slug.as_str(), node.parent.as_deref()?)))
+            .collect::<BTreeMap<&str, &str>>();
+
+        let mut result = BTreeSet::<String>::new();
+        for group in &groups {
+            let mut current = Some(group.as_str());
+            while let Some(slug) = current {
+                if !result.insert(slug.to_string()) {
+                    break;
+                }
+                current = parents.get(slug).copied();
+            }
+        }
+
+        if is_descendants {
+            result.extend(self.descendants(groups).await?);
+        }
+
+        Ok(result.into_iter().collect())
+    }
+
+    /// The given groups together with every group below them.
+    async fn descendants(&self, groups: Vec<String>) -> Result<Vec<String>> {
+        let nodes = self.nodes().await?;
+
+        let mut result = BTreeSet::<String>::new();
+        let mut pending = groups;
+        while let Some(slug) = pending.pop() {
+            if !result.insert(slug.clone()) {
+                continue;
+            }
+            for node in &nodes {
+                if node.parent.as_ref().is_some_and(|parent| parent.eq(&slug)) {
+                    pending.push(node.slug.clone());
+                }
+            }
+        }
+
+        Ok(result.into_iter().collect())
+    }
+
+    async fn check_parent(&self, slug: &str, parent: Option<&str>) -> Result<()> {
+        let Some(parent) = parent else {
+            return Ok(());
+        };
+
+        let nodes = self.nodes().await?;
+        if !nodes.iter().any(|node| node.slug.eq(parent)) {
+            Err(DbError::EntryNotFound)?
+        }
+
+        // walking up from the new parent must never reach the group itself
+        let mut current = Some(parent);
+        let mut visited = BTreeSet::<&str>::new();
+        while let Some(value) = current {
+            if value.eq(slug) || !visited.insert(value) {
+                Err(DbError::GroupCycle)?
+            }
+            current = nodes
+                .iter()
+                .find(|node| node.slug.eq(value))
+                .and_then(|node| node.parent.as_deref());
+        }
+
+        Ok(())
+    }
+}
+
+fn tree_level<'a>(
+    children: &BTreeMap<Option<&'a str>, Vec<&'a GroupNodeModel>>,
+    parent: Option<&'a str>,
+    visited: &mut BTreeSet<&'a str>,
+) -> Vec<GroupTreeModel> {
+    let mut result = vec![];
+
+    for node in children.get(&parent).into_iter().flatten() {
+        if !visited.insert(node.slug.as_str()) {
+            continue;
+        }
+
+        result.push(GroupTreeModel {
+            slug: node.slug.clone(),
+            title: node.title.clone(),
+            children: tree_level(children, Some(node.slug.as_str()), visited),
+        });
+    }
+
+    result
+}
@@ -0,0 +1,397 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{TimeDelta, Utc};
+use jsonwebtoken::jwk::{JwkSet, KeyAlgorithm};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Url;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::{error, warn};
+
+use mtc_model::user_model::UserModel;
+
+use crate::error::api_error::{ApiError, ToApiError};
+use crate::error::session_error::SessionError;
+use crate::error::Result;
+use crate::model::access_model::AccessModel;
+use crate::model::oidc_model::{
+    OidcClaimsModel, OidcDiscoveryModel, OidcPendingModel, OidcTokenModel,
+};
+use crate::repository::group_repository::GroupRepositoryTrait;
+use crate::repository::identity_repository::IdentityRepositoryTrait;
+use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::system_repository::SystemRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::state::AppState;
+
+crate::impl_service!(IdentityService);
+
+/// How long the browser may stay at the identity provider before the callback is refused.
+pub const OIDC_PENDING_MINUTES: i64 = 10;
+
+const OIDC_TIMEOUT_SECONDS: u64 = 10;
+
+#[async_trait]
+pub trait OidcTrait {
+    fn is_oidc_enabled(&self) -> bool;
+    fn oidc_pending(&self, link: Option<String>) -> OidcPendingModel;
+    async fn oidc_discovery(&self) -> Result<OidcDiscoveryModel>;
+    async fn oidc_authorization_url(&self, pending: &OidcPendingModel) -> Result<String>;
+    async fn oidc_claims(&self, code: &str, pending: &OidcPendingModel) -> Result<OidcClaimsModel>;
+    fn oidc_mapped(
+        &self,
+        claims: &OidcClaimsModel,
+        claim: &str,
+        map: &[(String, String)],
+    ) -> BTreeSet<String>;
+}
+
+#[async_trait]
+impl OidcTrait for IdentityService {
+    fn is_oidc_enabled(&self) -> bool {
+        !self.cfg.oidc_issuer.is_empty()
+    }
+
+    fn oidc_pending(&self, link: Option<String>) -> OidcPendingModel {
+        OidcPendingModel {
+            state: random_string(),
+            nonce: random_string(),
+            verifier: random_string(),
+            link,
+            expires_at: (Utc::now() + TimeDelta::minutes(OIDC_PENDING_MINUTES)).timestamp(),
+        }
+    }
+
+    async fn oidc_discovery(&self) -> Result<OidcDiscoveryModel> {
+        if !self.is_oidc_enabled() {
+            Err("single sign-on is not configured".to_bad_request_error())?
+        }
+
+        let discovery = http_client()?
+            .get([&self.cfg.oidc_issuer, ".well-known/openid-configuration"].join("/"))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(provider_error)?
+            .json::<OidcDiscoveryModel>()
+            .await
+            .map_err(provider_error)?;
+
+        if discovery.issuer.trim_end_matches('/') != self.cfg.oidc_issuer {
+            Err(provider_error(["unexpected issuer ", &discovery.issuer].concat()))?
+        }
+
+        Ok(discovery)
+    }
+
+    async fn oidc_authorization_url(&self, pending: &OidcPendingModel) -> Result<String> {
+        let discovery = self.oidc_discovery().await?;
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(pending.verifier.as_bytes()));
+
+        Ok(Url::parse_with_params(
+            &discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", self.cfg.oidc_client_id.as_str()),
+                ("redirect_uri", self.cfg.oidc_redirect_url.as_str()),
+                ("scope", self.cfg.oidc_scopes.as_str()),
+                ("state", pending.state.as_str()),
+                ("nonce", pending.nonce.as_str()),
+                ("code_challenge", challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(provider_error)?
+        .to_string())
+    }
+
+    async fn oidc_claims(&self, code: &str, pending: &OidcPendingModel) -> Result<OidcClaimsModel> {
+        let discovery = self.oidc_discovery().await?;
+        let client = http_client()?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.cfg.oidc_redirect_url.as_str()),
+            ("client_id", self.cfg.oidc_client_id.as_str()),
+            ("code_verifier", pending.verifier.as_str()),
+        ];
+        // public clients rely on the code verifier alone
+        if !self.cfg.oidc_client_secret.is_empty() {
+            form.push(("client_secret", self.cfg.oidc_client_secret.as_str()));
+        }
+
+        let token = client
+            .post(&discovery.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(provider_error)?
+            .json::<OidcTokenModel>()
+            .await
+            .map_err(provider_error)?;
+
+        let jwks = client
+            .get(&discovery.jwks_uri)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(provider_error)?
+            .json::<JwkSet>()
+            .await
+            .map_err(provider_error)?;
+
+        let header = decode_header(&token.id_token).map_err(provider_error)?;
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            // without a key id only a single published key is unambiguous
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| provider_error("no signing key for the id token"))?;
+
+        // the algorithm comes from the key, the token header only has to agree with it
+        let algorithm = match &jwk.common.key_algorithm {
+            Some(algorithm) => signing_algorithm(algorithm),
+            None => [Algorithm::RS256, Algorithm::ES256]
+                .into_iter()
+                .find(|algorithm| algorithm.eq(&header.alg)),
+        }
+        .ok_or_else(|| provider_error("unsupported id token algorithm"))?;
+
+        let mut validation = Validation::new(algorithm);
+        validation.set_audience(&[&self.cfg.oidc_client_id]);
+        validation.set_issuer(&[&discovery.issuer]);
+
+        let claims = decode::<OidcClaimsModel>(
+            &token.id_token,
+            &DecodingKey::from_jwk(jwk).map_err(provider_error)?,
+            &validation,
+        )
+        .map_err(provider_error)?
+        .claims;
+
+        // a token issued for another attempt can't be replayed here
+        if claims.nonce.as_deref() != Some(pending.nonce.as_str()) {
+            Err(ApiError::from(SessionError::InvalidCredentials))?
+        }
+
+        Ok(claims)
+    }
+
+    /// Claims may point into nested objects with dots (`realm_access.roles`),
+    /// only the values listed in the map are taken.
+    fn oidc_mapped(
+        &self,
+        claims: &OidcClaimsModel,
+        claim: &str,
+        map: &[(String, String)],
+    ) -> BTreeSet<String> {
+        let mut path = claim.split('.');
+        let mut value = path.next().and_then(|key| claims.claims.get(key));
+        for key in path {
+            value = value.and_then(|value| value.get(key));
+        }
+
+        let values = match value {
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(|value| value.as_str())
+                .collect::<Vec<&str>>(),
+            Some(Value::String(value)) => vec![value.as_str()],
+            _ => vec![],
+        };
+
+        map.iter()
+            .filter(|(claim, _)| values.contains(&claim.as_str()))
+            .map(|(_, slug)| slug.clone())
+            .collect()
+    }
+}
+
+// Signing in with the identity provider touches users, roles and groups, so it is served by the whole state
+#[async_trait]
+pub trait IdentityTrait {
+    async fn identity_user(&self, claims: &OidcClaimsModel) -> Result<UserModel>;
+    async fn identity_sync(&self, user_model: &UserModel, claims: &OidcClaimsModel) -> Result<()>;
+}
+
+#[async_trait]
+impl IdentityTrait for AppState {
+    /// Linked identities win, otherwise the login claim either links an existing account
+    /// or creates a new one, as far as the configuration allows.
+    async fn identity_user(&self, claims: &OidcClaimsModel) -> Result<UserModel> {
+        let access = AccessModel {
+            users_level: -1,
+            users_all: true,
+            groups: None,
+        };
+
+        if let Some(login) = self
+            .identity_service
+            .find_login(&claims.iss, &claims.sub)
+            .await?
+        {
+            return self.user_service.find_by_login(&login, &access).await;
+        }
+
+        let login = claims
+            .claims
+            .get(&self.cfg.oidc_login_claim)
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_uppercase())
+            .filter(|value| (5..=15).contains(&value.chars().count()));
+        let Some(login) = login else {
+            return Err(ApiError::from(SessionError::IdentityNotLinked));
+        };
+
+        let user_model = match self.user_service.find_by_login(&login, &access).await {
+            Ok(user_model) if self.cfg.oidc_link_by_login => user_model,
+            Ok(_) => return Err(ApiError::from(SessionError::IdentityNotLinked)),
+            Err(_) if self.cfg.oidc_create_users => {
+                self.user_service.create_external(&login, &login).await?
+            }
+            Err(_) => return Err(ApiError::from(SessionError::IdentityNotLinked)),
+        };
+
+        self.identity_service
+            .create(&user_model.login, &claims.iss, &claims.sub)
+            .await?;
+
+        Ok(user_model)
+    }
+
+    /// The identity provider owns the roles and groups named in the maps,
+    /// anything assigned by hand outside of them is kept.
+    async fn identity_sync(&self, user_model: &UserModel, claims: &OidcClaimsModel) -> Result<()> {
+        let mut is_changed = false;
+
+        if !self.cfg.oidc_role_map.is_empty() {
+            let managed = self
+                .cfg
+                .oidc_role_map
+                .iter()
+                .map(|(_, slug)| slug.clone())
+                .collect::<BTreeSet<String>>();
+            let current = self
+                .role_service
+                .find_direct_by_user(&user_model.login)
+                .await?
+                .list
+                .into_iter()
+                .collect::<BTreeSet<String>>();
+            let roles = current
+                .difference(&managed)
+                .cloned()
+                .chain(self.identity_service.oidc_mapped(
+                    claims,
+                    &self.cfg.oidc_roles_claim,
+                    &self.cfg.oidc_role_map,
+                ))
+                .collect::<BTreeSet<String>>();
+
+            if roles != current {
+                self.user_service.roles_drop(&user_model.id).await?;
+                for role in roles {
+                    match self.role_service.find_by_slug(&role).await {
+                        Ok(value) => self.user_service.role_assign(&user_model.id, &value.id).await?,
+                        _ => warn!("can't find role -> {role}"),
+                    }
+                }
+                is_changed = true;
+            }
+        }
+
+        if !self.cfg.oidc_group_map.is_empty() {
+            let managed = self
+                .cfg
+                .oidc_group_map
+                .iter()
+                .map(|(_, slug)| slug.clone())
+                .collect::<BTreeSet<String>>();
+            let current = self
+                .group_service
+                .find_by_user(&user_model.login)
+                .await?
+                .list
+                .into_iter()
+                .collect::<BTreeSet<String>>();
+            let groups = current
+                .difference(&managed)
+                .cloned()
+                .chain(self.identity_service.oidc_mapped(
+                    claims,
+                    &self.cfg.oidc_groups_claim,
+                    &self.cfg.oidc_group_map,
+                ))
+                .collect::<BTreeSet<String>>();
+
+            if groups != current {
+                self.user_service.groups_drop(&user_model.id).await?;
+                for group in groups {
+                    match self.group_service.find_by_slug(&group).await {
+                        Ok(value) => self.user_service.group_assign(&user_model.id, &value.id).await?,
+                        _ => warn!("can't find group -> {group}"),
+                    }
+                }
+                is_changed = true;
+            }
+        }
+
+        if is_changed {
+            let access_level = self
+                .user_service
+                .get_roles_max_access_level(&user_model.login)
+                .await
+                .unwrap_or(999);
+            self.user_service
+                .update_access_level(&user_model.login, access_level)
+                .await?;
+            self.system_service.increment_auth_epoch().await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(OIDC_TIMEOUT_SECONDS))
+        .build()
+        .map_err(provider_error)
+}
+
+fn provider_error(e: impl Display) -> ApiError {
+    error!("Identity provider: {e}");
+    ApiError::from(SessionError::IdentityProvider)
+}
+
+/// Asymmetric algorithms only, a shared secret must never verify an id token.
+fn signing_algorithm(algorithm: &KeyAlgorithm) -> Option<Algorithm> {
+    match algorithm {
+        KeyAlgorithm::RS256 => Some(Algorithm::RS256),
+        KeyAlgorithm::RS384 => Some(Algorithm::RS384),
+        KeyAlgorithm::RS512 => Some(Algorithm::RS512),
+        KeyAlgorithm::PS256 => Some(Algorithm::PS256),
+        KeyAlgorithm::PS384 => Some(Algorithm::PS384),
+        KeyAlgorithm::PS512 => Some(Algorithm::PS512),
+        KeyAlgorithm::ES256 => Some(Algorithm::ES256),
+        KeyAlgorithm::ES384 => Some(Algorithm::ES384),
+        KeyAlgorithm::EdDSA => Some(Algorithm::EdDSA),
+        _ => None,
+    }
+}
+
+fn random_string() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    URL_SAFE_NO_PAD.encode(bytes)
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+crate::impl_service!(LockoutService);
+
+pub trait LockoutTrait {
+    fn failure_delay(&self, failed_count: i32) -> Duration;
+}
+
+impl LockoutTrait for LockoutService {
+    /// Every next failure in a row doubles the delay, up to the configured maximum.
+    fn failure_delay(&self, failed_count: i32) -> Duration {
+        let exponent = failed_count.clamp(1, 16) as u32 - 1;
+
+        Duration::from_millis(
+            self.cfg
+                .login_delay
+                .saturating_mul(2u64.pow(exponent))
+                .min(self.cfg.login_max_delay),
+        )
+    }
+}
@@ -7,6 +7,16 @@ pub mod api_service;
 pub mod storage_service;
 pub mod migration_service;
 pub mod system_service;
+pub mod bundle_service;
+pub mod auth_service;
+pub mod token_service;
+pub mod totp_service;
+pub mod lockout_service;
+pub mod session_service;
+pub mod audit_service;
+pub mod sign_in_log_service;
+pub mod certificate_service;
+pub mod identity_service;
 
 #[macro_export]
 macro_rules! impl_service {
@@ -29,3 +39,5 @@ macro_rules! impl_service {
         }
     }
 }
+pub mod person_service;
+pub mod provision_service;
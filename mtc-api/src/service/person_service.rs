@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+
+use axum::async_trait;
+use serde_json::{Map, Value};
+
+use mtc_model::field_model::{FieldModel, FieldTypeModel};
+use mtc_model::user_details_model::{
+    PersonFormatModel, PersonImportModel, PersonImportResultModel,
+};
+
+use crate::error::api_error::ToApiError;
+use crate::error::Result;
+use crate::model::access_model::AccessModel;
+use crate::repository::schema_repository::SchemaRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::schema_service::FieldMigrationTrait;
+use crate::state::AppState;
+
+/// The system schema whose fields describe the person details kept in `users.fields`.
+pub const PROFILE_SCHEMA: &str = "users";
+
+// Person details are users data shaped by a schema, so they are served by the whole state
+#[async_trait]
+pub trait PersonTrait {
+    async fn profile_fields(&self) -> Result<Vec<FieldModel>>;
+    async fn profile_validate(&self, fields: &Value) -> Result<Value>;
+    fn profile_normalize(
+        &self,
+        profile: &[FieldModel],
+        values: &Map<String, Value>,
+    ) -> core::result::Result<Value, Vec<String>>;
+    async fn person_import(
+        &self,
+        auth: &str,
+        model: &PersonImportModel,
+        access: &AccessModel,
+        is_dry_run: bool,
+    ) -> Result<PersonImportResultModel>;
+    async fn person_export(
+        &self,
+        format: &PersonFormatModel,
+        access: &AccessModel,
+    ) -> Result<String>;
+}
+
+#[async_trait]
+impl PersonTrait for AppState {
+    async fn profile_fields(&self) -> Result<Vec<FieldModel>> {
+        Ok(self
+            .schema_service
+            .get_fields(PROFILE_SCHEMA)
+            .await?
+            .fields
+            .unwrap_or_default())
+    }
+
+    async fn profile_validate(&self, fields: &Value) -> Result<Value> {
+        let Value::Object(values) = fields else {
+            return Err("person details must be an object".to_bad_request_error());
+        };
+
+        match self.profile_normalize(&self.profile_fields().await?, values) {
+            Ok(value) => Ok(value),
+            Err(errors) => Err(["invalid person details: ", &errors.join(", ")]
+                .concat()
+                .as_str()
+                .to_bad_request_error()),
+        }
+    }
+
+    async fn person_import(
+        &self,
+        auth: &str,
+        model: &PersonImportModel,
+        access: &AccessModel,
+        is_dry_run: bool,
+    ) -> Result<PersonImportResultModel> {
+        let profile = self.profile_fields().await?;
+        let persons = match model.format {
+            PersonFormatModel::Json => parse_json(&model.data)?,
+            PersonFormatModel::Csv => parse_table(&model.data, ',', &profile),
+            PersonFormatModel::Tsv => parse_table(&model.data, '\t', &profile),
+        };
+
+        let mut result = PersonImportResultModel::default();
+        for (login, values) in persons {
+            let Ok(user_model) = self.user_service.find_by_login(&login, access).await else {
+                result.unknown.push(login);
+                continue;
+            };
+
+            let values = match self.profile_normalize(&profile, &values) {
+                Ok(Value::Object(values)) => values,
+                Ok(_) => continue,
+                Err(errors) => {
+                    result.invalid.push([&login, ": ", &errors.join(", ")].concat());
+                    continue;
+                }
+            };
+
+            // imported values replace only the details they carry, the rest are kept
+            let mut fields = match user_model.fields {
+                Some(Value::Object(fields)) => fields,
+                _ => Map::new(),
+            };
+            fields.extend(values);
+
+            if !is_dry_run {
+                self.user_service
+                    .update_fields(auth, &user_model.login, &Value::Object(fields))
+                    .await?;
+            }
+            result.updated.push(user_model.login);
+        }
+
+        Ok(result)
+    }
+
+    async fn person_export(
+        &self,
+        format: &PersonFormatModel,
+        access: &AccessModel,
+    ) -> Result<String> {
+        let persons = self.user_service.find_persons(access).await?;
+
+        if let PersonFormatModel::Json = format {
+            let persons = persons
+                .into_iter()
+                .map(|person| (person.login, person.fields.unwrap_or(Value::Object(Map::new()))))
+                .collect::<BTreeMap<String, Value>>();
+
+            return serde_json::to_string_pretty(&persons)
+                .map_err(|e| e.to_string().as_str().to_internal_error());
+        }
+
+        let separator = match format {
+            PersonFormatModel::Csv => ',',
+            _ => '\t',
+        };
+        let profile = self.profile_fields().await?;
+
+        let mut data = std::iter::once("login")
+            .chain(profile.iter().map(|field| field.slug.as_str()))
+            .map(|value| table_field(value, separator))
+            .collect::<Vec<String>>()
+            .join(&separator.to_string());
+        data.push('\n');
+
+        for person in persons {
+            let fields = person.fields.unwrap_or_default();
+            let row = std::iter::once(person.login.clone())
+                .chain(profile.iter().map(|field| match fields.get(&field.slug) {
+                    Some(Value::String(value)) => value.clone(),
+                    Some(Value::Null) | None => String::new(),
+                    Some(value) => value.to_string(),
+                }))
+                .map(|value| table_field(&value, separator))
+                .collect::<Vec<String>>()
+                .join(&separator.to_string());
+            data.push_str(&row);
+            data.push('\n');
+        }
+
+        Ok(data)
+    }
+
+    /// Keeps the profile fields only, each converted to its field type.
+    /// Returns the slugs of the values that don't fit their type.
+    fn profile_normalize(
+        &self,
+        profile: &[FieldModel],
+        values: &Map<String, Value>,
+    ) -> core::result::Result<Value, Vec<String>> {
+        let mut result = Map::new();
+        let mut errors = vec![];
+
+        for field in profile {
+            let value = match values.get(&field.slug) {
+                Some(Value::String(value)) => value.clone(),
+                Some(Value::Number(value)) => value.to_string(),
+                Some(Value::Null) | None => continue,
+                Some(_) => {
+                    errors.push(field.slug.clone());
+                    continue;
+                }
+            };
+
+            let from = match field.field_type {
+                FieldTypeModel::Html => FieldTypeModel::Html,
+                _ => FieldTypeModel::Text,
+            };
+            match self
+                .schema_service
+                .convert_value(&value, &from, &field.field_type)
+            {
+                Some(value) => {
+                    result.insert(field.slug.clone(), Value::String(value));
+                }
+                None => errors.push(field.slug.clone()),
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(Value::Object(result)),
+            false => Err(errors),
+        }
+    }
+}
+
+fn parse_json(data: &str) -> Result<Vec<(String, Map<String, Value>)>> {
+    let persons = serde_json::from_str::<BTreeMap<String, Value>>(data)
+        .map_err(|e| e.to_string().as_str().to_bad_request_error())?;
+
+    Ok(persons
+        .into_iter()
+        .filter_map(|(login, fields)| match fields {
+            Value::Object(fields) => Some((login.trim().to_uppercase(), fields)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Rows with a `login` column are read by their header, headerless rows (as copied from a
+/// roster spreadsheet) hold the profile fields in schema order followed by the login.
+fn parse_table(
+    data: &str,
+    separator: char,
+    profile: &[FieldModel],
+) -> Vec<(String, Map<String, Value>)> {
+    let mut rows = parse_rows(data, separator).into_iter().peekable();
+
+    let is_header = rows.peek().is_some_and(|row| {
+        row.iter()
+            .any(|value| value.trim().eq_ignore_ascii_case("login"))
+    });
+    let header = match is_header {
+        true => rows
+            .next()
+            .unwrap_or_default()
+            .iter()
+            .map(|value| value.trim().to_lowercase())
+            .collect::<Vec<String>>(),
+        false => profile
+            .iter()
+            .map(|field| field.slug.clone())
+            .chain(std::iter::once("login".to_string()))
+            .collect::<Vec<String>>(),
+    };
+
+    rows.filter_map(|row| {
+        let mut login = String::new();
+        let mut fields = Map::new();
+
+        for (key, value) in header.iter().zip(row) {
+            match key.as_str() {
+                "login" => login = value.trim().to_uppercase(),
+                _ => {
+                    fields.insert(key.clone(), Value::String(value.trim().to_string()));
+                }
+            }
+        }
+
+        match login.is_empty() {
+            true => None,
+            false => Some((login, fields)),
+        }
+    })
+    .collect()
+}
+
+fn parse_rows(data: &str, separator: char) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut value = String::new();
+    let mut is_quoted = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if is_quoted && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            }
+            '"' if is_quoted => is_quoted = false,
+            '"' if value.is_empty() => is_quoted = true,
+            '\r' if !is_quoted => (),
+            '\n' if !is_quoted => {
+                row.push(std::mem::take(&mut value));
+                if row.iter().any(|value| !value.trim().is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            char if char == separator && !is_quoted => row.push(std::mem::take(&mut value)),
+            char => value.push(char),
+        }
+    }
+
+    row.push(value);
+    if row.iter().any(|value| !value.trim().is_empty()) {
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn table_field(value: &str, separator: char) -> String {
+    match value.contains(separator) || value.contains('"') || value.contains('\n') {
+        true => ["\"", &value.replace('"', "\"\""), "\""].concat(),
+        false => value.to_string(),
+    }
+}
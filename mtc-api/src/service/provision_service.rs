@@ -0,0 +1,286 @@
+use std::collections::BTreeSet;
+
+use axum::async_trait;
+use serde_json::{json, Map, Value};
+
+use mtc_model::field_model::FieldModel;
+use mtc_model::group_model::GroupModel;
+use mtc_model::user_model::{UserCredentialModel, UserProvisionModel, UserValidityModel};
+
+use crate::error::api_error::ToApiError;
+use crate::error::Result;
+use crate::repository::group_repository::GroupRepositoryTrait;
+use crate::repository::role_repository::RoleRepositoryTrait;
+use crate::repository::user_repository::UserRepositoryTrait;
+use crate::service::person_service::PersonTrait;
+use crate::service::schema_service::escape_html;
+use crate::service::user_service::PasswordTrait;
+use crate::state::AppState;
+
+const PROVISION_LOGIN_DIGITS: usize = 3;
+
+// Provisioning touches users, groups, roles and the profile schema, so it is served by the whole state
+#[async_trait]
+pub trait ProvisionTrait {
+    async fn user_provision(
+        &self,
+        auth: &str,
+        model: &UserProvisionModel,
+    ) -> Result<Vec<UserCredentialModel>>;
+    async fn provision_logins(&self, prefix: &str, count: usize) -> Result<Vec<String>>;
+    fn credentials_csv(&self, credentials: &[UserCredentialModel], profile: &[FieldModel]) -> String;
+    fn credentials_html(
+        &self,
+        group: &GroupModel,
+        credentials: &[UserCredentialModel],
+        profile: &[FieldModel],
+    ) -> String;
+}
+
+#[async_trait]
+impl ProvisionTrait for AppState {
+    async fn user_provision(
+        &self,
+        auth: &str,
+        model: &UserProvisionModel,
+    ) -> Result<Vec<UserCredentialModel>> {
+        let group_model = self.group_service.find_by_slug(&model.group).await?;
+
+        let mut role_ids = vec![];
+        let mut access_level = None::<i32>;
+        for role in &model.roles {
+            let role_model = self.role_service.find_by_slug(role).await?;
+            access_level = access_level.max(Some(role_model.user_access_level));
+            role_ids.push(role_model.id);
+        }
+
+        let persons = match (&model.persons, model.count) {
+            (Some(persons), _) => persons.clone(),
+            (None, Some(count)) => vec![Value::Object(Map::new()); count],
+            _ => Err("either persons or count is required".to_bad_request_error())?,
+        };
+
+        let profile = self.profile_fields().await?;
+        let mut fields = vec![];
+        let mut given_logins = vec![];
+        for (index, person) in persons.iter().enumerate() {
+            let Value::Object(values) = person else {
+                return Err(["person ", &(index + 1).to_string(), " must be an object"]
+                    .concat()
+                    .as_str()
+                    .to_bad_request_error());
+            };
+
+            let login = values
+                .get("login")
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim().to_uppercase())
+                .filter(|value| !value.is_empty());
+            if let Some(login) = &login {
+                if !(5..=15).contains(&login.chars().count()) {
+                    Err(["incorrect login: ", login].concat().as_str().to_bad_request_error())?
+                }
+            }
+            given_logins.push(login);
+
+            match self.profile_normalize(&profile, values) {
+                Ok(value) => fields.push(value),
+                Err(errors) => Err([
+                    "person ",
+                    &(index + 1).to_string(),
+                    ": ",
+                    &errors.join(", "),
+                ]
+                .concat()
+                .as_str()
+                .to_bad_request_error())?,
+            }
+        }
+
+        let taken = given_logins.iter().flatten().cloned().collect::<Vec<String>>();
+        if taken.iter().collect::<BTreeSet<&String>>().len() != taken.len() {
+            Err("duplicate logins in the list".to_conflict_error())?
+        }
+        let existing = self.user_service.find_logins(&taken).await?;
+        if !existing.is_empty() {
+            Err(["logins already exist: ", &existing.join(", ")]
+                .concat()
+                .as_str()
+                .to_conflict_error())?
+        }
+
+        let prefix = model
+            .prefix
+            .clone()
+            .unwrap_or(group_model.slug.clone())
+            .chars()
+            .filter(|char| char.is_ascii_alphanumeric())
+            .take(15 - PROVISION_LOGIN_DIGITS)
+            .collect::<String>()
+            .to_uppercase();
+        let mut generated = self
+            .provision_logins(&prefix, given_logins.iter().filter(|login| login.is_none()).count())
+            .await?
+            .into_iter()
+            .filter(|login| !taken.contains(login));
+
+        let mut credentials = vec![];
+        let mut users = vec![];
+        for (login, fields) in given_logins.into_iter().zip(fields) {
+            let Some(login) = login.or_else(|| generated.next()) else {
+                return Err("can't generate enough logins".to_conflict_error());
+            };
+            let password = self.user_service.generate_password(&login);
+
+            let mut user = json!({
+                "login": login,
+                "password": self.user_service.hash_password(&password)?,
+            });
+            let fields = match fields {
+                Value::Object(values) if !values.is_empty() => {
+                    user["fields"] = Value::Object(values.clone());
+                    Some(Value::Object(values))
+                }
+                _ => None,
+            };
+            users.push(user);
+
+            credentials.push(UserCredentialModel {
+                login,
+                password,
+                fields,
+            });
+        }
+
+        self.user_service
+            .provision(
+                auth,
+                users,
+                &group_model.id,
+                role_ids,
+                access_level.unwrap_or(999),
+                &UserValidityModel {
+                    valid_from: model.valid_from.clone(),
+                    valid_until: model.valid_until.clone(),
+                },
+            )
+            .await?;
+
+        Ok(credentials)
+    }
+
+    /// Returns the first free `PREFIX001`-style logins, generous enough to skip explicitly given ones.
+    async fn provision_logins(&self, prefix: &str, count: usize) -> Result<Vec<String>> {
+        if prefix.chars().count() < 2 {
+            Err("login prefix must be 2 characters at least".to_bad_request_error())?
+        }
+
+        let existing = self
+            .user_service
+            .find_logins_by_prefix(prefix)
+            .await?
+            .into_iter()
+            .collect::<BTreeSet<String>>();
+
+        Ok((1..10usize.pow(PROVISION_LOGIN_DIGITS as u32))
+            .map(|number| format!("{prefix}{number:0width$}", width = PROVISION_LOGIN_DIGITS))
+            .filter(|login| !existing.contains(login))
+            .take(count * 2)
+            .collect())
+    }
+
+    fn credentials_csv(&self, credentials: &[UserCredentialModel], profile: &[FieldModel]) -> String {
+        let mut csv = ["login", "password"]
+            .into_iter()
+            .chain(profile.iter().map(|field| field.slug.as_str()))
+            .map(csv_field)
+            .collect::<Vec<String>>()
+            .join(",");
+        csv.push('\n');
+
+        for item in credentials {
+            let row = [item.login.as_str(), item.password.as_str()]
+                .into_iter()
+                .map(csv_field)
+                .chain(
+                    profile
+                        .iter()
+                        .map(|field| csv_field(&credential_field(item, &field.slug))),
+                )
+                .collect::<Vec<String>>()
+                .join(",");
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    fn credentials_html(
+        &self,
+        group: &GroupModel,
+        credentials: &[UserCredentialModel],
+        profile: &[FieldModel],
+    ) -> String {
+        let cards = credentials
+            .iter()
+            .map(|item| {
+                let details = profile
+                    .iter()
+                    .map(|field| credential_field(item, &field.slug))
+                    .filter(|value| !value.is_empty())
+                    .map(|value| escape_html(&value))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+
+                [
+                    r#"<div class="card"><div class="group">"#,
+                    &escape_html(&group.title),
+                    r#"</div><div class="person">"#,
+                    &details,
+                    r#"</div><div class="line"><span>Login</span><code>"#,
+                    &escape_html(&item.login),
+                    r#"</code></div><div class="line"><span>Password</span><code>"#,
+                    &escape_html(&item.password),
+                    r#"</code></div><div class="note">Change the password at first sign-in</div></div>"#,
+                ]
+                .concat()
+            })
+            .collect::<String>();
+
+        [
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>"#,
+            &escape_html(&group.title),
+            r#"</title><style>
+body { font-family: sans-serif; margin: 1cm; }
+.sheet { display: grid; grid-template-columns: repeat(2, 1fr); gap: 0.5cm; }
+.card { border: 1px dashed #555; padding: 0.4cm; break-inside: avoid; page-break-inside: avoid; }
+.group { font-size: 0.8em; color: #555; }
+.person { font-weight: bold; min-height: 1.2em; margin: 0.2cm 0; }
+.line { display: flex; justify-content: space-between; }
+.line code { font-size: 1.2em; }
+.note { font-size: 0.7em; color: #555; margin-top: 0.2cm; }
+</style></head><body><div class="sheet">"#,
+            &cards,
+            r#"</div></body></html>"#,
+        ]
+        .concat()
+    }
+}
+
+fn credential_field(credential: &UserCredentialModel, slug: &str) -> String {
+    credential
+        .fields
+        .as_ref()
+        .and_then(|fields| fields.get(slug))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => ["\"", &value.replace('"', "\"\""), "\""].concat(),
+        false => value.to_string(),
+    }
+}
@@ -1 +1,270 @@
-crate::impl_service!(SchemaService);
\ No newline at end of file
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use serde_json::{Map, Value};
+
+use mtc_model::field_model::{FieldModel, FieldTypeModel};
+use mtc_model::schema_model::FieldChangeModel;
+
+crate::impl_service!(SchemaService);
+
+pub trait FieldMigrationTrait {
+    fn fields_diff(&self, old: &[FieldModel], new: &[FieldModel]) -> Vec<FieldChangeModel>;
+    fn migrate_fields(
+        &self,
+        old: &[FieldModel],
+        new: &[FieldModel],
+        fields: &Value,
+    ) -> (Value, Vec<String>);
+    fn convert_value(&self, value: &str, from: &FieldTypeModel, to: &FieldTypeModel)
+        -> Option<String>;
+}
+
+impl FieldMigrationTrait for SchemaService {
+    fn fields_diff(&self, old: &[FieldModel], new: &[FieldModel]) -> Vec<FieldChangeModel> {
+        let new_fields = new
+            .iter()
+            .map(|field| (field.key(), field))
+            .collect::<BTreeMap<&str, &FieldModel>>();
+
+        let mut changes = vec![];
+        for old_field in old {
+            match new_fields.get(old_field.key()) {
+                Some(new_field) => {
+                    if old_field.slug.ne(&new_field.slug) {
+                        changes.push(FieldChangeModel::Rename {
+                            from: old_field.slug.clone(),
+                            to: new_field.slug.clone(),
+                        })
+                    }
+                    if old_field.field_type.ne(&new_field.field_type) {
+                        changes.push(FieldChangeModel::Retype {
+                            slug: new_field.slug.clone(),
+                            from: old_field.field_type.clone(),
+                            to: new_field.field_type.clone(),
+                        })
+                    }
+                }
+                None => changes.push(FieldChangeModel::Remove {
+                    slug: old_field.slug.clone(),
+                }),
+            }
+        }
+
+        changes
+    }
+
+    fn migrate_fields(
+        &self,
+        old: &[FieldModel],
+        new: &[FieldModel],
+        fields: &Value,
+    ) -> (Value, Vec<String>) {
+        migrate_fields(old, new, fields)
+    }
+
+    fn convert_value(
+        &self,
+        value: &str,
+        from: &FieldTypeModel,
+        to: &FieldTypeModel,
+    ) -> Option<String> {
+        convert_value(value, from, to)
+    }
+}
+
+fn migrate_fields(old: &[FieldModel], new: &[FieldModel], fields: &Value) -> (Value, Vec<String>) {
+    let mut errors = vec![];
+    let Value::Object(values) = fields else {
+        return (fields.clone(), errors);
+    };
+
+    let old_fields = old
+        .iter()
+        .map(|field| (field.key(), field))
+        .collect::<BTreeMap<&str, &FieldModel>>();
+
+    // values that don't belong to any known field are kept as is
+    let mut result = values
+        .iter()
+        .filter(|(slug, _)| !old.iter().any(|field| field.slug.eq(*slug)))
+        .map(|(slug, value)| (slug.clone(), value.clone()))
+        .collect::<Map<String, Value>>();
+
+    for new_field in new {
+        let Some(old_field) = old_fields.get(new_field.key()) else {
+            continue;
+        };
+        let Some(value) = values.get(&old_field.slug) else {
+            continue;
+        };
+
+        match value
+            .as_str()
+            .and_then(|value| convert_value(value, &old_field.field_type, &new_field.field_type))
+        {
+            Some(value) => {
+                result.insert(new_field.slug.clone(), Value::String(value));
+            }
+            // nothing is lost, the value stays unconverted under the new name
+            None => {
+                result.insert(new_field.slug.clone(), value.clone());
+                errors.push(new_field.slug.clone());
+            }
+        }
+    }
+
+    (Value::Object(result), errors)
+}
+
+fn convert_value(value: &str, from: &FieldTypeModel, to: &FieldTypeModel) -> Option<String> {
+    if from.eq(to) {
+        return Some(value.to_string());
+    }
+
+    let value = match from {
+        FieldTypeModel::Html => strip_html(value),
+        _ => value.to_string(),
+    };
+
+    if value.trim().is_empty() {
+        return Some("".to_string());
+    }
+
+    match to {
+        FieldTypeModel::Str => Some(value.lines().collect::<Vec<&str>>().join(" ")),
+        FieldTypeModel::Text => Some(value),
+        FieldTypeModel::Html => Some(
+            value
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| ["<p>", &escape_html(line), "</p>"].concat())
+                .collect::<String>(),
+        ),
+        FieldTypeModel::Decimal => {
+            let value = value.trim().replace(',', ".");
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .map(|_| value)
+        }
+        FieldTypeModel::DateTime => DateTime::parse_from_rfc3339(value.trim())
+            .ok()
+            .map(|value| value.to_rfc3339()),
+    }
+}
+
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn strip_html(value: &str) -> String {
+    let value = value
+        .replace("</p>", "\n")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n");
+
+    let mut result = String::new();
+    let mut is_tag = false;
+    for char in value.chars() {
+        match char {
+            '<' => is_tag = true,
+            '>' => is_tag = false,
+            _ if !is_tag => result.push(char),
+            _ => (),
+        }
+    }
+
+    result
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(id: &str, slug: &str, field_type: FieldTypeModel) -> FieldModel {
+        FieldModel {
+            id: id.to_string(),
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            field_type,
+        }
+    }
+
+    #[test]
+    fn convert_value_between_types() {
+        use FieldTypeModel::*;
+
+        assert_eq!(convert_value("one\ntwo", &Text, &Str).as_deref(), Some("one two"));
+        assert_eq!(
+            convert_value("a < b\n\nc", &Text, &Html).as_deref(),
+            Some("<p>a &lt; b</p><p>c</p>")
+        );
+        assert_eq!(
+            convert_value("<p>a &amp; b</p><p>c</p>", &Html, &Text).as_deref(),
+            Some("a & b\nc")
+        );
+        assert_eq!(convert_value(" 1,5 ", &Str, &Decimal).as_deref(), Some("1.5"));
+        assert_eq!(convert_value("inf", &Str, &Decimal), None);
+        assert_eq!(convert_value("many", &Str, &Decimal), None);
+        assert_eq!(
+            convert_value("2024-05-01T10:00:00+02:00", &Str, &DateTime).as_deref(),
+            Some("2024-05-01T10:00:00+02:00")
+        );
+        assert_eq!(convert_value("yesterday", &Str, &DateTime), None);
+        assert_eq!(convert_value("  ", &Str, &Decimal).as_deref(), Some(""));
+        assert_eq!(convert_value("as is", &Html, &Html).as_deref(), Some("as is"));
+    }
+
+    #[test]
+    fn migrate_fields_renames_and_converts() {
+        let old = [
+            field("title", "title", FieldTypeModel::Str),
+            field("price", "price", FieldTypeModel::Str),
+            field("", "dropped", FieldTypeModel::Str),
+        ];
+        let new = [
+            field("title", "heading", FieldTypeModel::Str),
+            field("price", "price", FieldTypeModel::Decimal),
+        ];
+        let fields = json!({
+            "title": "Hello",
+            "price": "12,50",
+            "dropped": "gone",
+            "unknown": "kept"
+        });
+
+        let (migrated, errors) = migrate_fields(&old, &new, &fields);
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            migrated,
+            json!({ "heading": "Hello", "price": "12.50", "unknown": "kept" })
+        );
+    }
+
+    #[test]
+    fn migrate_fields_keeps_unconverted_values() {
+        let old = [field("price", "price", FieldTypeModel::Str)];
+        let new = [field("price", "cost", FieldTypeModel::Decimal)];
+
+        let (migrated, errors) = migrate_fields(&old, &new, &json!({ "price": "free" }));
+
+        assert_eq!(errors, vec!["cost".to_string()]);
+        assert_eq!(migrated, json!({ "cost": "free" }));
+    }
+}
@@ -0,0 +1,4 @@
+crate::impl_service!(SessionService);
+
+/// Table of the session store, the registry keeps the same record ids.
+pub const SESSION_STORE_TABLE: &str = "sessions";
@@ -0,0 +1,41 @@
+use axum::async_trait;
+use tracing::error;
+
+use crate::error::session_error::SessionError;
+use crate::model::request_model::ClientModel;
+use crate::model::sign_in_log_model::SignInLogEntryModel;
+use crate::repository::sign_in_log_repository::SignInLogRepositoryTrait;
+use crate::state::AppState;
+
+crate::impl_service!(SignInLogService);
+
+/// How many of the latest attempts are shown for a single account.
+pub const SIGN_IN_LOG_RECENT: usize = 20;
+
+#[async_trait]
+pub trait SignInLogTrait {
+    async fn sign_in_log(&self, login: &str, client: &ClientModel, failure: Option<&SessionError>);
+}
+
+#[async_trait]
+impl SignInLogTrait for AppState {
+    async fn sign_in_log(&self, login: &str, client: &ClientModel, failure: Option<&SessionError>) {
+        // the password was right, the attempt goes on with the second step
+        if let Some(SessionError::TwoFactorRequired | SessionError::TwoFactorEnrollment) = failure {
+            return;
+        }
+
+        let entry = SignInLogEntryModel {
+            login: login.to_string(),
+            success: failure.is_none(),
+            reason: failure.map(|value| value.to_string()),
+            ip: client.ip.clone(),
+            user_agent: client.user_agent.clone(),
+        };
+
+        // the answer to the client must not depend on the history being written
+        if let Err(e) = self.sign_in_log_service.create(entry).await {
+            error!("Sign-in log: {}", e.to_string())
+        }
+    }
+}
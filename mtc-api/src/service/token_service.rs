@@ -0,0 +1,23 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+crate::impl_service!(TokenService);
+
+pub trait TokenTrait {
+    fn generate_token(&self) -> String;
+}
+
+impl TokenTrait for TokenService {
+    fn generate_token(&self) -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        [
+            "mtc_",
+            &bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>(),
+        ]
+        .concat()
+    }
+}
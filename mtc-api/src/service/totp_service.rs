@@ -0,0 +1,119 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use mtc_model::totp_model::TotpSetupModel;
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::error::Result;
+
+crate::impl_service!(TotpService);
+
+pub const TOTP_ISSUER: &str = "MTC";
+pub const TOTP_RECOVERY_CODES: usize = 10;
+pub const TOTP_PENDING_MINUTES: i64 = 5;
+pub const TOTP_MAX_ATTEMPTS: u8 = 5;
+pub const TOTP_STEP_SECONDS: u64 = 30;
+/// Steps before and after the current one a code is still accepted for.
+pub const TOTP_SKEW: u64 = 1;
+
+pub trait TotpTrait {
+    fn generate_secret(&self) -> String;
+    fn setup_model(&self, login: &str, secret: &str) -> Result<TotpSetupModel>;
+    fn code_step(&self, login: &str, secret: &str, code: &str) -> Option<u64>;
+    fn generate_recovery_codes(&self) -> Vec<String>;
+}
+
+impl TotpTrait for TotpService {
+    fn generate_secret(&self) -> String {
+        Secret::generate_secret().to_encoded().to_string()
+    }
+
+    fn setup_model(&self, login: &str, secret: &str) -> Result<TotpSetupModel> {
+        let totp = totp(login, secret)?;
+
+        Ok(TotpSetupModel {
+            secret: secret.to_string(),
+            url: totp.get_url(),
+            qr: totp
+                .get_qr_base64()
+                .map_err(|_| ApiError::from(SessionError::TwoFactorSecret))?,
+        })
+    }
+
+    /// The time step the code belongs to, the caller makes sure a step is used only once.
+    fn code_step(&self, login: &str, secret: &str, code: &str) -> Option<u64> {
+        let totp = totp(login, secret).ok()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let current = now / TOTP_STEP_SECONDS;
+
+        (current.saturating_sub(TOTP_SKEW)..=current + TOTP_SKEW)
+            .find(|step| totp.check(code.trim(), step * TOTP_STEP_SECONDS))
+    }
+
+    fn generate_recovery_codes(&self) -> Vec<String> {
+        (0..TOTP_RECOVERY_CODES).map(|_| recovery_code()).collect()
+    }
+}
+
+/// Ten lowercase hex digits split in half, `1a2b3-c4d5e`.
+fn recovery_code() -> String {
+    let mut bytes = [0u8; 5];
+    OsRng.fill_bytes(&mut bytes);
+
+    let code = bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    [&code[..5], "-", &code[5..]].concat()
+}
+
+fn totp(login: &str, secret: &str) -> Result<TOTP> {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|_| ApiError::from(SessionError::TwoFactorSecret))?;
+
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP_SECONDS,
+        secret,
+        Some(TOTP_ISSUER.to_string()),
+        login.to_string(),
+    )
+    .map_err(|_| ApiError::from(SessionError::TwoFactorSecret))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn recovery_code_format() {
+        for _ in 0..100 {
+            let code = recovery_code();
+
+            assert_eq!(code.len(), 11);
+            assert_eq!(&code[5..6], "-");
+            assert!(code
+                .chars()
+                .filter(|char| *char != '-')
+                .all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char)));
+        }
+    }
+
+    #[test]
+    fn recovery_codes_differ() {
+        let codes = (0..TOTP_RECOVERY_CODES)
+            .map(|_| recovery_code())
+            .collect::<HashSet<String>>();
+
+        assert_eq!(codes.len(), TOTP_RECOVERY_CODES);
+    }
+}
@@ -1 +1,165 @@
-crate::impl_service!(UserService);
\ No newline at end of file
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::SaltString;
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use chrono::{TimeDelta, Utc};
+
+use mtc_model::user_model::UserModel;
+
+use crate::error::api_error::ApiError;
+use crate::error::session_error::SessionError;
+use crate::error::Result;
+
+crate::impl_service!(UserService);
+
+static COMMON_PASSWORDS: &[&str] = &[
+    "123456", "1234567", "12345678", "123456789", "1234567890", "111111", "000000", "654321",
+    "123123", "121212", "112233", "666666", "777777", "987654321", "qwerty", "qwerty123",
+    "qwertyuiop", "asdfgh", "asdfghjkl", "zxcvbnm", "1q2w3e4r", "1q2w3e4r5t", "1qaz2wsx",
+    "password", "password1", "password123", "passw0rd", "p@ssw0rd", "admin", "admin123",
+    "administrator", "root", "toor", "letmein", "welcome", "welcome1", "iloveyou", "monkey",
+    "dragon", "master", "sunshine", "princess", "football", "baseball", "superman", "trustno1",
+    "abc123", "abcdef", "secret", "changeme", "default", "guest", "user", "test", "test123",
+];
+
+// look-alike characters are left out, generated passwords are read from paper
+const PASSWORD_CHARS: &[u8] = b"abcdefghjkmnpqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789!@#$%*+=?";
+const PASSWORD_GENERATED_LENGTH: usize = 12;
+
+pub trait PasswordTrait {
+    fn argon2(&self) -> Result<Argon2<'static>>;
+    fn hash_password(&self, password: &str) -> Result<String>;
+    fn verify_password(&self, password: &str, hash: &str) -> Result<bool>;
+    fn needs_rehash(&self, hash: &str) -> bool;
+    fn password_policy(&self, login: &str, password: &str) -> Result<()>;
+    fn generate_password(&self, login: &str) -> String;
+    fn is_password_change_required(&self, user_model: &UserModel) -> bool;
+}
+
+impl PasswordTrait for UserService {
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(
+            self.cfg.password_memory_cost,
+            self.cfg.password_time_cost,
+            self.cfg.password_parallelism,
+            None,
+        )
+        .map_err(|_| ApiError::from(SessionError::PasswordHash))?;
+
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    fn hash_password(&self, password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        match self.argon2()?.hash_password(password.as_bytes(), &salt) {
+            Ok(value) => Ok(value.to_string()),
+            _ => Err(ApiError::from(SessionError::PasswordHash)),
+        }
+    }
+
+    fn verify_password(&self, password: &str, hash: &str) -> Result<bool> {
+        let parsed_hash = match PasswordHash::new(hash) {
+            Ok(value) => value,
+            _ => Err(ApiError::from(SessionError::PasswordHash))?,
+        };
+
+        // verification takes the parameters from the hash itself, so old hashes keep working
+        Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok())
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return false;
+        };
+
+        let is_shared_salt = parsed_hash
+            .salt
+            .is_some_and(|salt| salt.as_str().eq(self.cfg.password_salt.as_str()));
+
+        let is_outdated = match (Params::try_from(&parsed_hash), self.argon2()) {
+            (Ok(params), Ok(argon2)) => {
+                parsed_hash.algorithm.ne(&Algorithm::Argon2id.ident())
+                    || params.m_cost().ne(&argon2.params().m_cost())
+                    || params.t_cost().ne(&argon2.params().t_cost())
+                    || params.p_cost().ne(&argon2.params().p_cost())
+            }
+            _ => true,
+        };
+
+        is_shared_salt || is_outdated
+    }
+
+    fn password_policy(&self, login: &str, password: &str) -> Result<()> {
+        if password.chars().count() < self.cfg.password_min_length {
+            Err(ApiError::from(SessionError::PasswordLength))?
+        }
+
+        let classes = [
+            password.chars().any(|char| char.is_lowercase()),
+            password.chars().any(|char| char.is_uppercase()),
+            password.chars().any(|char| char.is_numeric()),
+            password.chars().any(|char| !char.is_alphanumeric()),
+        ]
+        .iter()
+        .filter(|value| **value)
+        .count();
+
+        if classes < self.cfg.password_min_classes {
+            Err(ApiError::from(SessionError::PasswordClasses))?
+        }
+
+        let password = password.to_lowercase();
+        if password.eq(&login.to_lowercase()) || COMMON_PASSWORDS.contains(&password.as_str()) {
+            Err(ApiError::from(SessionError::PasswordCommon))?
+        }
+
+        Ok(())
+    }
+
+    fn generate_password(&self, login: &str) -> String {
+        let length = self.cfg.password_min_length.max(PASSWORD_GENERATED_LENGTH);
+
+        loop {
+            let password = (0..length)
+                .map(|_| {
+                    PASSWORD_CHARS[OsRng.next_u32() as usize % PASSWORD_CHARS.len()] as char
+                })
+                .collect::<String>();
+
+            if self.password_policy(login, &password).is_ok() {
+                return password;
+            }
+        }
+    }
+
+    fn is_password_change_required(&self, user_model: &UserModel) -> bool {
+        // a zero expiration period keeps passwords valid until they are reset
+        let is_expired = self.cfg.password_expiration > 0
+            && user_model.password_changed_at.as_ref().is_some_and(|value| {
+                value.0 + TimeDelta::days(self.cfg.password_expiration) < Utc::now()
+            });
+
+        user_model.must_change_password || is_expired
+    }
+}
+
+pub trait AccountTrait {
+    fn is_account_valid(&self, user_model: &UserModel) -> bool;
+    fn is_certificate_password_required(&self, user_model: &UserModel) -> bool;
+}
+
+impl AccountTrait for UserService {
+    fn is_account_valid(&self, user_model: &UserModel) -> bool {
+        let now = Utc::now();
+
+        !(user_model.valid_from.as_ref().is_some_and(|value| value.0 > now)
+            || user_model.valid_until.as_ref().is_some_and(|value| value.0 <= now))
+    }
+
+    /// Accounts at the configured access level or above need both the certificate and the password.
+    fn is_certificate_password_required(&self, user_model: &UserModel) -> bool {
+        !self.cfg.mtls_ca_path.is_empty() && user_model.access_level <= self.cfg.mtls_password_level
+    }
+}
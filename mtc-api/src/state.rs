@@ -3,13 +3,21 @@ use std::sync::Arc;
 use crate::provider::config_provider::Config;
 use crate::provider::database_provider::Database;
 use crate::service::api_service::ApiService;
+use crate::service::audit_service::AuditService;
+use crate::service::certificate_service::CertificateService;
 use crate::service::group_service::GroupService;
+use crate::service::identity_service::IdentityService;
+use crate::service::lockout_service::LockoutService;
 use crate::service::migration_service::MigrationService;
 use crate::service::permissions_service::PermissionsService;
 use crate::service::role_service::RoleService;
 use crate::service::schema_service::SchemaService;
+use crate::service::session_service::SessionService;
+use crate::service::sign_in_log_service::SignInLogService;
 use crate::service::storage_service::StorageService;
 use crate::service::system_service::SystemService;
+use crate::service::token_service::TokenService;
+use crate::service::totp_service::TotpService;
 use crate::service::user_service::UserService;
 
 pub struct AppState {
@@ -25,6 +33,14 @@ pub struct AppState {
     pub storage_service: StorageService,
     pub migration_service: MigrationService,
     pub system_service: SystemService,
+    pub token_service: TokenService,
+    pub totp_service: TotpService,
+    pub lockout_service: LockoutService,
+    pub session_service: SessionService,
+    pub audit_service: AuditService,
+    pub sign_in_log_service: SignInLogService,
+    pub certificate_service: CertificateService,
+    pub identity_service: IdentityService,
 }
 
 impl AppState {
@@ -41,6 +57,14 @@ impl AppState {
         let storage_service = StorageService::new(&cfg, &db);
         let migration_service = MigrationService::new(&cfg, &db);
         let system_service = SystemService::new(&cfg, &db);
+        let token_service = TokenService::new(&cfg, &db);
+        let totp_service = TotpService::new(&cfg, &db);
+        let lockout_service = LockoutService::new(&cfg, &db);
+        let session_service = SessionService::new(&cfg, &db);
+        let audit_service = AuditService::new(&cfg, &db);
+        let sign_in_log_service = SignInLogService::new(&cfg, &db);
+        let certificate_service = CertificateService::new(&cfg, &db);
+        let identity_service = IdentityService::new(&cfg, &db);
 
         Self {
             cfg,
@@ -55,6 +79,14 @@ impl AppState {
             storage_service,
             migration_service,
             system_service,
+            token_service,
+            totp_service,
+            lockout_service,
+            session_service,
+            audit_service,
+            sign_in_log_service,
+            certificate_service,
+            identity_service,
         }
     }
 }
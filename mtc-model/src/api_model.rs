@@ -13,6 +13,8 @@ pub struct ApiModel {
     pub title: String,
     pub fields: Option<Value>,
     pub published: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
     pub created_by: String,
@@ -27,6 +29,7 @@ impl Default for ApiModel {
             title: "".to_string(),
             fields: None,
             published: false,
+            groups: None,
             created_at: Default::default(),
             updated_at: Default::default(),
             created_by: "".to_string(),
@@ -40,6 +43,8 @@ pub struct ApiPostModel {
     pub title: String,
     pub published: bool,
     pub fields: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
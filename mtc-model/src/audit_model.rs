@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+
+use crate::from_thing;
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct AuditModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub actor: String,
+    pub action: String,
+    pub target: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub ip: String,
+    pub created_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct AuditFilterModel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_to: Option<String>,
+}
@@ -7,6 +7,11 @@ pub struct AuthModel {
     pub roles: Vec<String>,
     pub groups: Vec<String>,
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub password_change: bool,
+    /// The administrator viewing the application as this user, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
 }
 
 impl Default for AuthModel {
@@ -16,6 +21,8 @@ impl Default for AuthModel {
             roles: vec!["anonymous".to_string()],
             groups: vec![],
             permissions: vec!["content::read".to_string()],
+            password_change: false,
+            impersonator: None,
         }
     }
 }
@@ -45,15 +52,135 @@ impl AuthModelTrait for AuthModel {
         self.groups.iter().any(|item| item == group)
     }
 
+    /// Grants may use a `*` segment (`*::read`, `news::*`), rules starting with `!` deny
+    /// whatever they match and always win over grants.
     fn is_permission(&self, permission: &str) -> bool {
-        self.permissions.iter().any(|item| item == permission)
+        let (deny, allow): (Vec<&String>, Vec<&String>) = self
+            .permissions
+            .iter()
+            .partition(|item| item.starts_with('!'));
+
+        allow.iter().any(|item| permission_match(item, permission))
+            && !deny
+                .iter()
+                .any(|item| rule_match(&item[1..], permission))
+    }
+}
+
+/// Scopes a wildcard never grants, their permissions are granted by the exact name only.
+pub const RESERVED_PERMISSION_SCOPES: [&str; 6] =
+    ["administrator", "user", "role", "group", "schema", "audit"];
+
+pub fn is_reserved_permission(permission: &str) -> bool {
+    permission
+        .split("::")
+        .next()
+        .is_some_and(|scope| RESERVED_PERMISSION_SCOPES.contains(&scope))
+}
+
+/// Matches a granted permission against a rule, a wildcard rule never reaches the reserved ones.
+pub fn permission_match(rule: &str, permission: &str) -> bool {
+    match rule.contains('*') && is_reserved_permission(permission) {
+        true => false,
+        false => rule_match(rule, permission),
     }
 }
 
+/// Matches a permission against a rule, where a single `*` segment stands for one or more segments.
+pub fn rule_match(rule: &str, permission: &str) -> bool {
+    match rule.split_once('*') {
+        None => rule == permission,
+        Some((prefix, suffix)) => {
+            permission.len() > prefix.len() + suffix.len()
+                && permission.starts_with(prefix)
+                && permission.ends_with(suffix)
+        }
+    }
+}
+
+/// A rule is a `::` separated slug with an optional leading `!`, where at most one segment is `*`
+/// and at least one segment is named.
+pub fn is_permission_rule(rule: &str) -> bool {
+    let rule = rule.strip_prefix('!').unwrap_or(rule);
+
+    rule != "*"
+        && rule.matches('*').count() <= 1
+        && rule.split("::").all(|segment| {
+            segment == "*"
+                || (!segment.is_empty()
+                    && segment
+                        .chars()
+                        .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-'))
+        })
+}
+
 #[derive(Deserialize, Serialize, Validate, Clone)]
 pub struct SignInModel {
     #[validate(length(min = 5, max = 15, message = "incorrect"))]
     pub login: String,
     #[validate(length(min = 6, message = "must be 6 characters at least"))]
     pub password: String,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(permissions: &[&str]) -> AuthModel {
+        AuthModel {
+            id: "tester".to_string(),
+            permissions: permissions.iter().map(|item| item.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn permission_match_wildcard_segments() {
+        assert!(permission_match("news::read", "news::read"));
+        assert!(permission_match("*::read", "news::read"));
+        assert!(permission_match("news::*", "news::write_own"));
+        assert!(permission_match("*::read", "private_storage::read"));
+        assert!(!permission_match("news::read", "news::write"));
+        assert!(!permission_match("*::read", "news::write"));
+        assert!(!permission_match("news::*", "blog::read"));
+        assert!(!permission_match("news::*", "news::"));
+    }
+
+    #[test]
+    fn permission_match_skips_reserved() {
+        assert!(!permission_match("*", "administrator"));
+        assert!(!permission_match("admin*", "administrator"));
+        assert!(!permission_match("*::write", "user::write"));
+        assert!(!permission_match("*::write", "role::write"));
+        assert!(!permission_match("*::read", "audit::read"));
+        assert!(!permission_match("user::*", "user::impersonate"));
+        assert!(permission_match("administrator", "administrator"));
+        assert!(permission_match("user::write", "user::write"));
+        assert!(rule_match("*::write", "user::write"));
+    }
+
+    #[test]
+    fn permission_rule_format() {
+        assert!(is_permission_rule("news::read"));
+        assert!(is_permission_rule("*::read"));
+        assert!(is_permission_rule("!news::*"));
+        assert!(!is_permission_rule("*"));
+        assert!(!is_permission_rule("!*"));
+        assert!(!is_permission_rule("*::*"));
+        assert!(!is_permission_rule("news::"));
+        assert!(!is_permission_rule("news:read"));
+        assert!(!is_permission_rule("news::re*d"));
+    }
+
+    #[test]
+    fn deny_wins_over_grant() {
+        let model = auth(&["*::read", "!news::read", "user::write", "!*::write"]);
+
+        assert!(model.is_permission("blog::read"));
+        assert!(!model.is_permission("news::read"));
+        assert!(!model.is_permission("user::write"));
+        assert!(!model.is_permission("audit::read"));
+        assert!(!model.is_admin());
+        assert!(auth(&["administrator"]).is_admin());
+        assert!(!auth(&["administrator", "!administrator"]).is_admin());
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::field_model::FieldModel;
+
+pub const SCHEMA_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Deserialize, Serialize, Validate, Debug, Clone, PartialEq)]
+pub struct SchemaBundleModel {
+    pub version: u32,
+    pub schemas: Vec<SchemaBundleItemModel>,
+    pub permissions: Vec<String>,
+    pub grants: Vec<PermissionGrantModel>,
+}
+
+impl Default for SchemaBundleModel {
+    fn default() -> Self {
+        Self {
+            version: SCHEMA_BUNDLE_VERSION,
+            schemas: vec![],
+            permissions: vec![],
+            grants: vec![],
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SchemaBundleItemModel {
+    pub slug: String,
+    pub title: String,
+    pub is_collection: bool,
+    pub is_public: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<FieldModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PermissionGrantModel {
+    pub role: String,
+    pub permission: String,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SchemaBundleDiffModel {
+    pub create: Vec<String>,
+    pub update: Vec<String>,
+    pub permissions: Vec<String>,
+    pub grants: Vec<PermissionGrantModel>,
+    pub missing_roles: Vec<String>,
+}
+
+impl SchemaBundleDiffModel {
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty()
+            && self.update.is_empty()
+            && self.permissions.is_empty()
+            && self.grants.is_empty()
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+use validator::Validate;
+
+use crate::from_thing;
+
+/// The certificate the browser presented on the current connection.
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct ClientCertificateModel {
+    pub subject: String,
+    pub common_name: Option<String>,
+    pub serial: String,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CertificateKindModel {
+    #[default]
+    Subject,
+    Serial,
+}
+
+impl CertificateKindModel {
+    /// Serial numbers are compared as upper case hex without separators and leading zeros.
+    pub fn normalize(&self, value: &str) -> String {
+        match self {
+            CertificateKindModel::Subject => value.trim().to_string(),
+            CertificateKindModel::Serial => value
+                .chars()
+                .filter(|char| char.is_ascii_hexdigit())
+                .collect::<String>()
+                .to_uppercase()
+                .trim_start_matches('0')
+                .to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct CertificateModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub login: String,
+    pub kind: CertificateKindModel,
+    pub value: String,
+    pub created_at: Datetime,
+    pub created_by: String,
+}
+
+#[derive(Deserialize, Serialize, Validate, Default, Clone)]
+pub struct CertificateCreateModel {
+    pub kind: CertificateKindModel,
+    #[validate(length(min = 1, max = 1024, message = "incorrect"))]
+    pub value: String,
+}
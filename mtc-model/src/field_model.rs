@@ -4,12 +4,23 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FieldModel {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub slug: String,
     pub title: String,
     #[serde(rename = "type")]
     pub field_type: FieldTypeModel,
 }
 
+impl FieldModel {
+    pub fn key(&self) -> &str {
+        match self.id.is_empty() {
+            true => &self.slug,
+            false => &self.id,
+        }
+    }
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum FieldTypeModel {
     #[default]
@@ -10,6 +10,8 @@ pub struct GroupModel {
     pub id: String,
     pub slug: String,
     pub title: String,
+    #[serde(default)]
+    pub parent: Option<String>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
     pub created_by: String,
@@ -22,6 +24,7 @@ impl Default for GroupModel {
             id: "".to_string(),
             slug: "".to_string(),
             title: "".to_string(),
+            parent: None,
             created_at: Default::default(),
             updated_at: Default::default(),
             created_by: "".to_string(),
@@ -33,9 +36,33 @@ impl Default for GroupModel {
 #[derive(Deserialize, Serialize, Validate)]
 pub struct GroupCreateModel {
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Validate)]
 pub struct GroupUpdateModel {
     pub title: String,
 }
+
+#[derive(Deserialize, Serialize, Validate, Default)]
+pub struct GroupParentModel {
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct GroupNodeModel {
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct GroupTreeModel {
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub children: Vec<GroupTreeModel>,
+}
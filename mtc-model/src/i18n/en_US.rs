@@ -2,6 +2,43 @@ pub static EN_US: &str = r#"{
     "id": "en-US",
     "texts": {
         "messages": {
+            "access_count_max": "sign ins to",
+            "access_count_min": "sign ins from",
+            "action": "action",
+            "actor": "actor",
+            "after": "after",
+            "any": "any",
+            "audit": "Audit log",
+            "before": "before",
+            "certificate_add": "Bind",
+            "certificate_serial": "Serial number",
+            "certificate_sign_in": "Sign in with certificate",
+            "certificate_subject": "Subject",
+            "certificates": "Client certificates",
+            "certificates_hint": "The account can be signed in with a certificate that matches one of these bindings",
+            "date_from": "date from",
+            "date_to": "date to",
+            "export": "export",
+            "filter": "filter",
+            "group": "group",
+            "group_root": "Drop here to move a group to the top level",
+            "group_tree_hint": "Drag a group onto another one to nest it",
+            "identities": "Linked identities",
+            "identities_hint": "Accounts of the identity provider that sign in as this user",
+            "identity_link": "Link",
+            "identity_sign_in": "Sign in with",
+            "identity_unlink": "Unlink",
+            "impersonate": "View as user",
+            "impersonation": "Viewing as",
+            "impersonation_end": "Return to my account",
+            "ip": "IP address",
+            "last_access_from": "last sign in from",
+            "last_access_to": "last sign in to",
+            "managers": "Managers",
+            "managers_applied": "Group managers saved",
+            "managers_apply": "Save managers",
+            "managers_hint": "Managers can view, create, block and reset passwords for members of the group and its subgroups",
+            "must_change_password": "password change pending",
             "not_found": "Page not found",
             "access": "access",
             "access_active": "active",
@@ -12,6 +49,7 @@ pub static EN_US: &str = r#"{
             "add": "Add",
             "add_field": "Add",
             "administrator": "Administrator",
+            "audience": "audience",
             "blocked": "blocked",
             "cancel": "Cancel",
             "caption_alert": "ALERT!",
@@ -28,6 +66,7 @@ pub static EN_US: &str = r#"{
             "collections": "Collections",
             "constructor": "Constructor",
             "content": "Content",
+            "conversion_errors": "conversion errors",
             "copyright": "2024 © Powered by MTC-CMS",
             "created_at": "created at",
             "created_by": "created by",
@@ -37,16 +76,24 @@ pub static EN_US: &str = r#"{
             "download": "download",
             "editor": "Editor",
             "external_data": "External data",
+            "failed_attempts": "failed attempts",
+            "field_remove": "remove",
+            "field_rename": "rename",
+            "field_retype": "change type",
             "fields": "fields",
+            "fields_migration": "fields migration",
+            "fields_migration_confirm": "The listed items will be migrated, values with conversion errors will be dropped. Press save again to apply.",
             "file": "file",
             "files": "Files",
             "home": "Home",
             "groups": "Groups",
             "in_progress": "In progress...",
+            "last_seen": "last seen",
             "load": "Load",
             "loading": "Loading...",
             "login": "login",
             "login_announcement": "You need to contact the responsible persons of the military unit to obtain credentials.",
+            "locked_until": "locked until",
             "logged_in": "You are logged in.",
             "login_now": "Login now!",
             "menu": "Menu",
@@ -59,63 +106,142 @@ pub static EN_US: &str = r#"{
             "page": "page",
             "password": "password",
             "password_change": "Change password",
+            "password_change_announcement": "Your password was set by an administrator or has expired. Choose a new one to continue.",
             "password_change_success": "Password success changed",
             "password_confirm": "confirm password",
             "password_new": "new password",
             "password_old": "old password",            
             "permissions": "Permissions",
+            "permissions_effective": "Effective permissions",
             "persons": "Persons",
+            "persons_import": "import",
+            "persons_import_preview": "Import preview",
+            "persons_imported": "Persons updated",
+            "persons_invalid": "invalid",
+            "persons_profile": "Profile fields",
+            "persons_unknown": "unknown logins",
+            "persons_updated": "to update",
             "processing": "Processing",
             "private_storage": "Private storage",
+            "provision": "Trainee accounts",
+            "provision_announcement": "one person per line, tab separated; leave empty to create the number of accounts above",
+            "provision_count": "number of accounts",
+            "provision_create": "create accounts",
+            "provision_prefix": "login prefix",
+            "provision_sheet": "credential sheet",
+            "provision_success": "Accounts created, keep the credential sheet safe",
             "public_storage": "Public storage",
             "published": "Published",
             "rank": "rank",
+            "recovery_codes": "Recovery codes",
+            "recovery_codes_announcement": "Keep these codes in a safe place. Each code can be used once instead of an authenticator code.",
             "refresh": "Refresh",
             "reload": "Reload",
+            "role": "role",
             "roles": "Roles",
+            "roles_applied": "Group roles saved",
+            "roles_apply": "Save roles",
+            "roles_group_hint": "Members of the group and of all its subgroups get these roles",
+            "roles_inherited": "Inherited from groups",
             "save": "Save",
             "schema": "Schemas",
             "schema_type": "schema type",
+            "session_current": "this session",
+            "session_terminate": "Terminate session",
+            "sessions": "Active sessions",
+            "sessions_terminate_others": "Sign out other sessions",
             "settings": "Settings",
             "sign_in": "Sign In",
+            "sign_in_failure": "failed",
+            "sign_in_result": "result",
+            "sign_in_success": "signed in",
+            "sign_ins": "Sign-in history",
+            "sign_ins_hint": "If you don't recognize an attempt, change your password",
             "sign_out": "Sign Out",
+            "sign_out_everywhere": "Sign out everywhere",
+            "sign_out_everywhere_success": "All sessions of the user are terminated",
             "single": "single",
             "singles": "Single",
             "size": "size",
             "slug": "slug",
             "something_wrong": "Something went wrong",
+            "sort": "sort",
+            "sort_access_count": "fewest sign ins",
+            "sort_access_count_desc": "most sign ins",
+            "sort_created_at": "created, oldest first",
+            "sort_created_at_desc": "created, newest first",
+            "sort_last_access": "last sign in, oldest first",
+            "sort_last_access_desc": "last sign in, newest first",
+            "sort_login": "login A-Z",
+            "sort_login_desc": "login Z-A",
+            "target": "target",
             "title": "title",
+            "totp_code": "authentication code",
+            "totp_disable": "Disable 2FA",
+            "totp_disabled": "Two-factor authentication is disabled",
+            "totp_enable": "Enable 2FA",
+            "totp_reset": "Reset 2FA",
+            "totp_reset_success": "Two-factor authentication is reset",
+            "totp_setup_announcement": "Scan the QR code with an authenticator app and enter the generated code.",
             "try_again": "Try again",
+            "two_factor": "two-factor authentication",
+            "two_factor_announcement": "Enter the code from your authenticator app or one of the recovery codes.",
             "type": "type",
+            "unlock": "Unlock",
             "updated_at": "updated at",
             "upload": "upload",
+            "user_agent": "browser",
+            "user_state": "state",
             "users": "Users",
             "user_active": "Active",
             "user_blocked": "Blocked",          
+            "valid_from": "valid from",
+            "valid_until": "valid until",
+            "validity": "Account validity of members",
+            "validity_applied": "Validity updated for members",
+            "validity_apply": "Apply to members",
             "welcome": "Welcome!",
             "welcome_announcement": "You can change your password with this form іf necessary.",
             "yes": "Yes"
         },
         "errors": {
             "access_forbidden": "Access forbidden",
+            "account_inactive": "Account is outside of its validity period",
+            "account_locked": "Account is temporarily locked after failed sign in attempts",
             "already_exists": "Entity already exists",
             "auth": "Authorization error",
             "bad_request": "Bad API request",
             "bad_response": "Bad API response",
+            "certificate_password_required": "This account needs the password together with the certificate",
+            "certificate_required": "Sign in to this account needs a client certificate bound to it",
             "clipboard": "Paste from clipboard error",
             "conflict": "Conflict error",
             "connection": "Connection error",
+            "group_cycle": "A group can't be nested inside itself or its subgroups",
+            "identity_not_linked": "This identity provider account isn't linked to any user",
+            "identity_provider": "The identity provider can't be reached or gave an invalid answer",
+            "impersonation_read_only": "Nothing can be changed while viewing as another user",
             "invalid_session": "Invalid session",
             "health": "Health check error",
             "invalid_credentials": "Invalid credential",
             "internal": "Internal server error",
             "fields": "All fields must be filled correctly",
+            "field_migration": "Some content values can't be converted to the new field types",
             "not_deleted": "Entity not deleted",
             "not_found": "Entity not found",
             "not_updated": "Entity not updated",
+            "password_change_required": "Password must be changed first",
+            "password_classes": "Password must combine lowercase and uppercase letters, digits and symbols",
+            "password_common": "Password is too common",
             "password_hash": "Generate password hash error",
+            "password_length": "Password is too short",
+            "password_unchanged": "New password must differ from the current one",
             "passwords_match": "New password and confirmation doesn't match",
             "something_wrong": "Something went wrong",
+            "too_many_attempts": "Too many sign in attempts, try again later",
+            "two_factor_enrollment": "Two-factor authentication must be set up to sign in",
+            "two_factor_required": "Two-factor authentication code is required",
+            "two_factor_secret": "Two-factor secret error",
             "validation": "Request validation error",
             "unsupported_media": "Unsupported Media Type",
             "user_blocked": "Account is blocked"
@@ -2,6 +2,43 @@ pub static UK_UA: &str = r#"{
     "id": "uk-UA",
     "texts": {
         "messages": {
+            "access_count_max": "входів до",
+            "access_count_min": "входів від",
+            "action": "дія",
+            "actor": "виконавець",
+            "after": "після",
+            "any": "будь-який",
+            "audit": "Журнал аудиту",
+            "before": "до",
+            "certificate_add": "Прив'язати",
+            "certificate_serial": "Серійний номер",
+            "certificate_sign_in": "Увійти із сертифікатом",
+            "certificate_subject": "Суб'єкт",
+            "certificates": "Клієнтські сертифікати",
+            "certificates_hint": "До облікового запису можна увійти із сертифікатом, що відповідає одній з цих прив'язок",
+            "date_from": "дата з",
+            "date_to": "дата по",
+            "export": "експорт",
+            "filter": "фільтр",
+            "group": "група",
+            "group_root": "Перетягніть сюди, щоб перенести групу на верхній рівень",
+            "group_tree_hint": "Перетягніть групу на іншу, щоб вкласти її",
+            "identities": "Прив'язані ідентичності",
+            "identities_hint": "Облікові записи постачальника ідентичності, що входять як цей користувач",
+            "identity_link": "Прив'язати",
+            "identity_sign_in": "Увійти через",
+            "identity_unlink": "Відв'язати",
+            "impersonate": "Переглянути як користувач",
+            "impersonation": "Перегляд від імені",
+            "impersonation_end": "Повернутися до мого облікового запису",
+            "ip": "IP адреса",
+            "last_access_from": "останній вхід від",
+            "last_access_to": "останній вхід до",
+            "managers": "Керівники",
+            "managers_applied": "Керівників групи збережено",
+            "managers_apply": "Зберегти керівників",
+            "managers_hint": "Керівники можуть переглядати, створювати, блокувати облікові записи та скидати паролі учасників групи та її підгруп",
+            "must_change_password": "очікується зміна пароля",
             "not_found": "Сторінка відсутня",
             "access": "доступ",
             "access_active": "активні",
@@ -12,6 +49,7 @@ pub static UK_UA: &str = r#"{
             "add": "Створити",
             "add_field": "Додати",
             "administrator": "Адміністратор",
+            "audience": "аудиторія",
             "blocked": "блок",
             "cancel": "Скасувати",
             "caption_alert": "УВАГА!",
@@ -28,6 +66,7 @@ pub static UK_UA: &str = r#"{
             "collections": "Колекції",
             "constructor": "Конструктор",
             "content": "Контент",
+            "conversion_errors": "помилки перетворення",
             "copyright": "2024 © Працює на MTC-CMS",
             "created_at": "створено",
             "created_by": "створено",            
@@ -37,16 +76,24 @@ pub static UK_UA: &str = r#"{
             "draft": "Чернетка",
             "editor": "Редагування",
             "external_data": "Зовнішні дані",
+            "failed_attempts": "невдалі спроби",
+            "field_remove": "видалення",
+            "field_rename": "перейменування",
+            "field_retype": "зміна типу",
             "fields": "поля",
+            "fields_migration": "міграція полів",
+            "fields_migration_confirm": "Перелічені записи буде перенесено, значення з помилками перетворення буде втрачено. Натисніть зберегти ще раз для застосування.",
             "file": "файл",
             "files": "Файли",
             "home": "Головна",
             "groups": "Групи",
             "in_progress": "Обробка...",
+            "last_seen": "остання активність",
             "load": "Завантажити",
             "loading": "Завантаження...",
             "login": "логін",
             "login_announcement": "Для отримання облікових даних, або у разі їх втрати, Вам потрібно звернутися до відповідальних осіб військової частини.",
+            "locked_until": "заблоковано до",
             "logged_in": "Ви успішно авторизовані.",
             "login_now": "Авторизація!",
             "menu": "Меню",
@@ -59,63 +106,142 @@ pub static UK_UA: &str = r#"{
             "page": "сторінка",
             "password": "пароль",
             "password_change": "Змінити пароль",
+            "password_change_announcement": "Ваш пароль встановлено адміністратором або термін його дії сплив. Оберіть новий, щоб продовжити.",
             "password_change_success": "Пароль успішно змінено",
             "password_confirm": "підтвердження пароля",
             "password_new": "новий пароль",
             "password_old": "дійсний пароль", 
             "permissions": "Дозволи",
+            "permissions_effective": "Дійсні дозволи",
             "persons": "Особовий склад",
+            "persons_import": "імпортувати",
+            "persons_import_preview": "Попередній перегляд імпорту",
+            "persons_imported": "Оновлено осіб",
+            "persons_invalid": "помилка",
+            "persons_profile": "Поля профілю",
+            "persons_unknown": "невідомі логіни",
+            "persons_updated": "буде оновлено",
             "processing": "Опрацювання",
             "private_storage": "Захищене сховище",
+            "provision": "Облікові записи слухачів",
+            "provision_announcement": "одна особа на рядок, через табуляцію; залиште порожнім, щоб створити вказану кількість облікових записів",
+            "provision_count": "кількість облікових записів",
+            "provision_create": "створити облікові записи",
+            "provision_prefix": "префікс логіна",
+            "provision_sheet": "аркуш облікових даних",
+            "provision_success": "Облікові записи створено, збережіть аркуш облікових даних",
             "public_storage": "Загальне сховище",
             "published": "Опубліковано",
             "rank": "звання",
+            "recovery_codes": "Резервні коди",
+            "recovery_codes_announcement": "Збережіть ці коди у надійному місці. Кожен код можна використати один раз замість коду автентифікатора.",
             "refresh": "Оновити",
             "reload": "Перезавантажити",
+            "role": "роль",
             "roles": "Ролі",
+            "roles_applied": "Ролі групи збережено",
+            "roles_apply": "Зберегти ролі",
+            "roles_group_hint": "Ці ролі отримують учасники групи та всіх її підгруп",
+            "roles_inherited": "Успадковано від груп",
             "save": "Зберегти",
             "schema": "Схеми",
             "schema_type": "тип схеми",
+            "session_current": "поточний сеанс",
+            "session_terminate": "Завершити сеанс",
+            "sessions": "Активні сеанси",
+            "sessions_terminate_others": "Завершити інші сеанси",
             "settings": "Налаштування",
             "sign_in": "Вхід",
+            "sign_in_failure": "невдало",
+            "sign_in_result": "результат",
+            "sign_in_success": "вхід виконано",
+            "sign_ins": "Історія входів",
+            "sign_ins_hint": "Якщо ви не впізнаєте спробу входу, змініть пароль",
             "sign_out": "Вихід",
+            "sign_out_everywhere": "Вийти всюди",
+            "sign_out_everywhere_success": "Усі сеанси користувача завершено",
             "single": "одиночна",
             "singles": "Одиночні",
             "size": "розмір",
             "slug": "ідентифікатор",
             "something_wrong": "Невдача спіткала нашу команду",
+            "sort": "сортування",
+            "sort_access_count": "найменше входів",
+            "sort_access_count_desc": "найбільше входів",
+            "sort_created_at": "створені, давніші спочатку",
+            "sort_created_at_desc": "створені, новіші спочатку",
+            "sort_last_access": "останній вхід, давніші спочатку",
+            "sort_last_access_desc": "останній вхід, новіші спочатку",
+            "sort_login": "логін А-Я",
+            "sort_login_desc": "логін Я-А",
+            "target": "об'єкт",
             "title": "назва",
+            "totp_code": "код автентифікації",
+            "totp_disable": "Вимкнути 2FA",
+            "totp_disabled": "Двофакторну автентифікацію вимкнено",
+            "totp_enable": "Увімкнути 2FA",
+            "totp_reset": "Скинути 2FA",
+            "totp_reset_success": "Двофакторну автентифікацію скинуто",
+            "totp_setup_announcement": "Відскануйте QR-код застосунком автентифікації та введіть згенерований код.",
             "try_again": "Спробувати ще",
+            "two_factor": "двофакторна автентифікація",
+            "two_factor_announcement": "Введіть код із застосунку автентифікації або один з резервних кодів.",
             "type": "тип",
+            "unlock": "Розблокувати",
             "upload": "завантажити",
             "updated_at": "оновлено",
+            "user_agent": "браузер",
+            "user_state": "стан",
             "users": "Користувачі",
             "user_active": "Чинний",
             "user_blocked": "Блок",
+            "valid_from": "діє з",
+            "valid_until": "діє до",
+            "validity": "Термін дії облікових записів учасників",
+            "validity_applied": "Термін дії оновлено для учасників",
+            "validity_apply": "Застосувати до учасників",
             "welcome": "Вітаємо!",
             "welcome_announcement": "У разі необхідності Ви можете змінити свій пароль за допомоги цієї форми.",
             "yes": "Так"
         },
         "errors": {
             "access_forbidden": "Доступ заборонено",
+            "account_inactive": "Обліковий запис поза терміном дії",
+            "account_locked": "Обліковий запис тимчасово заблоковано після невдалих спроб входу",
             "already_exists": "Запис вже існує",
             "auth": "Помилка авторізації",
             "bad_request": "Помилковий запит АПІ",
             "bad_response": "Помилкова відповідь АПІ",
+            "certificate_password_required": "Цей обліковий запис потребує пароля разом із сертифікатом",
+            "certificate_required": "Вхід до цього облікового запису потребує прив'язаного клієнтського сертифіката",
             "clipboard": "Помилка читання буфера обміну",
             "conflict": "Конфликт при обробці запиту",
             "connection": "Помилка з'єднання",
+            "group_cycle": "Групу не можна вкласти в неї саму або в її підгрупи",
+            "identity_not_linked": "Цей обліковий запис постачальника ідентичності не прив'язаний до жодного користувача",
+            "identity_provider": "Постачальник ідентичності недоступний або надав некоректну відповідь",
+            "impersonation_read_only": "Під час перегляду від імені іншого користувача нічого не можна змінювати",
             "invalid_session": "Недійсна сессія",
             "internal": "Внутрішня помилка серверу",
             "health": "Помилка перевірки зв'язку",
             "invalid_credentials": "Недійсні облікові дані",
             "fields": "Усі поля повинні бути заповнені коректно",
+            "field_migration": "Деякі значення вмісту неможливо перетворити до нових типів полів",
             "not_deleted": "Помилка видалення запису",
             "not_found": "Запис відсутній",
             "not_updated": "Помилка оновлення запису",
+            "password_change_required": "Спершу необхідно змінити пароль",
+            "password_classes": "Пароль повинен поєднувати малі та великі літери, цифри та символи",
+            "password_common": "Пароль занадто поширений",
             "password_hash": "Помилка генерування хеша паролю",
+            "password_length": "Пароль занадто короткий",
+            "password_unchanged": "Новий пароль має відрізнятися від поточного",
             "passwords_match": "Новий пароль та підтвердження повинні співпадати",
             "something_wrong": "Щось пійшло не так",
+            "too_many_attempts": "Забагато спроб входу, спробуйте пізніше",
+            "two_factor_enrollment": "Для входу необхідно налаштувати двофакторну автентифікацію",
+            "two_factor_required": "Потрібен код двофакторної автентифікації",
+            "two_factor_secret": "Помилка секрету двофакторної автентифікації",
             "validation": "Помилка перевірки запросу",
             "unsupported_media": "Тип запиту не підтримується",
             "user_blocked": "Обліковий запис заблоковано"
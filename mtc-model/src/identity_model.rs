@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+
+use crate::from_thing;
+
+/// An identity provider account linked to a user.
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct IdentityModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub login: String,
+    pub issuer: String,
+    pub subject: String,
+    pub created_at: Datetime,
+}
+
+/// The single sign-on provider offered on the sign-in page,
+/// with the outcome of the last redirect back from it.
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct IdentityProviderModel {
+    pub name: String,
+    pub message: Option<String>,
+}
@@ -15,6 +15,14 @@ pub mod user_details_model;
 pub mod storage_model;
 pub mod record_model;
 pub mod list_model;
+pub mod bundle_model;
+pub mod token_model;
+pub mod totp_model;
+pub mod session_model;
+pub mod audit_model;
+pub mod sign_in_log_model;
+pub mod certificate_model;
+pub mod identity_model;
 
 pub fn from_thing<'de, D>(deserializer: D) -> Result<String, D::Error>
     where
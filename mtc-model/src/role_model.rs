@@ -12,6 +12,8 @@ pub struct RoleModel {
     pub title: String,
     pub user_access_level: i32,
     pub user_access_all: bool,
+    #[serde(default)]
+    pub two_factor: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<Vec<String>>,
     pub created_at: Datetime,
@@ -28,6 +30,7 @@ impl Default for RoleModel {
             title: "".to_string(),
             user_access_level: 999,
             user_access_all: false,
+            two_factor: false,
             permissions: Some(vec!["content:read".to_string()]),
             created_at: Default::default(),
             updated_at: Default::default(),
@@ -42,6 +45,8 @@ pub struct RoleCreateModel {
     pub title: String,
     pub user_access_level: i32,
     pub user_access_all: bool,
+    #[serde(default)]
+    pub two_factor: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<Vec<String>>,
 }
@@ -51,6 +56,8 @@ pub struct RoleUpdateModel {
     pub title: String,
     pub user_access_level: i32,
     pub user_access_all: bool,
+    #[serde(default)]
+    pub two_factor: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<Vec<String>>,
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use surrealdb_sql::Datetime;
 use validator::Validate;
 
-use crate::field_model::FieldModel;
+use crate::field_model::{FieldModel, FieldTypeModel};
 use crate::from_thing;
 
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
@@ -16,6 +16,8 @@ pub struct SchemaModel {
     pub is_public: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<Vec<FieldModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
     pub created_by: String,
@@ -32,6 +34,7 @@ impl Default for SchemaModel{
             is_collection: false,
             is_public: false,
             fields: None,
+            groups: None,
             created_at: Default::default(),
             updated_at: Default::default(),
             created_by: "".to_string(),
@@ -47,16 +50,48 @@ pub struct SchemaCreateModel {
     pub is_public: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<Vec<FieldModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Serialize, Validate)]
 pub struct SchemaUpdateModel {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_collection: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_public: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<Vec<FieldModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Validate)]
 pub struct SchemaFieldsModel {
     pub fields: Option<Vec<FieldModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum FieldChangeModel {
+    Rename { from: String, to: String },
+    Retype { slug: String, from: FieldTypeModel, to: FieldTypeModel },
+    Remove { slug: String },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct FieldMigrationItemModel {
+    pub slug: String,
+    pub title: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SchemaMigrationModel {
+    pub changes: Vec<FieldChangeModel>,
+    pub items: Vec<FieldMigrationItemModel>,
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+
+use crate::from_thing;
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct SessionModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub login: String,
+    pub ip: String,
+    pub user_agent: String,
+    pub created_at: Datetime,
+    pub last_seen: Datetime,
+    #[serde(default)]
+    pub current: bool,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+
+use crate::from_thing;
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct SignInLogModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub login: String,
+    pub success: bool,
+    pub reason: Option<String>,
+    pub ip: String,
+    pub user_agent: String,
+    pub created_at: Datetime,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct SignInLogFilterModel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_to: Option<String>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use surrealdb_sql::Datetime;
+use validator::Validate;
+
+use crate::from_thing;
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct TokenModel {
+    #[serde(deserialize_with = "from_thing")]
+    pub id: String,
+    pub login: String,
+    pub title: String,
+    pub scope: Vec<String>,
+    pub expires_at: Option<Datetime>,
+    pub last_used_at: Option<Datetime>,
+    pub last_used_ip: Option<String>,
+    pub created_at: Datetime,
+}
+
+#[derive(Deserialize, Serialize, Validate)]
+pub struct TokenCreateModel {
+    #[validate(length(min = 4, max = 50, message = "incorrect"))]
+    pub title: String,
+    pub scope: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Datetime>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct TokenSecretModel {
+    pub token: String,
+    pub model: TokenModel,
+}
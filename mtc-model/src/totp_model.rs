@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct TotpSetupModel {
+    pub secret: String,
+    pub url: String,
+    pub qr: String,
+}
+
+#[derive(Deserialize, Serialize, Validate)]
+pub struct TotpCodeModel {
+    #[validate(length(min = 6, max = 20, message = "incorrect"))]
+    pub code: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct TotpRecoveryModel {
+    pub codes: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct TotpStatusModel {
+    pub enabled: bool,
+    pub required: bool,
+}
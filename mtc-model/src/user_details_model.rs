@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use surrealdb_sql::Datetime;
+use validator::Validate;
 
 #[derive(Serialize, Default, Debug, Deserialize, Clone, PartialEq)]
 pub struct UserDetailsModel {
@@ -13,4 +15,44 @@ pub struct UserDetailsStateModel {
     pub blocked: bool,
     pub last_access: Datetime,
     pub access_count: i32,
+    #[serde(default)]
+    pub failed_count: i32,
+    #[serde(default)]
+    pub locked_until: Option<Datetime>,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonModel {
+    pub login: String,
+    #[serde(default)]
+    pub fields: Option<Value>,
+}
+
+#[derive(Serialize, Default, Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PersonFormatModel {
+    #[default]
+    Tsv,
+    Csv,
+    Json,
+}
+
+#[derive(Serialize, Default, Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonExportModel {
+    #[serde(default)]
+    pub format: PersonFormatModel,
+}
+
+#[derive(Serialize, Debug, Deserialize, Validate, Clone, PartialEq)]
+pub struct PersonImportModel {
+    #[serde(default)]
+    pub format: PersonFormatModel,
+    pub data: String,
+}
+
+#[derive(Serialize, Default, Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonImportResultModel {
+    pub updated: Vec<String>,
+    pub unknown: Vec<String>,
+    pub invalid: Vec<String>,
 }
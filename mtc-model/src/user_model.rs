@@ -13,9 +13,23 @@ pub struct UserModel {
     #[serde(skip_serializing, default)]
     pub password: String,
     pub blocked: bool,
+    #[serde(default)]
+    pub totp_enabled: bool,
     pub access_level: i32,
     pub access_count: i32,
     pub last_access: Option<Datetime>,
+    #[serde(default)]
+    pub failed_count: i32,
+    #[serde(default)]
+    pub locked_until: Option<Datetime>,
+    #[serde(default)]
+    pub must_change_password: bool,
+    #[serde(default)]
+    pub password_changed_at: Option<Datetime>,
+    #[serde(default)]
+    pub valid_from: Option<Datetime>,
+    #[serde(default)]
+    pub valid_until: Option<Datetime>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<Value>,
     pub created_at: Datetime,
@@ -31,9 +45,16 @@ impl Default for UserModel {
             login: "".to_string(),
             password: "".to_string(),
             blocked: false,
+            totp_enabled: false,
             access_level: 999,
             access_count: 0,
             last_access: None,
+            failed_count: 0,
+            locked_until: None,
+            must_change_password: false,
+            password_changed_at: None,
+            valid_from: None,
+            valid_until: None,
             fields: None,
             created_at: Default::default(),
             updated_at: Default::default(),
@@ -48,6 +69,10 @@ pub struct UserCreateModel {
     pub blocked: bool,
     pub password: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub groups: Option<Vec<String>>,
@@ -59,6 +84,10 @@ pub struct UserUpdateModel {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub groups: Option<Vec<String>>,
@@ -66,6 +95,87 @@ pub struct UserUpdateModel {
     pub fields: Option<Value>,
 }
 
+#[derive(Serialize, Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct UserFilterModel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_access_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_access_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_count_min: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_count_max: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
+
+/// Sort keys accepted by the users list, the first one is the default.
+pub const USER_SORT: [&str; 8] = [
+    "login",
+    "login_desc",
+    "last_access",
+    "last_access_desc",
+    "access_count",
+    "access_count_desc",
+    "created_at",
+    "created_at_desc",
+];
+
+#[derive(Deserialize, Serialize, Validate, Default)]
+pub struct UserValidityModel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<Datetime>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserProvisionFormatModel {
+    #[default]
+    Json,
+    Csv,
+    Html,
+}
+
+#[derive(Deserialize, Serialize, Validate, Default, Clone)]
+pub struct UserProvisionModel {
+    pub group: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 10, message = "incorrect"))]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 500, message = "incorrect"))]
+    pub count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 500, message = "incorrect"))]
+    pub persons: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<Datetime>,
+    #[serde(default)]
+    pub format: UserProvisionFormatModel,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct UserCredentialModel {
+    pub login: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Value>,
+}
+
 #[derive(Deserialize, Serialize, Validate)]
 pub struct UserChangePasswordModel {
     #[validate(length(min = 6, message = "Password must be 6 characters at least"))]
@@ -3,6 +3,12 @@ use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
 use dioxus_std::translate;
 
+use mtc_model::totp_model::TotpStatusModel;
+
+use crate::element::user_dashboard::identities::Identities;
+use crate::element::user_dashboard::sessions::Sessions;
+use crate::element::user_dashboard::sign_ins::SignIns;
+use crate::element::user_dashboard::two_factor::TwoFactorSetup;
 use crate::handler::auth_handler::AuthHandler;
 use crate::model::modal_model::ModalModel;
 use crate::service::validator_service::ValidatorService;
@@ -13,6 +19,18 @@ pub fn Dashboard() -> Element {
     let i18 = use_i18();
 
     let mut is_busy = use_signal(|| false);
+    let mut totp_status = use_signal(TotpStatusModel::default);
+    let mut is_totp_setup = use_signal(|| false);
+
+    let totp_reload = move || {
+        spawn(async move {
+            if let Ok(value) = APP_STATE.peek().api.get_totp_status().await {
+                totp_status.set(value)
+            }
+        });
+    };
+
+    use_hook(totp_reload);
 
     let sign_out = move |event: Event<MouseData>| {
         spawn(async move {
@@ -72,6 +90,35 @@ pub fn Dashboard() -> Element {
         });
     };
 
+    let totp_disable = move |event: Event<FormData>| {
+        is_busy.set(true);
+
+        if !event.is_string_valid("code", 6) {
+            APP_STATE
+                .peek()
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            is_busy.set(false);
+            return;
+        };
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.disable_totp(&event.get_string("code")).await {
+                Ok(_) => {
+                    totp_status.set(TotpStatusModel::default());
+                    app_state.modal.signal().set(ModalModel::Success(translate!(
+                        i18,
+                        "messages.totp_disabled"
+                    )))
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
     rsx! {
         div { class: "hero",
             div { class: "hero-content flex-col lg:flex-row-reverse",
@@ -79,63 +126,128 @@ pub fn Dashboard() -> Element {
                     h1 { class: "text-3xl font-bold", { translate!(i18, "messages.welcome") } }
                     p { class: "py-6", { translate!(i18, "messages.welcome_announcement") } }
                 }
-                div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
-                    form { class: "card-body",
-                        id: "password-form",
-                        autocomplete: "off",
-                        onsubmit: password_submit,
-                        div { class: "form-control",
-                            label { class: "label",
-                                span { class:"label-text", { translate!(i18, "messages.password_old") } }
-                            }
-                            input { r#type: "password", name: "old-password",
-                                class: "input input-bordered",
-                                minlength: 6,
-                                maxlength: 20,
-                                required: true,
-                            }
-                        }
-                        div { class: "form-control",
-                            label { class: "label",
-                                span { class:"label-text", { translate!(i18, "messages.password_new") } }
+                div { class: "flex flex-col gap-6 w-full max-w-sm",
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        form { class: "card-body",
+                            id: "password-form",
+                            autocomplete: "off",
+                            onsubmit: password_submit,
+                            div { class: "form-control",
+                                label { class: "label",
+                                    span { class:"label-text", { translate!(i18, "messages.password_old") } }
+                                }
+                                input { r#type: "password", name: "old-password",
+                                    class: "input input-bordered",
+                                    minlength: 6,
+                                    maxlength: 20,
+                                    required: true,
+                                }
                             }
-                            input { r#type: "password", name: "new-password",
-                                class: "input input-bordered",
-                                minlength: 6,
-                                maxlength: 20,
-                                required: true,
+                            div { class: "form-control",
+                                label { class: "label",
+                                    span { class:"label-text", { translate!(i18, "messages.password_new") } }
+                                }
+                                input { r#type: "password", name: "new-password",
+                                    class: "input input-bordered",
+                                    minlength: 6,
+                                    maxlength: 20,
+                                    required: true,
+                                }
                             }
-                        }
-                        div { class: "form-control",
-                            label { class: "label",
-                                span { class:"label-text", { translate!(i18, "messages.password_confirm") } }
+                            div { class: "form-control",
+                                label { class: "label",
+                                    span { class:"label-text", { translate!(i18, "messages.password_confirm") } }
+                                }
+                                input { r#type: "password", name: "confirm-password",
+                                    class: "input input-bordered",
+                                    minlength: 6,
+                                    maxlength: 20,
+                                    required: true,
+                                }
+                            }                        
+                            div { class: "form-control mt-6",
+                                if !is_busy() {
+                                    button { class: "btn btn-primary",
+                                        r#type: "submit",
+                                        Icon {
+                                            width: 22,
+                                            height: 22,
+                                            icon: dioxus_free_icons::icons::md_action_icons::MdLockOutline
+                                        }
+                                        { translate!(i18, "messages.password_change") }
+                                    }
+                                } else {
+                                    div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
+                                        span { class: "loading loading-spinner loading-md" }
+                                        span { { translate!(i18, "messages.processing") } "..." }
+                                    }
+                                }
                             }
-                            input { r#type: "password", name: "confirm-password",
-                                class: "input input-bordered",
-                                minlength: 6,
-                                maxlength: 20,
-                                required: true,
+                        }    
+                    }
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        if is_totp_setup() {
+                            TwoFactorSetup {
+                                on_done: move |_| {
+                                    is_totp_setup.set(false);
+                                    totp_reload();
+                                }
                             }
-                        }                        
-                        div { class: "form-control mt-6",
-                            if !is_busy() {
-                                button { class: "btn btn-primary",
-                                    r#type: "submit",
-                                    Icon {
-                                        width: 22,
-                                        height: 22,
-                                        icon: dioxus_free_icons::icons::md_action_icons::MdLockOutline
+                        } else if totp_status().enabled {
+                            form { class: "card-body",
+                                autocomplete: "off",
+                                onsubmit: totp_disable,
+                                h2 { class: "card-title", { translate!(i18, "messages.two_factor") } }
+                                if !totp_status().required {
+                                    div { class: "form-control",
+                                        label { class: "label",
+                                            span { class:"label-text", { translate!(i18, "messages.totp_code") } }
+                                        }
+                                        input { r#type: "text", name: "code",
+                                            class: "input input-bordered",
+                                            minlength: 6,
+                                            maxlength: 6,
+                                            required: true,
+                                        }
+                                    }
+                                    div { class: "form-control mt-6",
+                                        button { class: "btn btn-outline btn-error",
+                                            r#type: "submit",
+                                            disabled: is_busy(),
+                                            Icon {
+                                                width: 22,
+                                                height: 22,
+                                                icon: dioxus_free_icons::icons::md_action_icons::MdLockOpen
+                                            }
+                                            { translate!(i18, "messages.totp_disable") }
+                                        }
                                     }
-                                    { translate!(i18, "messages.password_change") }
                                 }
-                            } else {
-                                div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
-                                    span { class: "loading loading-spinner loading-md" }
-                                    span { { translate!(i18, "messages.processing") } "..." }
+                            }
+                        } else {
+                            div { class: "card-body",
+                                h2 { class: "card-title", { translate!(i18, "messages.two_factor") } }
+                                div { class: "form-control mt-6",
+                                    button { class: "btn btn-primary",
+                                        onclick: move |_| is_totp_setup.set(true),
+                                        Icon {
+                                            width: 22,
+                                            height: 22,
+                                            icon: dioxus_free_icons::icons::md_action_icons::MdVerifiedUser
+                                        }
+                                        { translate!(i18, "messages.totp_enable") }
+                                    }
                                 }
                             }
                         }
-                    }    
+                    }
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        Sessions {}
+                    }
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        SignIns {}
+                    }
+                    Identities {}
                 }
             }
         }
@@ -0,0 +1,112 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::identity_model::{IdentityModel, IdentityProviderModel};
+
+use crate::handler::auth_handler::AuthHandler;
+use crate::model::modal_model::ModalModel;
+use crate::{API_URL, APP_STATE};
+
+#[component]
+pub fn Identities() -> Element {
+    let i18 = use_i18();
+
+    let mut is_busy = use_signal(|| false);
+    let mut identity_provider = use_signal(|| None::<IdentityProviderModel>);
+    let mut identities = use_signal(Vec::<IdentityModel>::new);
+
+    let identities_reload = move || {
+        spawn(async move {
+            match APP_STATE.peek().api.get_identities().await {
+                Ok(value) => identities.set(value),
+                Err(e) => APP_STATE
+                    .peek()
+                    .modal
+                    .signal()
+                    .set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    // linking ends with a redirect back to the dashboard, a failure comes along with the provider
+    use_hook(move || {
+        spawn(async move {
+            let Ok(Some(value)) = APP_STATE.peek().api.get_identity_provider().await else {
+                return;
+            };
+
+            if let Some(message) = value.message.clone() {
+                APP_STATE
+                    .peek()
+                    .modal
+                    .signal()
+                    .set(ModalModel::Error(translate!(i18, message.as_str())))
+            }
+            identity_provider.set(Some(value));
+            identities_reload();
+        });
+    });
+
+    let mut identity_delete = move |id: String| {
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.delete_identity(&id).await {
+                Ok(_) => identities_reload(),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let Some(provider) = identity_provider() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+            div { class: "card-body",
+                h2 { class: "card-title", { translate!(i18, "messages.identities") } }
+                span { class: "label-text-alt", { translate!(i18, "messages.identities_hint") } }
+                for identity in identities() {
+                    div { class: "flex flex-row items-center gap-3 rounded border p-2 input-bordered",
+                        div { class: "flex grow flex-col overflow-hidden",
+                            span { class: "truncate label-text", title: identity.subject.clone(), { identity.subject.clone() } }
+                            span { class: "truncate label-text-alt", { identity.issuer.clone() } }
+                            span { class: "label-text-alt",
+                                { translate!(i18, "messages.created_at") } ": "
+                                { identity.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() }
+                            }
+                        }
+                        button { class: "btn btn-ghost btn-sm text-error",
+                            disabled: is_busy(),
+                            title: translate!(i18, "messages.identity_unlink"),
+                            onclick: move |_| identity_delete(identity.id.clone()),
+                            Icon {
+                                width: 18,
+                                height: 18,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_content_icons::MdLinkOff
+                            }
+                        }
+                    }
+                }
+                div { class: "form-control mt-6",
+                    a { class: "btn btn-outline",
+                        href: [API_URL, "auth", "oidc", "link"].join("/"),
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            icon: dioxus_free_icons::icons::md_content_icons::MdLink
+                        }
+                        { translate!(i18, "messages.identity_link") } " " { provider.name }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -4,14 +4,26 @@ use mtc_model::auth_model::AuthModelTrait;
 
 use crate::APP_STATE;
 use crate::element::user_dashboard::dashboard::Dashboard;
+use crate::element::user_dashboard::password_change::PasswordChange;
 use crate::element::user_dashboard::sign_in::SignIn;
 
 mod sign_in;
 mod dashboard;
+mod two_factor;
+mod sessions;
+mod sign_ins;
+mod identities;
+mod password_change;
 
 #[component]
 pub fn UserDashboard() -> Element {
-    match !APP_STATE.peek().auth.signal().read().is_auth() {
+    let auth_state = APP_STATE.peek().auth.signal();
+
+    if auth_state.read().password_change {
+        return rsx! { PasswordChange {} };
+    }
+
+    match !auth_state.read().is_auth() {
         true => rsx! { SignIn {} },
         false => rsx! { Dashboard {} },
     }
@@ -0,0 +1,155 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use crate::handler::auth_handler::AuthHandler;
+use crate::model::modal_model::ModalModel;
+use crate::service::health_service::HealthService;
+use crate::service::validator_service::ValidatorService;
+use crate::APP_STATE;
+
+#[component]
+pub fn PasswordChange() -> Element {
+    let i18 = use_i18();
+
+    let mut is_busy = use_signal(|| false);
+
+    let sign_out = move |_| {
+        spawn(async move {
+            is_busy.set(true);
+            let app_state = APP_STATE.read();
+
+            match app_state.api.sign_out().await {
+                Ok(auth_model) => app_state.auth.signal().set(auth_model),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let password_submit = move |event: Event<FormData>| {
+        is_busy.set(true);
+
+        if event.get_string("confirm-password") != event.get_string("new-password") {
+            APP_STATE
+                .peek()
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.passwords_match")));
+            is_busy.set(false);
+            return;
+        };
+
+        if !event.is_string_valid("old-password", 6) | !event.is_string_valid("new-password", 6) {
+            APP_STATE
+                .peek()
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            is_busy.set(false);
+            return;
+        };
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state
+                .api
+                .change_password(
+                    &event.get_string("old-password"),
+                    &event.get_string("new-password"),
+                )
+                .await
+            {
+                Ok(_) => {
+                    app_state.service.get_credentials();
+                    app_state.modal.signal().set(ModalModel::Success(translate!(
+                        i18,
+                        "messages.password_change_success"
+                    )))
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "hero",
+            div { class: "hero-content flex-col lg:flex-row-reverse",
+                div { class: "text-center lg:text-left w-full sm:max-w-sm",
+                    h1 { class: "text-3xl font-bold", { translate!(i18, "messages.password_change") } }
+                    p { class: "py-6", { translate!(i18, "messages.password_change_announcement") } }
+                }
+                div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                    form { class: "card-body",
+                        id: "password-form",
+                        autocomplete: "off",
+                        onsubmit: password_submit,
+                        div { class: "form-control",
+                            label { class: "label",
+                                span { class:"label-text", { translate!(i18, "messages.password_old") } }
+                            }
+                            input { r#type: "password", name: "old-password",
+                                class: "input input-bordered",
+                                minlength: 6,
+                                maxlength: 20,
+                                required: true,
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label",
+                                span { class:"label-text", { translate!(i18, "messages.password_new") } }
+                            }
+                            input { r#type: "password", name: "new-password",
+                                class: "input input-bordered",
+                                minlength: 6,
+                                maxlength: 20,
+                                required: true,
+                            }
+                        }
+                        div { class: "form-control",
+                            label { class: "label",
+                                span { class:"label-text", { translate!(i18, "messages.password_confirm") } }
+                            }
+                            input { r#type: "password", name: "confirm-password",
+                                class: "input input-bordered",
+                                minlength: 6,
+                                maxlength: 20,
+                                required: true,
+                            }
+                        }
+                        div { class: "form-control mt-6 gap-3",
+                            if !is_busy() {
+                                button { class: "btn btn-primary",
+                                    r#type: "submit",
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        icon: dioxus_free_icons::icons::md_action_icons::MdLockOutline
+                                    }
+                                    { translate!(i18, "messages.password_change") }
+                                }
+                                button { class: "btn btn-outline btn-error",
+                                    r#type: "button",
+                                    onclick: sign_out,
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        icon: dioxus_free_icons::icons::md_action_icons::MdLogout
+                                    }
+                                    { translate!(i18, "messages.sign_out") }
+                                }
+                            } else {
+                                div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
+                                    span { class: "loading loading-spinner loading-md" }
+                                    span { { translate!(i18, "messages.processing") } "..." }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::session_model::SessionModel;
+
+use crate::handler::auth_handler::AuthHandler;
+use crate::model::modal_model::ModalModel;
+use crate::APP_STATE;
+
+#[component]
+pub fn Sessions() -> Element {
+    let i18 = use_i18();
+
+    let mut is_busy = use_signal(|| false);
+    let mut sessions = use_signal(Vec::<SessionModel>::new);
+
+    let sessions_reload = move || {
+        spawn(async move {
+            match APP_STATE.peek().api.get_sessions().await {
+                Ok(value) => sessions.set(value),
+                Err(e) => APP_STATE
+                    .peek()
+                    .modal
+                    .signal()
+                    .set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    use_hook(sessions_reload);
+
+    let mut session_delete = move |session: SessionModel| {
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.delete_session(&session.id).await {
+                Ok(_) => {
+                    // terminating the current session is a regular sign out
+                    if session.current {
+                        if let Ok(auth_model) = app_state.api.get_credentials().await {
+                            app_state.auth.signal().set(auth_model)
+                        }
+                    } else {
+                        sessions_reload()
+                    }
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let sessions_delete_others = move |_| {
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.delete_other_sessions().await {
+                Ok(_) => sessions_reload(),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "card-body",
+            h2 { class: "card-title", { translate!(i18, "messages.sessions") } }
+            for session in sessions() {
+                div { class: "flex flex-row items-center gap-3 rounded border p-2 input-bordered",
+                    div { class: "flex grow flex-col overflow-hidden",
+                        span { class: "truncate label-text",
+                            title: session.user_agent.clone(),
+                            { session.user_agent.clone() }
+                        }
+                        span { class: "label-text-alt", { session.ip.clone() } }
+                        span { class: "label-text-alt",
+                            { translate!(i18, "messages.created_at") } ": "
+                            { session.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() }
+                        }
+                        span { class: "label-text-alt",
+                            { translate!(i18, "messages.last_seen") } ": "
+                            { session.last_seen.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() }
+                        }
+                        if session.current {
+                            span { class: "label-text-alt text-success", { translate!(i18, "messages.session_current") } }
+                        }
+                    }
+                    button { class: "btn btn-ghost btn-sm text-error",
+                        disabled: is_busy(),
+                        title: translate!(i18, "messages.session_terminate"),
+                        onclick: move |_| session_delete(session.clone()),
+                        Icon {
+                            width: 18,
+                            height: 18,
+                            fill: "currentColor",
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLogout
+                        }
+                    }
+                }
+            }
+            if sessions().iter().any(|session| !session.current) {
+                div { class: "form-control mt-6",
+                    button { class: "btn btn-outline btn-error",
+                        disabled: is_busy(),
+                        onclick: sessions_delete_others,
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLogout
+                        }
+                        { translate!(i18, "messages.sessions_terminate_others") }
+                    }
+                }
+            }
+        }
+    }
+}
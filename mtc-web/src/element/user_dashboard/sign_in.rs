@@ -3,10 +3,15 @@ use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
 use dioxus_std::translate;
 
+use mtc_model::certificate_model::ClientCertificateModel;
+use mtc_model::identity_model::IdentityProviderModel;
+
+use crate::element::user_dashboard::two_factor::TwoFactorSetup;
+use crate::error::api_error::ApiError;
 use crate::handler::auth_handler::AuthHandler;
 use crate::model::modal_model::ModalModel;
 use crate::service::validator_service::ValidatorService;
-use crate::APP_STATE;
+use crate::{API_URL, APP_STATE};
 use crate::router::Route::HomePage;
 
 #[component]
@@ -14,6 +19,43 @@ pub fn SignIn() -> Element {
     let i18 = use_i18();
 
     let mut is_busy = use_signal(|| false);
+    let mut two_factor = use_signal(String::new);
+    let mut certificate = use_signal(|| None::<ClientCertificateModel>);
+    let mut identity_provider = use_signal(|| None::<IdentityProviderModel>);
+
+    // the button is offered only when the browser has presented a certificate
+    use_hook(move || {
+        spawn(async move {
+            if let Ok(value) = APP_STATE.peek().api.get_certificate().await {
+                certificate.set(value)
+            }
+        });
+    });
+
+    // the identity provider sends the browser back here, the outcome comes along with its name
+    use_hook(move || {
+        spawn(async move {
+            let Ok(Some(value)) = APP_STATE.peek().api.get_identity_provider().await else {
+                return;
+            };
+
+            match value.message.clone() {
+                Some(message)
+                    if message.eq("errors.two_factor_required")
+                        || message.eq("errors.two_factor_enrollment") =>
+                {
+                    two_factor.set(message)
+                }
+                Some(message) => APP_STATE
+                    .peek()
+                    .modal
+                    .signal()
+                    .set(ModalModel::Error(translate!(i18, message.as_str()))),
+                None => (),
+            }
+            identity_provider.set(Some(value))
+        });
+    });
 
     let sign_in_task = move |event: Event<FormData>| {
         is_busy.set(true);
@@ -44,12 +86,151 @@ pub fn SignIn() -> Element {
 
                     navigator().push(HomePage {});
                 },
+                Err(ApiError::ResponseError(message))
+                    if message.eq("errors.two_factor_required")
+                        || message.eq("errors.two_factor_enrollment") =>
+                {
+                    two_factor.set(message)
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let certificate_task = move |_| {
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+
+            match app_state.api.sign_in_certificate().await {
+                Ok(auth_model) => {
+                    app_state.auth.signal().set(auth_model);
+
+                    navigator().push(HomePage {});
+                },
+                Err(ApiError::ResponseError(message))
+                    if message.eq("errors.two_factor_required")
+                        || message.eq("errors.two_factor_enrollment") =>
+                {
+                    two_factor.set(message)
+                }
                 Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
             }
             is_busy.set(false);
         });
     };
 
+    let totp_task = move |event: Event<FormData>| {
+        is_busy.set(true);
+
+        if !event.is_string_valid("code", 6) {
+            APP_STATE
+                .peek()
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            is_busy.set(false);
+            return;
+        }
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+
+            match app_state.api.sign_in_totp(&event.get_string("code")).await {
+                Ok(auth_model) => {
+                    app_state.auth.signal().set(auth_model);
+
+                    navigator().push(HomePage {});
+                },
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    // the enrollment signs the session in, so only the credentials are left to fetch
+    let enrollment_done = move |_: ()| {
+        spawn(async move {
+            let app_state = APP_STATE.read();
+
+            match app_state.api.get_credentials().await {
+                Ok(auth_model) => {
+                    app_state.auth.signal().set(auth_model);
+
+                    navigator().push(HomePage {});
+                },
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    if two_factor().eq("errors.two_factor_enrollment") {
+        return rsx! {
+            div { class: "hero",
+                div { class: "hero-content flex-col lg:flex-row-reverse",
+                    div { class: "text-center lg:text-left w-full sm:max-w-sm",
+                        h1 { class: "text-3xl font-bold", { translate!(i18, "messages.login_now") } }
+                        p { class: "py-6", { translate!(i18, "errors.two_factor_enrollment") } }
+                    }
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        TwoFactorSetup { on_done: enrollment_done }
+                    }
+                }
+            }
+        };
+    }
+
+    if two_factor().eq("errors.two_factor_required") {
+        return rsx! {
+            div { class: "hero",
+                div { class: "hero-content flex-col lg:flex-row-reverse",
+                    div { class: "text-center lg:text-left w-full sm:max-w-sm",
+                        h1 { class: "text-3xl font-bold", { translate!(i18, "messages.login_now") } }
+                        p { class: "py-6", { translate!(i18, "messages.two_factor_announcement") } }
+                    }
+                    div { class: "card w-full max-w-sm shrink-0 border input-bordered rounded",
+                        form { class: "card-body",
+                            autocomplete: "off",
+                            onsubmit: totp_task,
+                            div { class: "form-control",
+                                label { class: "label",
+                                    span { class:"label-text", { translate!(i18, "messages.totp_code") } }
+                                }
+                                input { class: "input input-bordered",
+                                    r#type: "text",
+                                    name: "code",
+                                    minlength: 6,
+                                    maxlength: 20,
+                                    required: true,
+                                }
+                            }
+                            div { class: "form-control mt-6",
+                                if !is_busy() {
+                                    button { class: "btn btn-primary",
+                                        r#type: "submit",
+                                        Icon {
+                                            width: 22,
+                                            height: 22,
+                                            icon: dioxus_free_icons::icons::md_action_icons::MdLogin
+                                        }
+                                        { translate!(i18, "messages.sign_in") }
+                                    }
+                                } else {
+                                    div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
+                                        span { class: "loading loading-spinner loading-md" }
+                                        span { { translate!(i18, "messages.sign_in") } "..." }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
     rsx! {
         div { class: "hero",
             div { class: "hero-content flex-col lg:flex-row-reverse",
@@ -96,6 +277,30 @@ pub fn SignIn() -> Element {
                                     }
                                     { translate!(i18, "messages.sign_in") }
                                 }
+                                if let Some(certificate) = certificate() {
+                                    button { class: "btn btn-outline mt-2",
+                                        r#type: "button",
+                                        title: certificate.subject,
+                                        onclick: certificate_task,
+                                        Icon {
+                                            width: 22,
+                                            height: 22,
+                                            icon: dioxus_free_icons::icons::md_action_icons::MdVerifiedUser
+                                        }
+                                        { translate!(i18, "messages.certificate_sign_in") }
+                                    }
+                                }
+                                if let Some(identity_provider) = identity_provider() {
+                                    a { class: "btn btn-outline mt-2",
+                                        href: [API_URL, "auth", "oidc", "sign_in"].join("/"),
+                                        Icon {
+                                            width: 22,
+                                            height: 22,
+                                            icon: dioxus_free_icons::icons::md_social_icons::MdGroups
+                                        }
+                                        { translate!(i18, "messages.identity_sign_in") } " " { identity_provider.name }
+                                    }
+                                }
                             } else {
                                 div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
                                     span { class: "loading loading-spinner loading-md" }
@@ -0,0 +1,74 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::sign_in_log_model::SignInLogModel;
+
+use crate::handler::auth_handler::AuthHandler;
+use crate::model::modal_model::ModalModel;
+use crate::APP_STATE;
+
+#[component]
+pub fn SignIns() -> Element {
+    let i18 = use_i18();
+
+    let mut sign_ins = use_signal(Vec::<SignInLogModel>::new);
+
+    use_hook(move || {
+        spawn(async move {
+            match APP_STATE.peek().api.get_sign_ins().await {
+                Ok(value) => sign_ins.set(value),
+                Err(e) => APP_STATE
+                    .peek()
+                    .modal
+                    .signal()
+                    .set(ModalModel::Error(e.message())),
+            }
+        });
+    });
+
+    rsx! {
+        div { class: "card-body",
+            h2 { class: "card-title", { translate!(i18, "messages.sign_ins") } }
+            span { class: "label-text-alt", { translate!(i18, "messages.sign_ins_hint") } }
+            for item in sign_ins() {
+                div { class: "flex flex-row items-center gap-3 rounded border p-2 input-bordered",
+                    if item.success {
+                        div { class: "text-success",
+                            Icon {
+                                width: 18,
+                                height: 18,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_action_icons::MdCheckCircle
+                            }
+                        }
+                    } else {
+                        div { class: "text-error",
+                            Icon {
+                                width: 18,
+                                height: 18,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_action_icons::MdHighlightOff
+                            }
+                        }
+                    }
+                    div { class: "flex grow flex-col overflow-hidden",
+                        span { class: "label-text",
+                            { item.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() }
+                        }
+                        span { class: "truncate label-text-alt",
+                            title: item.user_agent.clone(),
+                            { item.user_agent.clone() }
+                        }
+                        span { class: "label-text-alt", { item.ip.clone() } }
+                        if let Some(reason) = item.reason.clone() {
+                            span { class: "label-text-alt text-error", { translate!(i18, reason.as_str()) } }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::totp_model::TotpSetupModel;
+
+use crate::component::loading_box::LoadingBoxComponent;
+use crate::handler::auth_handler::AuthHandler;
+use crate::model::modal_model::ModalModel;
+use crate::service::validator_service::ValidatorService;
+use crate::APP_STATE;
+
+#[component]
+pub fn TwoFactorSetup(on_done: EventHandler<()>) -> Element {
+    let i18 = use_i18();
+
+    let mut is_busy = use_signal(|| true);
+    let mut setup = use_signal(|| None::<TotpSetupModel>);
+    let mut recovery_codes = use_signal(Vec::<String>::new);
+
+    use_hook(|| {
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.setup_totp().await {
+                Ok(value) => setup.set(Some(value)),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    });
+
+    let enable_submit = move |event: Event<FormData>| {
+        is_busy.set(true);
+
+        if !event.is_string_valid("code", 6) {
+            APP_STATE
+                .peek()
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            is_busy.set(false);
+            return;
+        }
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.enable_totp(&event.get_string("code")).await {
+                Ok(value) => recovery_codes.set(value.codes),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    if !recovery_codes().is_empty() {
+        return rsx! {
+            div { class: "card-body",
+                h2 { class: "card-title", { translate!(i18, "messages.recovery_codes") } }
+                p { { translate!(i18, "messages.recovery_codes_announcement") } }
+                div { class: "grid grid-cols-2 gap-2 font-mono",
+                    for code in recovery_codes() {
+                        span { { code } }
+                    }
+                }
+                div { class: "form-control mt-6",
+                    button { class: "btn btn-primary",
+                        onclick: move |_| on_done.call(()),
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            icon: dioxus_free_icons::icons::md_navigation_icons::MdCheck
+                        }
+                        { translate!(i18, "messages.close") }
+                    }
+                }
+            }
+        };
+    }
+
+    let Some(setup_model) = setup() else {
+        return rsx! {
+            div { class: "card-body",
+                LoadingBoxComponent {}
+            }
+        };
+    };
+
+    rsx! {
+        form { class: "card-body",
+            autocomplete: "off",
+            onsubmit: enable_submit,
+            h2 { class: "card-title", { translate!(i18, "messages.two_factor") } }
+            p { { translate!(i18, "messages.totp_setup_announcement") } }
+            img { class: "self-center w-48 h-48",
+                src: ["data:image/png;base64,", &setup_model.qr].concat(),
+            }
+            p { class: "break-all text-center font-mono text-sm", { setup_model.secret.clone() } }
+            div { class: "form-control",
+                label { class: "label",
+                    span { class:"label-text", { translate!(i18, "messages.totp_code") } }
+                }
+                input { class: "input input-bordered",
+                    r#type: "text",
+                    name: "code",
+                    inputmode: "numeric",
+                    minlength: 6,
+                    maxlength: 6,
+                    required: true,
+                }
+            }
+            div { class: "form-control mt-6",
+                if !is_busy() {
+                    button { class: "btn btn-primary",
+                        r#type: "submit",
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLockOutline
+                        }
+                        { translate!(i18, "messages.totp_enable") }
+                    }
+                } else {
+                    div { class: "flex flex-nowrap gap-4 self-center justify center items-center",
+                        span { class: "loading loading-spinner loading-md" }
+                        span { { translate!(i18, "messages.processing") } "..." }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use reqwest::StatusCode;
+
+use mtc_model::audit_model::{AuditFilterModel, AuditModel};
+use mtc_model::sign_in_log_model::{SignInLogFilterModel, SignInLogModel};
+
+use crate::error::api_error::ApiError;
+use crate::handler::{ApiHandler, HandlerResponse};
+use crate::model::response_model::{ApiErrorResponse, ApiResponse};
+
+pub trait AuditHandler {
+    async fn get_audit_list(
+        &self,
+        page: usize,
+        filter: &AuditFilterModel,
+    ) -> Result<ApiResponse<Vec<AuditModel>>, ApiError>;
+    async fn get_audit_export(&self, filter: &AuditFilterModel) -> Result<String, ApiError>;
+    async fn get_sign_in_list(
+        &self,
+        page: usize,
+        filter: &SignInLogFilterModel,
+    ) -> Result<ApiResponse<Vec<SignInLogModel>>, ApiError>;
+}
+
+impl AuditHandler for ApiHandler {
+    async fn get_audit_list(
+        &self,
+        page: usize,
+        filter: &AuditFilterModel,
+    ) -> Result<ApiResponse<Vec<AuditModel>>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "audit", "list", &page.to_string()].join("/"))
+            .query(filter)
+            .send()
+            .await
+            .consume_page()
+            .await
+    }
+
+    async fn get_audit_export(&self, filter: &AuditFilterModel) -> Result<String, ApiError> {
+        let response = self
+            .api_client
+            .get([&self.api_url, "audit", "export"].join("/"))
+            .query(filter)
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::OK {
+            Ok(response.text().await?)
+        } else {
+            Err(ApiError::ResponseError(
+                response
+                    .json::<ApiErrorResponse>()
+                    .await?
+                    .message
+                    .unwrap_or("errors.bad_response".to_string()),
+            ))
+        }
+    }
+
+    async fn get_sign_in_list(
+        &self,
+        page: usize,
+        filter: &SignInLogFilterModel,
+    ) -> Result<ApiResponse<Vec<SignInLogModel>>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "audit", "sign_ins", &page.to_string()].join("/"))
+            .query(filter)
+            .send()
+            .await
+            .consume_page()
+            .await
+    }
+}
@@ -1,4 +1,9 @@
 use mtc_model::auth_model::{AuthModel, SignInModel};
+use mtc_model::certificate_model::ClientCertificateModel;
+use mtc_model::identity_model::{IdentityModel, IdentityProviderModel};
+use mtc_model::session_model::SessionModel;
+use mtc_model::sign_in_log_model::SignInLogModel;
+use mtc_model::totp_model::{TotpCodeModel, TotpRecoveryModel, TotpSetupModel, TotpStatusModel};
 use mtc_model::user_model::UserChangePasswordModel;
 
 use crate::error::api_error::ApiError;
@@ -6,9 +11,24 @@ use crate::handler::{ApiHandler, HandlerNullResponse, HandlerResponse};
 
 pub trait AuthHandler {
     async fn sign_in(&self, login: String, password: String) -> Result<AuthModel, ApiError>;
+    async fn sign_in_certificate(&self) -> Result<AuthModel, ApiError>;
+    async fn get_certificate(&self) -> Result<Option<ClientCertificateModel>, ApiError>;
+    async fn get_identity_provider(&self) -> Result<Option<IdentityProviderModel>, ApiError>;
+    async fn get_identities(&self) -> Result<Vec<IdentityModel>, ApiError>;
+    async fn delete_identity(&self, id: &str) -> Result<(), ApiError>;
     async fn sign_out(&self) -> Result<AuthModel, ApiError>;
     async fn get_credentials(&self) -> Result<AuthModel, ApiError>;
     async fn change_password(&self, old: &str, new: &str) -> Result<(), ApiError>;
+    async fn end_impersonation(&self) -> Result<AuthModel, ApiError>;
+    async fn sign_in_totp(&self, code: &str) -> Result<AuthModel, ApiError>;
+    async fn get_totp_status(&self) -> Result<TotpStatusModel, ApiError>;
+    async fn setup_totp(&self) -> Result<TotpSetupModel, ApiError>;
+    async fn enable_totp(&self, code: &str) -> Result<TotpRecoveryModel, ApiError>;
+    async fn disable_totp(&self, code: &str) -> Result<(), ApiError>;
+    async fn get_sessions(&self) -> Result<Vec<SessionModel>, ApiError>;
+    async fn delete_session(&self, id: &str) -> Result<(), ApiError>;
+    async fn delete_other_sessions(&self) -> Result<(), ApiError>;
+    async fn get_sign_ins(&self) -> Result<Vec<SignInLogModel>, ApiError>;
 }
 
 impl AuthHandler for ApiHandler {
@@ -25,6 +45,51 @@ impl AuthHandler for ApiHandler {
             .await
     }
 
+    async fn sign_in_certificate(&self) -> Result<AuthModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "auth", "certificate"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_certificate(&self) -> Result<Option<ClientCertificateModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "certificate"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_identity_provider(&self) -> Result<Option<IdentityProviderModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "oidc"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_identities(&self) -> Result<Vec<IdentityModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "identities"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn delete_identity(&self, id: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "auth", "identities", id].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
     async fn sign_out(&self) -> Result<AuthModel, ApiError> {
         self.api_client
             .delete([&self.api_url, "auth"].join("/"))
@@ -43,6 +108,15 @@ impl AuthHandler for ApiHandler {
             .await
     }
 
+    async fn end_impersonation(&self) -> Result<AuthModel, ApiError> {
+        self.api_client
+            .delete([&self.api_url, "auth", "impersonate"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
     async fn change_password(&self, old: &str, new: &str) -> Result<(), ApiError> {
         self.api_client
             .post([&self.api_url, "auth", "change"].join("/"))
@@ -55,4 +129,94 @@ impl AuthHandler for ApiHandler {
             .consume()
             .await
     }
+
+    async fn sign_in_totp(&self, code: &str) -> Result<AuthModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "auth", "totp"].join("/"))
+            .json(&TotpCodeModel {
+                code: code.to_string(),
+            })
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_totp_status(&self) -> Result<TotpStatusModel, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "totp"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn setup_totp(&self) -> Result<TotpSetupModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "auth", "totp", "setup"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn enable_totp(&self, code: &str) -> Result<TotpRecoveryModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "auth", "totp", "enable"].join("/"))
+            .json(&TotpCodeModel {
+                code: code.to_string(),
+            })
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn disable_totp(&self, code: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "auth", "totp"].join("/"))
+            .json(&TotpCodeModel {
+                code: code.to_string(),
+            })
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn get_sessions(&self) -> Result<Vec<SessionModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "sessions"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn delete_session(&self, id: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "auth", "sessions", id].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn delete_other_sessions(&self) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "auth", "sessions"].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn get_sign_ins(&self) -> Result<Vec<SignInLogModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "auth", "sign_ins"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
 }
@@ -1,7 +1,8 @@
 use mtc_model::group_model::{
-    GroupCreateModel, GroupModel, GroupUpdateModel
+    GroupCreateModel, GroupModel, GroupParentModel, GroupTreeModel, GroupUpdateModel
 };
-use mtc_model::list_model::RecordListModel;
+use mtc_model::list_model::{RecordListModel, StringListModel};
+use mtc_model::user_model::UserValidityModel;
 use crate::error::api_error::ApiError;
 use crate::handler::{ApiHandler, HandlerNullResponse, HandlerResponse};
 use crate::model::response_model::ApiResponse;
@@ -9,6 +10,7 @@ use crate::model::response_model::ApiResponse;
 pub trait GroupHandler {
     async fn get_group(&self, slug: &str) -> Result<GroupModel, ApiError>;
     async fn get_group_all(&self) -> Result<RecordListModel, ApiError>;
+    async fn get_group_tree(&self) -> Result<Vec<GroupTreeModel>, ApiError>;
     async fn get_group_list(&self, page: usize) -> Result<ApiResponse<Vec<GroupModel>>, ApiError>;
     async fn delete_group(&self, slug: &str) -> Result<(), ApiError>;
     async fn create_group(
@@ -21,6 +23,28 @@ pub trait GroupHandler {
         slug: &str,
         group: &GroupUpdateModel,
     ) -> Result<GroupModel, ApiError>;
+    async fn set_group_validity(
+        &self,
+        slug: &str,
+        validity: &UserValidityModel,
+    ) -> Result<StringListModel, ApiError>;
+    async fn set_group_parent(
+        &self,
+        slug: &str,
+        parent: &GroupParentModel,
+    ) -> Result<GroupModel, ApiError>;
+    async fn get_group_roles(&self, slug: &str) -> Result<StringListModel, ApiError>;
+    async fn get_group_managers(&self, slug: &str) -> Result<StringListModel, ApiError>;
+    async fn set_group_managers(
+        &self,
+        slug: &str,
+        managers: &StringListModel,
+    ) -> Result<StringListModel, ApiError>;
+    async fn set_group_roles(
+        &self,
+        slug: &str,
+        roles: &StringListModel,
+    ) -> Result<StringListModel, ApiError>;
 }
 
 impl GroupHandler for ApiHandler {
@@ -42,6 +66,15 @@ impl GroupHandler for ApiHandler {
             .await
     }
 
+    async fn get_group_tree(&self) -> Result<Vec<GroupTreeModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "group", "tree"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
     async fn get_group_list(&self, page: usize) -> Result<ApiResponse<Vec<GroupModel>>, ApiError> {
         self.api_client
             .get([&self.api_url, "group", "list", &page.to_string()].join("/"))
@@ -87,4 +120,78 @@ impl GroupHandler for ApiHandler {
             .consume_data()
             .await
     }
+
+    async fn set_group_validity(
+        &self,
+        slug: &str,
+        validity: &UserValidityModel,
+    ) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "group", slug, "validity"].join("/"))
+            .json(validity)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn set_group_parent(
+        &self,
+        slug: &str,
+        parent: &GroupParentModel,
+    ) -> Result<GroupModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "group", slug, "parent"].join("/"))
+            .json(parent)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_group_roles(&self, slug: &str) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .get([&self.api_url, "group", slug, "roles"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn set_group_roles(
+        &self,
+        slug: &str,
+        roles: &StringListModel,
+    ) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "group", slug, "roles"].join("/"))
+            .json(roles)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_group_managers(&self, slug: &str) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .get([&self.api_url, "group", slug, "managers"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn set_group_managers(
+        &self,
+        slug: &str,
+        managers: &StringListModel,
+    ) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "group", slug, "managers"].join("/"))
+            .json(managers)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
 }
@@ -1,9 +1,10 @@
 use reqwest::{Error, Response, StatusCode};
 use serde::de::DeserializeOwned;
 
-use crate::API_URL;
+use crate::{API_URL, APP_STATE};
 use crate::error::api_error::ApiError;
 use crate::model::response_model::{ApiErrorResponse, ApiResponse};
+use crate::service::health_service::HealthService;
 
 pub mod health_handler;
 pub mod auth_handler;
@@ -15,6 +16,8 @@ pub mod schema_handler;
 pub mod migration_handler;
 pub mod content_handler;
 pub mod storage_handler;
+pub mod audit_handler;
+pub mod person_handler;
 
 pub struct ApiHandler {
     pub api_url: String,
@@ -32,6 +35,16 @@ impl Default for ApiHandler {
     }
 }
 
+static AUTH_CHANGED_HEADER: &str = "x-auth-changed";
+
+/// The API marks responses with a header once the user's roles or permissions were changed,
+/// so the cached credentials are reloaded.
+fn check_credentials(response: &Response) {
+    if response.headers().contains_key(AUTH_CHANGED_HEADER) {
+        APP_STATE.peek().service.get_credentials()
+    }
+}
+
 pub trait HandlerNullResponse {
     async fn consume(self) -> Result<(), ApiError>;
 }
@@ -40,6 +53,7 @@ impl HandlerNullResponse for Result<Response, Error> {
     async fn consume(self) -> Result<(), ApiError> {
         match self {
             Ok(response) => {
+                check_credentials(&response);
                 if response.status() == StatusCode::OK {
                     Ok(())
                 } else {
@@ -61,6 +75,7 @@ impl<T: DeserializeOwned> HandlerResponse<T> for Result<Response, Error> {
     async fn consume_data(self) -> Result<T, ApiError> {
         match self {
             Ok(response) => {
+                check_credentials(&response);
                 if response.status() == StatusCode::OK {
                     Ok(response.json::<ApiResponse<T>>().await?.data)
                 } else {
@@ -75,6 +90,7 @@ impl<T: DeserializeOwned> HandlerResponse<T> for Result<Response, Error> {
     async fn consume_page(self) -> Result<ApiResponse<T>, ApiError> {
         match self {
             Ok(response) => {
+                check_credentials(&response);
                 if response.status() == StatusCode::OK {
                     Ok(response.json::<ApiResponse<T>>().await?)
                 } else {
@@ -0,0 +1,91 @@
+use reqwest::StatusCode;
+
+use mtc_model::field_model::FieldModel;
+use mtc_model::user_details_model::{
+    PersonExportModel, PersonImportModel, PersonImportResultModel, PersonModel,
+};
+
+use crate::error::api_error::ApiError;
+use crate::handler::{ApiHandler, HandlerResponse};
+use crate::model::response_model::ApiErrorResponse;
+
+pub trait PersonHandler {
+    async fn get_persons(&self) -> Result<Vec<PersonModel>, ApiError>;
+    async fn get_person_profile(&self) -> Result<Vec<FieldModel>, ApiError>;
+    async fn preview_person_import(
+        &self,
+        model: &PersonImportModel,
+    ) -> Result<PersonImportResultModel, ApiError>;
+    async fn import_persons(
+        &self,
+        model: &PersonImportModel,
+    ) -> Result<PersonImportResultModel, ApiError>;
+    async fn get_person_export(&self, model: &PersonExportModel) -> Result<String, ApiError>;
+}
+
+impl PersonHandler for ApiHandler {
+    async fn get_persons(&self) -> Result<Vec<PersonModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "persons"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_person_profile(&self) -> Result<Vec<FieldModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "persons", "profile"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn preview_person_import(
+        &self,
+        model: &PersonImportModel,
+    ) -> Result<PersonImportResultModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "persons", "import", "preview"].join("/"))
+            .json(model)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn import_persons(
+        &self,
+        model: &PersonImportModel,
+    ) -> Result<PersonImportResultModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "persons", "import"].join("/"))
+            .json(model)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_person_export(&self, model: &PersonExportModel) -> Result<String, ApiError> {
+        let response = self
+            .api_client
+            .get([&self.api_url, "persons", "export"].join("/"))
+            .query(model)
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::OK {
+            Ok(response.text().await?)
+        } else {
+            Err(ApiError::ResponseError(
+                response
+                    .json::<ApiErrorResponse>()
+                    .await?
+                    .message
+                    .unwrap_or("errors.bad_response".to_string()),
+            ))
+        }
+    }
+}
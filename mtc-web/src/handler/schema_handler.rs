@@ -1,5 +1,7 @@
 use mtc_model::list_model::RecordListModel;
-use mtc_model::schema_model::{SchemaCreateModel, SchemaModel, SchemaUpdateModel};
+use mtc_model::schema_model::{
+    SchemaCreateModel, SchemaFieldsModel, SchemaMigrationModel, SchemaModel, SchemaUpdateModel,
+};
 
 use crate::error::api_error::ApiError;
 use crate::handler::{ApiHandler, HandlerNullResponse, HandlerResponse};
@@ -21,6 +23,11 @@ pub trait SchemaHandler {
         schema: &SchemaUpdateModel,
     ) -> Result<SchemaModel, ApiError>;
     async fn get_all_collections(&self) -> Result<RecordListModel, ApiError>;
+    async fn get_migration_preview(
+        &self,
+        slug: &str,
+        fields: &SchemaFieldsModel,
+    ) -> Result<SchemaMigrationModel, ApiError>;
 }
 
 impl SchemaHandler for ApiHandler {
@@ -90,4 +97,18 @@ impl SchemaHandler for ApiHandler {
             .consume_data()
             .await
     }
+
+    async fn get_migration_preview(
+        &self,
+        slug: &str,
+        fields: &SchemaFieldsModel,
+    ) -> Result<SchemaMigrationModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "schema", slug, "fields", "preview"].join("/"))
+            .json(fields)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
 }
@@ -1,20 +1,46 @@
+use reqwest::StatusCode;
+
+use mtc_model::auth_model::AuthModel;
+use mtc_model::certificate_model::{CertificateCreateModel, CertificateModel};
+use mtc_model::identity_model::IdentityModel;
 use mtc_model::list_model::StringListModel;
-use mtc_model::user_model::{UserCreateModel, UserModel, UserUpdateModel};
+use mtc_model::sign_in_log_model::SignInLogModel;
+use mtc_model::user_model::{
+    UserCreateModel, UserFilterModel, UserModel, UserProvisionModel, UserUpdateModel,
+};
 
 use crate::error::api_error::ApiError;
 use crate::handler::{ApiHandler, HandlerNullResponse, HandlerResponse};
-use crate::model::response_model::ApiResponse;
+use crate::model::response_model::{ApiErrorResponse, ApiResponse};
 
 pub trait UserHandler {
     async fn get_user(&self, login: &str) -> Result<UserModel, ApiError>;
-    async fn get_user_list(&self, page: usize) -> Result<ApiResponse<Vec<UserModel>>, ApiError>;
+    async fn get_user_list(
+        &self,
+        page: usize,
+        filter: &UserFilterModel,
+    ) -> Result<ApiResponse<Vec<UserModel>>, ApiError>;
     async fn get_user_groups(&self, login: &str) -> Result<StringListModel, ApiError>;
     async fn get_user_roles(&self, login: &str) -> Result<StringListModel, ApiError>;
+    async fn get_user_inherited_roles(&self, login: &str) -> Result<StringListModel, ApiError>;
+    async fn get_user_effective_permissions(&self, login: &str) -> Result<StringListModel, ApiError>;
+    async fn get_user_sign_ins(&self, login: &str) -> Result<Vec<SignInLogModel>, ApiError>;
+    async fn get_user_certificates(&self, login: &str) -> Result<Vec<CertificateModel>, ApiError>;
+    async fn add_user_certificate(&self, login: &str, model: &CertificateCreateModel)
+        -> Result<CertificateModel, ApiError>;
+    async fn delete_user_certificate(&self, login: &str, id: &str) -> Result<(), ApiError>;
+    async fn get_user_identities(&self, login: &str) -> Result<Vec<IdentityModel>, ApiError>;
+    async fn delete_user_identity(&self, login: &str, id: &str) -> Result<(), ApiError>;
     async fn delete_user(&self, login: &str) -> Result<(), ApiError>;
+    async fn reset_user_totp(&self, login: &str) -> Result<(), ApiError>;
+    async fn unlock_user(&self, login: &str) -> Result<(), ApiError>;
+    async fn sign_out_user(&self, login: &str) -> Result<(), ApiError>;
+    async fn impersonate_user(&self, login: &str) -> Result<AuthModel, ApiError>;
     async fn create_user(&self, login: &str, user: &UserCreateModel)
         -> Result<UserModel, ApiError>;
     async fn update_user(&self, login: &str, user: &UserUpdateModel)
         -> Result<UserModel, ApiError>;
+    async fn provision_users(&self, model: &UserProvisionModel) -> Result<String, ApiError>;
 }
 
 impl UserHandler for ApiHandler {
@@ -27,9 +53,14 @@ impl UserHandler for ApiHandler {
             .await
     }
 
-    async fn get_user_list(&self, page: usize) -> Result<ApiResponse<Vec<UserModel>>, ApiError> {
+    async fn get_user_list(
+        &self,
+        page: usize,
+        filter: &UserFilterModel,
+    ) -> Result<ApiResponse<Vec<UserModel>>, ApiError> {
         self.api_client
             .get([&self.api_url, "user", "list", &page.to_string()].join("/"))
+            .query(filter)
             .send()
             .await
             .consume_page()
@@ -54,6 +85,80 @@ impl UserHandler for ApiHandler {
             .await
     }
 
+    async fn get_user_inherited_roles(&self, login: &str) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .get([&self.api_url, "user", login, "roles", "inherited"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_user_effective_permissions(&self, login: &str) -> Result<StringListModel, ApiError> {
+        self.api_client
+            .get([&self.api_url, "user", login, "permissions", "effective"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_user_sign_ins(&self, login: &str) -> Result<Vec<SignInLogModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "user", login, "sign_ins"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn get_user_certificates(&self, login: &str) -> Result<Vec<CertificateModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "user", login, "certificates"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn add_user_certificate(&self, login: &str, model: &CertificateCreateModel)
+        -> Result<CertificateModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "user", login, "certificates"].join("/"))
+            .json(model)
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn delete_user_certificate(&self, login: &str, id: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "user", login, "certificates", id].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn get_user_identities(&self, login: &str) -> Result<Vec<IdentityModel>, ApiError> {
+        self.api_client
+            .get([&self.api_url, "user", login, "identities"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn delete_user_identity(&self, login: &str, id: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "user", login, "identities", id].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
     async fn delete_user(&self, login: &str) -> Result<(), ApiError> {
         self.api_client
             .delete([&self.api_url, "user", login].join("/"))
@@ -63,6 +168,42 @@ impl UserHandler for ApiHandler {
             .await
     }
 
+    async fn reset_user_totp(&self, login: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "user", login, "totp"].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn unlock_user(&self, login: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "user", login, "lockout"].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
+    async fn impersonate_user(&self, login: &str) -> Result<AuthModel, ApiError> {
+        self.api_client
+            .post([&self.api_url, "user", login, "impersonate"].join("/"))
+            .send()
+            .await
+            .consume_data()
+            .await
+    }
+
+    async fn sign_out_user(&self, login: &str) -> Result<(), ApiError> {
+        self.api_client
+            .delete([&self.api_url, "user", login, "sessions"].join("/"))
+            .send()
+            .await
+            .consume()
+            .await
+    }
+
     async fn create_user(
         &self,
         login: &str,
@@ -90,4 +231,25 @@ impl UserHandler for ApiHandler {
             .consume_data()
             .await
     }
+
+    async fn provision_users(&self, model: &UserProvisionModel) -> Result<String, ApiError> {
+        let response = self
+            .api_client
+            .post([&self.api_url, "users", "provision"].join("/"))
+            .json(model)
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::OK {
+            Ok(response.text().await?)
+        } else {
+            Err(ApiError::ResponseError(
+                response
+                    .json::<ApiErrorResponse>()
+                    .await?
+                    .message
+                    .unwrap_or("errors.bad_response".to_string()),
+            ))
+        }
+    }
 }
@@ -0,0 +1,204 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::audit_model::AuditFilterModel;
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::record_model::RecordModel;
+
+use crate::APP_STATE;
+use crate::component::loading_box::LoadingBoxComponent;
+use crate::component::paginator::{PaginatorComponent, PaginatorComponentMode};
+use crate::component::reloading_box::ReloadingBoxComponent;
+use crate::handler::audit_handler::AuditHandler;
+use crate::model::modal_model::ModalModel;
+use crate::page::not_found::NotFoundPage;
+use crate::service::validator_service::ValidatorService;
+
+#[component]
+pub fn AuditPage() -> Element {
+    let app_state = APP_STATE.peek();
+    let auth_state = app_state.auth.read_unchecked();
+    let i18 = use_i18();
+
+    if !auth_state.is_permission("audit::read") {
+        return rsx! { NotFoundPage {} };
+    }
+
+    let mut breadcrumbs = app_state.breadcrumbs.signal();
+    use_effect(move || {
+        breadcrumbs.set(vec![
+            RecordModel { title: translate!(i18, "messages.administrator"), slug: "/administrator".to_string() },
+            RecordModel { title: translate!(i18, "messages.audit"), slug: "/administrator/audit".to_string() },
+        ]);
+    });
+
+    let mut page = use_signal(|| 1usize);
+    let mut filter = use_signal(AuditFilterModel::default);
+
+    let audit_future =
+        use_resource(move || async move { APP_STATE.peek().api.get_audit_list(page(), &filter()).await });
+
+    let apply_filter = move |event: Event<FormData>| {
+        let field = |name: &str| event.get_string_option(name).filter(|value| !value.trim().is_empty());
+
+        filter.set(AuditFilterModel {
+            actor: field("actor"),
+            action: field("action"),
+            target: field("target"),
+            date_from: field("date_from"),
+            date_to: field("date_to"),
+        });
+        page.set(1);
+    };
+
+    let export_audit = move |_| {
+        let audit_download_eval = eval(
+            r#"
+            const file = new Blob([await dioxus.recv()], { type: "text/csv" });
+
+            if( window.showSaveFilePicker ) {
+                let opts = {
+                    types: [{
+                    description: 'CSV',
+                    accept: {'text/csv': ['.csv']},
+                    }],
+                    suggestedName: 'mtc-audit',
+                };
+                var handle = await showSaveFilePicker(opts);
+                var writable = await handle.createWritable();
+                await writable.write(file);
+                writable.close();
+            } else { alert( "File save error" ); }
+        "#,
+        );
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.get_audit_export(&filter()).await {
+                Ok(csv) => audit_download_eval.send(csv.into()).unwrap(),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    rsx! {
+        section { class: "w-full flex-grow p-3",
+            form { class: "flex flex-wrap gap-2 items-end",
+                autocomplete: "off",
+                onsubmit: apply_filter,
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.actor") } }
+                    }
+                    input { r#type: "text", name: "actor",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().actor.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.action") } }
+                    }
+                    input { r#type: "text", name: "action",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().action.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.target") } }
+                    }
+                    input { r#type: "text", name: "target",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().target.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.date_from") } }
+                    }
+                    input { r#type: "date", name: "date_from",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().date_from.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.date_to") } }
+                    }
+                    input { r#type: "date", name: "date_to",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().date_to.unwrap_or_default(),
+                    }
+                }
+                button { class: "btn btn-sm btn-primary",
+                    r#type: "submit",
+                    Icon {
+                        width: 18,
+                        height: 18,
+                        fill: "currentColor",
+                        icon: dioxus_free_icons::icons::md_content_icons::MdFilterList
+                    }
+                    { translate!(i18, "messages.filter") }
+                }
+                button { class: "btn btn-sm btn-neutral",
+                    r#type: "button",
+                    onclick: export_audit,
+                    Icon {
+                        width: 18,
+                        height: 18,
+                        fill: "currentColor",
+                        icon: dioxus_free_icons::icons::md_file_icons::MdFileDownload
+                    }
+                    { translate!(i18, "messages.export") }
+                }
+            }
+            match &*audit_future.read() {
+                Some(Ok(response)) => rsx! {
+                    table { class: "table table-sm w-full",
+                        thead {
+                            tr {
+                                th { { translate!(i18, "messages.created_at") } }
+                                th { { translate!(i18, "messages.actor") } }
+                                th { { translate!(i18, "messages.action") } }
+                                th { { translate!(i18, "messages.target") } }
+                                th { { translate!(i18, "messages.before") } }
+                                th { { translate!(i18, "messages.after") } }
+                                th { { translate!(i18, "messages.ip") } }
+                            }
+                        }
+                        tbody {
+                            for item in response.data.iter() {
+                                tr { class: "hover:bg-base-200",
+                                    td { class: "whitespace-nowrap", { item.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
+                                    td { { item.actor.clone() } }
+                                    td { { item.action.clone() } }
+                                    td { { item.target.clone() } }
+                                    td { class: "font-mono text-xs break-all", { item.before.clone().unwrap_or_default() } }
+                                    td { class: "font-mono text-xs break-all", { item.after.clone().unwrap_or_default() } }
+                                    td { { item.ip.clone() } }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex w-full py-2 justify-center",
+                        PaginatorComponent { mode: PaginatorComponentMode::Full, page, pagination: response.pagination.clone().unwrap_or_default() }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: crate::DIV_CENTER,
+                        ReloadingBoxComponent { message: e.message(), resource: audit_future }
+                    }
+                },
+                None => rsx! {
+                    div { class: crate::DIV_CENTER,
+                        LoadingBoxComponent {}
+                    }
+                },
+            }
+        }
+    }
+}
@@ -114,9 +114,9 @@ pub fn ContentPage(schema_prop: String) -> Element {
 
     let schema_permission = use_memo(move || {
         if schema().is_public {
-            "content::write".to_string()
+            "content".to_string()
         } else {
-            [schema().slug.clone().as_str(), "::write"].concat()
+            schema().slug.clone()
         }
     });
 
@@ -143,6 +143,7 @@ pub fn ContentPage(schema_prop: String) -> Element {
                         title: event.get_string("title"),
                         published: false,
                         fields: None,
+                        groups: None,
                     },
                 )
                 .await
@@ -160,6 +161,7 @@ pub fn ContentPage(schema_prop: String) -> Element {
                         title: event.get_string("title"),
                         fields: None,
                         published: false,
+                        groups: None,
                         created_at: Default::default(),
                         updated_at: Default::default(),
                         created_by: "".to_string(),
@@ -285,7 +287,10 @@ pub fn ContentPage(schema_prop: String) -> Element {
                 }
             }
 
-            if schema_slug().ne("singles") && auth_state.is_permission(&schema_permission()) {
+            if schema_slug().ne("singles")
+                && (auth_state.is_permission(&[&schema_permission(), "::write"].concat())
+                    || auth_state.is_permission(&[&schema_permission(), "::write_own"].concat())
+                    || auth_state.is_permission(&[&schema().slug, "::write_own"].concat())) {
                 button {
                     class: "fixed right-4 bottom-4 btn btn-circle btn-neutral",
                     onclick: move |_| is_new_content.set(true),
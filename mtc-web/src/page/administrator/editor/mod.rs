@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use chrono::Local;
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
@@ -15,14 +17,17 @@ use string_field::StringField;
 use text_field::TextField;
 
 use crate::APP_STATE;
+use crate::component::list_switcher::ListSwitcherComponent;
 use crate::component::loading_box::LoadingBoxComponent;
 use crate::handler::content_handler::ContentHandler;
+use crate::handler::group_handler::GroupHandler;
 use crate::handler::schema_handler::SchemaHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::administrator::storage::StorageManager;
 use crate::page::not_found::NotFoundPage;
 use crate::repository::storage::use_session_storage;
 use crate::service::content_service::ContentService;
+use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
 mod html_field;
@@ -54,6 +59,9 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
     let mut content_slug = use_signal(|| content_prop.clone());
     let storage = use_memo(move || content().id);
     let mut form_published = use_signal(|| false);
+    let mut content_groups = use_signal(BTreeSet::<String>::new);
+    let mut all_groups = use_signal(BTreeSet::<String>::new);
+    let mut groups_title = use_signal(BTreeMap::<String, String>::new);
 
     let mut is_public_storage_shown = use_signal(|| false);
     let mut is_private_storage_shown = use_signal(|| false);
@@ -73,6 +81,8 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
             }
         }));
 
+    let users_details = app_state.users.signal();
+
     let mut breadcrumbs = app_state.breadcrumbs.signal();
     use_effect(move || {
         breadcrumbs.set(
@@ -147,6 +157,35 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                 }
             }
 
+            let mut groups_list = BTreeSet::<String>::new();
+            let groups_content = content()
+                .groups
+                .unwrap_or_default()
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<String>>();
+
+            if let Ok(groups_model) = APP_STATE.peek().api.get_group_all().await {
+                groups_title.set(
+                    groups_model
+                        .list
+                        .iter()
+                        .cloned()
+                        .map(|item| (item.slug, item.title))
+                        .collect::<BTreeMap<String, String>>(),
+                );
+                groups_list = groups_model
+                    .list
+                    .iter()
+                    .cloned()
+                    .map(|item| item.slug)
+                    .filter(|slug| !groups_content.contains(slug))
+                    .collect::<BTreeSet<String>>();
+            }
+
+            all_groups.set(groups_list);
+            content_groups.set(groups_content);
+
             is_busy.set(false);
         });
     });
@@ -159,6 +198,18 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
         }
     });
 
+    // every schema has its own permissions, public ones also honor the content ones
+    let is_own_permission = |action: &str| {
+        auth_state.is_permission(&[&schema_permission(), "::", action].concat())
+            || auth_state.is_permission(&[&schema().slug, "::", action].concat())
+    };
+
+    let is_owner = content().created_by.eq(&auth_state.id);
+    let is_writer = auth_state.is_permission(&[&schema_permission(), "::write"].concat())
+        || (is_owner && is_own_permission("write_own"));
+    let is_deleter = auth_state.is_permission(&[&schema_permission(), "::delete"].concat())
+        || (is_owner && is_own_permission("delete_own"));
+
     let submit_task = move |event: Event<FormData>| {
         if !event.is_title_valid() {
             APP_STATE
@@ -180,6 +231,11 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
             });
         }
 
+        let groups = match content_groups().is_empty() {
+            true => None,
+            false => Some(content_groups().iter().cloned().collect::<Vec<String>>()),
+        };
+
         let submit_form = ApiPostModel {
             title: event.get_string("title"),
             published: event.get_string_option("published").is_some(),
@@ -187,6 +243,7 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                 true => None,
                 false => Some(Value::Object(submit_fields.clone())),
             },
+            groups: groups.clone(),
         };
 
         let t_schema = schema().slug.clone();
@@ -217,6 +274,7 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                             false => Some(Value::Object(submit_fields)),
                         },
                         published: event.get_string_option("published").is_some(),
+                        groups,
                         created_at: content().created_at.clone(),
                         updated_at: content().updated_at.clone(),
                         created_by: content().created_by.clone(),
@@ -318,6 +376,8 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                         }
                     }
                 }
+
+                ListSwitcherComponent { title: translate!(i18, "messages.audience"), items: content_groups, all: all_groups, items_title: groups_title }
             }
 
             aside { class: "flex flex-col gap-3 pt-5 min-w-36",
@@ -332,13 +392,13 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                 }
                 div { class: "flex flex-col gap-1 rounded border p-2 input-bordered label-text",
                     span { class: "italic label-text text-primary", { translate!(i18, "messages.created_at") } ":" }
-                    span { { content.read().created_by.clone() } }
+                    span { { users_details().get_user_title(&content.read().created_by) } }
                     span { class: "label-text-alt", { content().created_at.clone().with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                     span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.updated_at") } ":" }
-                    span { { content.read().updated_by.clone() } }
+                    span { { users_details().get_user_title(&content.read().updated_by) } }
                     span { class: "label-text-alt", { content().updated_at.clone().with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                 }
-                if auth_state.is_permission(&[&schema_permission(), "::write"].concat()) {
+                if is_writer {
                     label { class:
                         if form_published() {
                             "items-center p-3 swap text-success"
@@ -441,7 +501,7 @@ pub fn EditorPage(schema_prop: String, content_prop: String) -> Element {
                         { translate!(i18, "messages.save") }
                     }
                 }
-                if schema().is_collection && auth_state.is_permission(&[&schema_permission(), "::delete"].concat()) {
+                if schema().is_collection && is_deleter {
                     div { class: "divider" }
                     button { class: "btn btn-ghost text-error",
                         onclick: content_delete,
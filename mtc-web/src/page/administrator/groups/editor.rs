@@ -1,18 +1,30 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use chrono::Local;
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
 use dioxus_std::translate;
+use serde_json::{Map, Value};
+use tracing::error;
 
 use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::field_model::FieldModel;
 use mtc_model::group_model::{GroupCreateModel, GroupModel, GroupUpdateModel};
+use mtc_model::list_model::StringListModel;
 use mtc_model::record_model::RecordModel;
+use mtc_model::user_model::{UserProvisionFormatModel, UserProvisionModel, UserValidityModel};
 
 use crate::APP_STATE;
+use crate::component::list_switcher::ListSwitcherComponent;
 use crate::component::loading_box::LoadingBoxComponent;
 use crate::handler::group_handler::GroupHandler;
+use crate::handler::person_handler::PersonHandler;
+use crate::handler::role_handler::RoleHandler;
+use crate::handler::user_handler::UserHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
+use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
 #[component]
@@ -31,6 +43,17 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
     let mut group = use_signal(GroupModel::default);
     let is_new_group = use_memo(move || group_slug().eq("new"));
 
+    let users_details = app_state.users.signal();
+    let mut profile = use_signal(Vec::<FieldModel>::new);
+    let provision_roles = use_signal(BTreeSet::<String>::new);
+    let mut all_roles = use_signal(BTreeSet::<String>::new);
+    let mut roles_title = use_signal(BTreeMap::<String, String>::new);
+    let mut group_roles = use_signal(BTreeSet::<String>::new);
+    let mut group_all_roles = use_signal(BTreeSet::<String>::new);
+    let mut group_managers = use_signal(BTreeSet::<String>::new);
+    let mut group_all_users = use_signal(BTreeSet::<String>::new);
+    let mut users_title = use_signal(BTreeMap::<String, String>::new);
+
     let mut breadcrumbs = app_state.breadcrumbs.signal();
     use_effect(move || {
         breadcrumbs.set(vec![
@@ -68,6 +91,62 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
                 }
             }
 
+            if APP_STATE.peek().auth.read().is_permission("user::write") {
+                if let Ok(roles_model) = APP_STATE.peek().api.get_role_all().await {
+                    let roles_group = APP_STATE
+                        .peek()
+                        .api
+                        .get_group_roles(&group_slug())
+                        .await
+                        .map(|value| value.list.into_iter().collect::<BTreeSet<String>>())
+                        .unwrap_or_default();
+                    group_all_roles.set(
+                        roles_model
+                            .list
+                            .iter()
+                            .map(|item| item.slug.clone())
+                            .filter(|item| !roles_group.contains(item))
+                            .collect(),
+                    );
+                    group_roles.set(roles_group);
+
+                    all_roles.set(roles_model.list.iter().map(|item| item.slug.clone()).collect());
+
+                    let managers = APP_STATE
+                        .peek()
+                        .api
+                        .get_group_managers(&group_slug())
+                        .await
+                        .map(|value| value.list.into_iter().collect::<BTreeSet<String>>())
+                        .unwrap_or_default();
+                    let users = APP_STATE.peek().users.read().clone();
+                    group_all_users.set(
+                        users
+                            .keys()
+                            .filter(|login| !managers.contains(*login))
+                            .cloned()
+                            .collect(),
+                    );
+                    users_title.set(
+                        users
+                            .keys()
+                            .map(|login| (login.clone(), users.get_user_title(login)))
+                            .collect(),
+                    );
+                    group_managers.set(managers);
+                    roles_title.set(
+                        roles_model
+                            .list
+                            .iter()
+                            .map(|item| (item.slug.clone(), item.title.clone()))
+                            .collect(),
+                    );
+                }
+                if let Ok(value) = APP_STATE.peek().api.get_person_profile().await {
+                    profile.set(value);
+                }
+            }
+
             is_busy.set(false);
         });
     });
@@ -105,6 +184,7 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
                             &event.get_string("slug"),
                             &GroupCreateModel {
                                 title: event.get_string("title"),
+                                parent: None,
                             },
                         )
                         .await
@@ -120,6 +200,7 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
                             group().slug
                         },
                         title: event.get_string("title"),
+                        parent: group().parent,
                         created_at: group().created_at,
                         updated_at: group().updated_at,
                         created_by: group().created_by,
@@ -134,6 +215,180 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
         });
     };
 
+    let group_validity_submit = move |event: Event<FormData>| {
+        let app_state = APP_STATE.peek();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state
+                .api
+                .set_group_validity(
+                    &group_slug(),
+                    &UserValidityModel {
+                        valid_from: event.get_datetime_option("valid_from").map(Into::into),
+                        valid_until: event.get_datetime_option("valid_until").map(Into::into),
+                    },
+                )
+                .await
+            {
+                Ok(value) => app_state.modal.signal().set(ModalModel::Success(
+                    [translate!(i18, "messages.validity_applied"), ": ".to_string(), value.list.len().to_string()].concat(),
+                )),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let group_roles_submit = move |_| {
+        let app_state = APP_STATE.peek();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state
+                .api
+                .set_group_roles(
+                    &group_slug(),
+                    &StringListModel { list: group_roles().iter().cloned().collect() },
+                )
+                .await
+            {
+                Ok(_) => app_state.modal.signal().set(ModalModel::Success(translate!(
+                    i18,
+                    "messages.roles_applied"
+                ))),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let group_managers_submit = move |_| {
+        let app_state = APP_STATE.peek();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state
+                .api
+                .set_group_managers(
+                    &group_slug(),
+                    &StringListModel { list: group_managers().iter().cloned().collect() },
+                )
+                .await
+            {
+                Ok(_) => app_state.modal.signal().set(ModalModel::Success(translate!(
+                    i18,
+                    "messages.managers_applied"
+                ))),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let group_provision_submit = move |event: Event<FormData>| {
+        let app_state = APP_STATE.peek();
+
+        // one trainee per line: profile fields in schema order, optionally followed by a login
+        let persons = event
+            .get_string("persons")
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let values = line.split('\t').map(str::trim).collect::<Vec<&str>>();
+                let mut person = profile()
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(field, value)| (field.slug.clone(), Value::String(value.to_string())))
+                    .collect::<Map<String, Value>>();
+                if let Some(login) = values.get(profile().len()).filter(|value| !value.is_empty()) {
+                    person.insert("login".to_string(), Value::String(login.to_string()));
+                }
+                Value::Object(person)
+            })
+            .collect::<Vec<Value>>();
+
+        let format = match event.get_string("format").as_str() {
+            "csv" => UserProvisionFormatModel::Csv,
+            _ => UserProvisionFormatModel::Html,
+        };
+
+        let model = UserProvisionModel {
+            group: group_slug(),
+            roles: provision_roles().iter().cloned().collect(),
+            prefix: event.get_string_option("prefix").filter(|value| !value.trim().is_empty()),
+            count: match persons.is_empty() {
+                true => event
+                    .get_int_option("count")
+                    .and_then(|value| usize::try_from(value).ok()),
+                false => None,
+            },
+            persons: match persons.is_empty() {
+                true => None,
+                false => Some(persons),
+            },
+            valid_from: None,
+            valid_until: None,
+            format,
+        };
+
+        if model.count.is_none() && model.persons.is_none() {
+            app_state
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            return;
+        }
+
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state.api.provision_users(&model).await {
+                Ok(sheet) => {
+                    let sheet_eval = match format {
+                        UserProvisionFormatModel::Csv => eval(
+                            r#"
+                            const file = new Blob([await dioxus.recv()], { type: "text/csv" });
+
+                            if( window.showSaveFilePicker ) {
+                                let opts = {
+                                    types: [{
+                                    description: 'CSV',
+                                    accept: {'text/csv': ['.csv']},
+                                    }],
+                                    suggestedName: 'mtc-credentials',
+                                };
+                                var handle = await showSaveFilePicker(opts);
+                                var writable = await handle.createWritable();
+                                await writable.write(file);
+                                writable.close();
+                            } else { alert( "File save error" ); }
+                        "#,
+                        ),
+                        _ => eval(
+                            r#"
+                            const sheet = window.open('', '_blank');
+                            sheet.document.write(await dioxus.recv());
+                            sheet.document.close();
+                            sheet.focus();
+                            sheet.print();
+                        "#,
+                        ),
+                    };
+                    if let Err(e) = sheet_eval.send(sheet.into()) {
+                        error!("{:#?}", e)
+                    }
+                    app_state.modal.signal().set(ModalModel::Success(translate!(
+                        i18,
+                        "messages.provision_success"
+                    )))
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
     let group_delete = move |_| {
         let app_state = APP_STATE.read();
         is_busy.set(true);
@@ -157,43 +412,183 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
 
     rsx! {
         section { class: "flex grow select-none flex-row gap-6",
-            form { class: "flex grow flex-col items-center gap-3",
-                id: "group-form",
-                autocomplete: "off",
-                onsubmit: group_submit,
-
-                label { class: "w-full form-control",
-                    div { class: "label",
-                        span { class: "label-text text-primary",
-                            { translate!(i18, "messages.slug") }
+            div { class: "flex grow flex-col gap-3",
+                form { class: "flex flex-col items-center gap-3",
+                    id: "group-form",
+                    autocomplete: "off",
+                    onsubmit: group_submit,
+
+                    label { class: "w-full form-control",
+                        div { class: "label",
+                            span { class: "label-text text-primary",
+                                { translate!(i18, "messages.slug") }
+                            }
+                        }
+                        input { r#type: "text", name: "slug",
+                            class: "input input-bordered",
+                            disabled: !is_new_group(),
+                            minlength: 4,
+                            maxlength: 30,
+                            required: true,
+                            pattern: crate::SLUG_PATTERN,
+                            initial_value: group().slug
                         }
+                        span {}
                     }
-                    input { r#type: "text", name: "slug",
-                        class: "input input-bordered",
-                        disabled: !is_new_group(),
-                        minlength: 4,
-                        maxlength: 30,
-                        required: true,
-                        pattern: crate::SLUG_PATTERN,
-                        initial_value: group().slug
+                    label { class: "w-full form-control",
+                        div { class: "label",
+                            span { class: "label-text text-primary",
+                                { translate!(i18, "messages.title") }
+                            }
+                        }
+                        input { r#type: "text", name: "title",
+                            class: "input input-bordered",
+                            minlength: 4,
+                            maxlength: 50,
+                            required: true,
+                            pattern: crate::TITLE_PATTERN,
+                            initial_value: group().title
+                        }
+                        span {}
+                    }
+                }
+                if auth_state.is_permission("user::write") && auth_state.is_permission("group::write") && !is_new_group() {
+                    div { class: "w-full flex flex-col gap-3",
+                        div { class: "divider", { translate!(i18, "messages.roles") } }
+                        span { class: "label-text-alt", { translate!(i18, "messages.roles_group_hint") } }
+                        ListSwitcherComponent { title: translate!(i18, "messages.roles"), items: group_roles, all: group_all_roles, items_title: roles_title }
+                        button { class: "btn btn-outline self-end",
+                            onclick: group_roles_submit,
+                            Icon {
+                                width: 22,
+                                height: 22,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_action_icons::MdAssignmentInd
+                            }
+                            { translate!(i18, "messages.roles_apply") }
+                        }
+                        div { class: "divider", { translate!(i18, "messages.managers") } }
+                        span { class: "label-text-alt", { translate!(i18, "messages.managers_hint") } }
+                        ListSwitcherComponent { title: translate!(i18, "messages.managers"), items: group_managers, all: group_all_users, items_title: users_title }
+                        button { class: "btn btn-outline self-end",
+                            onclick: group_managers_submit,
+                            Icon {
+                                width: 22,
+                                height: 22,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_social_icons::MdSupervisorAccount
+                            }
+                            { translate!(i18, "messages.managers_apply") }
+                        }
                     }
-                    span {}
                 }
-                label { class: "w-full form-control",
-                    div { class: "label",
-                        span { class: "label-text text-primary",
-                            { translate!(i18, "messages.title") }
+                if auth_state.is_permission("user::write") && !is_new_group() {
+                    form { class: "w-full flex flex-col gap-3",
+                        id: "validity-form",
+                        autocomplete: "off",
+                        onsubmit: group_validity_submit,
+                        div { class: "divider", { translate!(i18, "messages.validity") } }
+                        div { class: "flex w-full gap-3",
+                            label { class: "w-full form-control",
+                                div { class: "label",
+                                    span { class: "label-text text-primary",
+                                        { translate!(i18, "messages.valid_from") }
+                                    }
+                                }
+                                input { r#type: "datetime-local", name: "valid_from",
+                                    class: "input input-bordered",
+                                }
+                            }
+                            label { class: "w-full form-control",
+                                div { class: "label",
+                                    span { class: "label-text text-primary",
+                                        { translate!(i18, "messages.valid_until") }
+                                    }
+                                }
+                                input { r#type: "datetime-local", name: "valid_until",
+                                    class: "input input-bordered",
+                                }
+                            }
+                        }
+                        button { class: "btn btn-outline self-end",
+                            r#type: "submit",
+                            Icon {
+                                width: 22,
+                                height: 22,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_action_icons::MdEventAvailable
+                            }
+                            { translate!(i18, "messages.validity_apply") }
                         }
                     }
-                    input { r#type: "text", name: "title",
-                        class: "input input-bordered",
-                        minlength: 4,
-                        maxlength: 50,
-                        required: true,
-                        pattern: crate::TITLE_PATTERN,
-                        initial_value: group().title
+                    form { class: "w-full flex flex-col gap-3",
+                        id: "provision-form",
+                        autocomplete: "off",
+                        onsubmit: group_provision_submit,
+                        div { class: "divider", { translate!(i18, "messages.provision") } }
+                        div { class: "flex w-full gap-3",
+                            label { class: "w-full form-control",
+                                div { class: "label",
+                                    span { class: "label-text text-primary",
+                                        { translate!(i18, "messages.provision_count") }
+                                    }
+                                }
+                                input { r#type: "number", name: "count",
+                                    class: "input input-bordered",
+                                    min: 1,
+                                    max: 500,
+                                }
+                            }
+                            label { class: "w-full form-control",
+                                div { class: "label",
+                                    span { class: "label-text text-primary",
+                                        { translate!(i18, "messages.provision_prefix") }
+                                    }
+                                }
+                                input { r#type: "text", name: "prefix",
+                                    class: "input input-bordered",
+                                    maxlength: 10,
+                                    placeholder: group().slug.to_uppercase()
+                                }
+                            }
+                            label { class: "w-full form-control",
+                                div { class: "label",
+                                    span { class: "label-text text-primary",
+                                        { translate!(i18, "messages.provision_sheet") }
+                                    }
+                                }
+                                select { class: "select select-bordered", name: "format",
+                                    option { value: "html", "HTML" }
+                                    option { value: "csv", "CSV" }
+                                }
+                            }
+                        }
+                        label { class: "w-full form-control",
+                            div { class: "label",
+                                span { class: "label-text text-primary",
+                                    { translate!(i18, "messages.persons") }
+                                }
+                                span { class: "label-text-alt",
+                                    { profile().iter().map(|field| field.title.clone()).chain(std::iter::once(translate!(i18, "messages.login"))).collect::<Vec<String>>().join(" | ") }
+                                }
+                            }
+                            textarea { class: "textarea textarea-bordered h-32 font-mono",
+                                name: "persons",
+                                placeholder: translate!(i18, "messages.provision_announcement"),
+                            }
+                        }
+                        ListSwitcherComponent { title: translate!(i18, "messages.roles"), items: provision_roles, all: all_roles, items_title: roles_title }
+                        button { class: "btn btn-outline self-end",
+                            r#type: "submit",
+                            Icon {
+                                width: 22,
+                                height: 22,
+                                fill: "currentColor",
+                                icon: dioxus_free_icons::icons::md_social_icons::MdGroupAdd
+                            }
+                            { translate!(i18, "messages.provision_create") }
+                        }
                     }
-                    span {}
                 }
             }
 
@@ -209,10 +604,10 @@ pub fn GroupEditorPage(group_prop: String) -> Element {
                 }
                 div { class: "flex flex-col gap-1 rounded border p-2 input-bordered label-text",
                     span { class: "italic label-text text-primary", { translate!(i18, "messages.created_at") } ":" }
-                    span { { group().created_by } }
+                    span { { users_details().get_user_title(&group().created_by) } }
                     span { class: "label-text-alt", { group().created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                     span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.updated_at") } ":" }
-                    span { { group().updated_by } }
+                    span { { users_details().get_user_title(&group().updated_by) } }
                     span { class: "label-text-alt", { group().updated_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                 }
 
@@ -4,14 +4,14 @@ use dioxus_std::i18n::use_i18;
 use dioxus_std::translate;
 
 use mtc_model::auth_model::AuthModelTrait;
-use mtc_model::pagination_model::PaginationModel;
+use mtc_model::group_model::{GroupParentModel, GroupTreeModel};
 use mtc_model::record_model::RecordModel;
 
 use crate::APP_STATE;
 use crate::component::loading_box::LoadingBoxComponent;
-use crate::component::paginator::{PaginatorComponent, PaginatorComponentMode};
 use crate::component::reloading_box::ReloadingBoxComponent;
 use crate::handler::group_handler::GroupHandler;
+use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
 use crate::router::Route::GroupEditorPage;
 
@@ -35,41 +35,58 @@ pub fn GroupsPage() -> Element {
         ]);
     });
 
-    let pagination = use_signal(|| PaginationModel::new(0, 10));
-    let page = use_signal(|| 1usize);
+    let mut dragged = use_signal(|| None::<String>);
 
-    let groups_future =
-        use_resource(move || async move { APP_STATE.peek().api.get_group_list(page()).await });
+    let mut groups_future =
+        use_resource(move || async move { APP_STATE.peek().api.get_group_tree().await });
+
+    let mut group_move = move |(slug, parent): (String, Option<String>)| {
+        dragged.set(None);
+        if parent.as_ref().is_some_and(|parent| parent.eq(&slug)) {
+            return;
+        }
+
+        spawn(async move {
+            let app_state = APP_STATE.peek();
+            match app_state
+                .api
+                .set_group_parent(&slug, &GroupParentModel { parent })
+                .await
+            {
+                Ok(_) => groups_future.restart(),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
 
     rsx! {
         match &*groups_future.read() {
             Some(Ok(response)) => rsx! {
                 section { class: "w-full flex-grow p-3",
-                    table { class: "table w-full",
-                        thead {
-                            tr {
-                                th { { translate!(i18, "messages.slug") } }
-                                th { { translate!(i18, "messages.title") } }
-                            }
-                        }
-                        tbody {
-                            for item in response.data.iter(){
-                                {
-                                    let m_slug = item.slug.clone();
-                                    rsx! {
-                                        tr { class: "cursor-pointer hover:bg-base-200 hover:shadow-md",
-                                            onclick: move |_| { navigator().push(GroupEditorPage{ group_prop: m_slug.clone() }); },
-                                            td { { item.slug.clone() } }
-                                            td { { item.title.clone() } }
-                                        }
-                                    }
+                    if auth_state.is_permission("group::write") {
+                        p { class: "mb-2 text-sm opacity-70", { translate!(i18, "messages.group_tree_hint") } }
+                        div { class: "mb-3 rounded border border-dashed p-3 text-center opacity-70 input-bordered",
+                            prevent_default: "ondragover ondrop",
+                            ondragover: |_| {},
+                            ondrop: move |_| {
+                                if let Some(slug) = dragged() {
+                                    group_move((slug, None));
                                 }
+                            },
+                            { translate!(i18, "messages.group_root") }
+                        }
+                    }
+                    ul { class: "menu w-full",
+                        for item in response.iter() {
+                            GroupTreeComponent {
+                                key: "{item.slug}",
+                                node: item.clone(),
+                                dragged,
+                                is_draggable: auth_state.is_permission("group::write"),
+                                on_move: group_move,
                             }
                         }
                     }
-                    div { class: "flex w-full py-2 justify-center",
-                        PaginatorComponent { mode: PaginatorComponentMode::Full, page, pagination: response.pagination.clone().unwrap_or_default() }
-                    }    
                 }
                 button {
                     class: "fixed right-4 bottom-4 btn btn-circle btn-neutral",
@@ -94,3 +111,49 @@ pub fn GroupsPage() -> Element {
         }
     }
 }
+
+#[component]
+fn GroupTreeComponent(
+    node: GroupTreeModel,
+    dragged: Signal<Option<String>>,
+    is_draggable: bool,
+    on_move: EventHandler<(String, Option<String>)>,
+) -> Element {
+    let mut dragged = dragged;
+    let m_slug = node.slug.clone();
+    let drag_slug = node.slug.clone();
+    let drop_slug = node.slug.clone();
+
+    rsx! {
+        li {
+            a {
+                draggable: is_draggable,
+                prevent_default: "ondragover ondrop",
+                onclick: move |_| { navigator().push(GroupEditorPage{ group_prop: m_slug.clone() }); },
+                ondragstart: move |_| dragged.set(Some(drag_slug.clone())),
+                ondragover: |_| {},
+                ondrop: move |event| {
+                    event.stop_propagation();
+                    if let Some(slug) = dragged() {
+                        on_move.call((slug, Some(drop_slug.clone())));
+                    }
+                },
+                span { { node.title.clone() } }
+                span { class: "opacity-50", { node.slug.clone() } }
+            }
+            if !node.children.is_empty() {
+                ul {
+                    for item in node.children.iter() {
+                        GroupTreeComponent {
+                            key: "{item.slug}",
+                            node: item.clone(),
+                            dragged,
+                            is_draggable,
+                            on_move,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -8,4 +8,6 @@ pub mod schemas;
 pub mod users;
 pub mod storage;
 pub mod migration;
-pub mod permissions;
\ No newline at end of file
+pub mod permissions;
+pub mod audit;
+pub mod sign_ins;
\ No newline at end of file
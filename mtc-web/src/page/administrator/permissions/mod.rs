@@ -14,6 +14,7 @@ use crate::component::reloading_box::ReloadingBoxComponent;
 use crate::handler::permissions_handler::PermissionsHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
+use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
 pub fn PermissionsPage() -> Element {
@@ -28,6 +29,8 @@ pub fn PermissionsPage() -> Element {
         return rsx! { NotFoundPage {} };
     }
 
+    let users_details = app_state.users.signal();
+
     let mut breadcrumbs = app_state.breadcrumbs.signal();
     use_effect(move || {
         breadcrumbs.set(vec![
@@ -104,6 +107,7 @@ pub fn PermissionsPage() -> Element {
                             minlength: 4,
                             maxlength: 30,
                             required: true,
+                            placeholder: "news::read, *::read, !news::delete",
                         }
                     }
                     div { class: "flex p-2 gap-5 flex-inline",
@@ -169,7 +173,7 @@ pub fn PermissionsPage() -> Element {
                                                 }
                                             }
                                             td { { item.slug.clone() } }
-                                            td { { item.created_by.clone() } }
+                                            td { { users_details().get_user_title(&item.created_by) } }
                                             td { { item.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                                         }
                                     }
@@ -1,5 +1,3 @@
-use std::collections::BTreeMap;
-
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
@@ -8,13 +6,18 @@ use serde_json::Value;
 use tracing::error;
 
 use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::field_model::FieldModel;
 use mtc_model::record_model::RecordModel;
-use mtc_model::user_details_model::UserDetailsModel;
+use mtc_model::user_details_model::{
+    PersonExportModel, PersonFormatModel, PersonImportModel, PersonImportResultModel, PersonModel,
+};
 
 use crate::APP_STATE;
+use crate::handler::person_handler::PersonHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
-use crate::service::user_service::UserService;
+use crate::router::Route::SchemaEditorPage;
+use crate::service::user_service::PersonService;
 
 #[component]
 pub fn PersonsPage() -> Element {
@@ -33,19 +36,75 @@ pub fn PersonsPage() -> Element {
         ]);
     });
 
-    let users_to_clipboard = move |_| {
-        let users = APP_STATE.peek().users.signal();
+    let mut is_busy = use_signal(|| false);
+    let mut persons = use_signal(Vec::<PersonModel>::new);
+    let mut profile = use_signal(Vec::<FieldModel>::new);
+    let mut import = use_signal(|| None::<PersonImportModel>);
+    let mut preview = use_signal(|| None::<PersonImportResultModel>);
+    let mut export_format = use_signal(PersonFormatModel::default);
 
-        match eval(r#"
-            let msg = await dioxus.recv();
-            navigator.clipboard.write([new ClipboardItem({'text/plain': new Blob([msg], {type: 'text/plain;charset=utf-8'})})]);
-        "#).send(users().get_users_string()) {
-            Ok(_) => {}
-            Err(e) => error!("{:#?}", e),
-        }
+    let mut persons_reload = move || {
+        spawn(async move {
+            let app_state = APP_STATE.peek();
+
+            if let Ok(value) = app_state.api.get_person_profile().await {
+                profile.set(value);
+            }
+            match app_state.api.get_persons().await {
+                Ok(value) => persons.set(value),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    use_hook(persons_reload);
+
+    let mut import_preview = move |model: PersonImportModel| {
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.preview_person_import(&model).await {
+                Ok(value) => {
+                    preview.set(Some(value));
+                    import.set(Some(model));
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let import_confirm = move |_| {
+        let Some(model) = import() else {
+            return;
+        };
+        is_busy.set(true);
+
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state.api.import_persons(&model).await {
+                Ok(value) => {
+                    app_state.modal.signal().set(ModalModel::Success(
+                        [&translate!(i18, "messages.persons_imported"), ": ", &value.updated.len().to_string()].concat(),
+                    ));
+                    app_state.service.get_persons();
+                    persons_reload();
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            import.set(None);
+            preview.set(None);
+            is_busy.set(false);
+        });
+    };
+
+    let import_cancel = move |_| {
+        import.set(None);
+        preview.set(None);
     };
 
-    let users_from_clipboard = move |_| {
+    let persons_from_clipboard = move |_| {
         let clipboard_read_eval = eval(
             r#"
             navigator.clipboard.readText().then((clipText) => (dioxus.send(clipText)));
@@ -55,14 +114,10 @@ pub fn PersonsPage() -> Element {
         spawn(async move {
             to_owned![clipboard_read_eval];
             match clipboard_read_eval.recv().await {
-                Ok(Value::String(value)) => {
-                    let users = APP_STATE.peek().users.signal();
-                    APP_STATE
-                        .peek()
-                        .users
-                        .signal()
-                        .set(users().import_str(value.as_str()))
-                }
+                Ok(Value::String(value)) => import_preview(PersonImportModel {
+                    format: PersonFormatModel::Tsv,
+                    data: value,
+                }),
                 _ => APP_STATE
                     .peek()
                     .modal
@@ -72,24 +127,43 @@ pub fn PersonsPage() -> Element {
         });
     };
 
-    let download_user_set = move |_| {
-        let users_download_eval = eval(
+    let persons_upload = move |event: Event<FormData>| async move {
+        let Some(file_engine) = event.files() else {
+            return;
+        };
+
+        if let Some(file_name) = file_engine.files().first() {
+            let format = match file_name.to_lowercase().rsplit('.').next() {
+                Some("json") => PersonFormatModel::Json,
+                Some("csv") => PersonFormatModel::Csv,
+                _ => PersonFormatModel::Tsv,
+            };
+
+            if let Some(data) = file_engine.read_file_to_string(file_name).await {
+                import_preview(PersonImportModel { format, data });
+            }
+        }
+    };
+
+    let persons_download = move |_| {
+        let (content_type, extension) = match export_format() {
+            PersonFormatModel::Tsv => ("text/tab-separated-values", "tsv"),
+            PersonFormatModel::Csv => ("text/csv", "csv"),
+            PersonFormatModel::Json => ("application/json", "json"),
+        };
+
+        let persons_download_eval = eval(
             r#"
-            const toObject = (map = new Map()) =>
-                Object.fromEntries(Array.from(map.entries(), ([ k, v ]) =>
-                    v instanceof Map ? [ k, toObject (v) ] : [ k, v ]
-                )
-            )
-            const obj = JSON.stringify(toObject(await dioxus.recv()));
-            const file = new Blob([obj], { type: "application/json" });
+            const [data, type, extension] = await dioxus.recv();
+            const file = new Blob([data], { type: type });
 
             if( window.showSaveFilePicker ) {
                 let opts = {
                     types: [{
-                    description: 'JSON',
-                    accept: {'application/json': ['.json']},
+                    description: extension.toUpperCase(),
+                    accept: {[type]: ['.' + extension]},
                     }],
-                    suggestedName: 'mtc-users',
+                    suggestedName: 'mtc-persons',
                 };
                 var handle = await showSaveFilePicker(opts);
                 var writable = await handle.createWritable();
@@ -99,90 +173,95 @@ pub fn PersonsPage() -> Element {
         "#,
         );
 
-        let users = APP_STATE.peek().users.signal();
-        if users().is_empty() {
-            return;
-        }
-        users_download_eval.send(users().get_users_json()).unwrap()
-    };
-
-    let upload_user_set = move |event: Event<FormData>| async move {
-        let users = APP_STATE.peek().users.signal();
-        let mut user_details = users.peek().clone();
-
-        if let Some(file_engine) = event.files() {
-            let files = file_engine.files();
-            for file_name in &files {
-                if let Some(file) = file_engine.read_file_to_string(file_name).await {
-                    user_details.import_json(file.as_str());
+        spawn(async move {
+            let app_state = APP_STATE.read();
+            match app_state
+                .api
+                .get_person_export(&PersonExportModel { format: export_format() })
+                .await
+            {
+                Ok(data) => {
+                    if let Err(e) = persons_download_eval.send(Value::Array(vec![
+                        data.into(),
+                        content_type.into(),
+                        extension.into(),
+                    ])) {
+                        error!("{:#?}", e)
+                    }
                 }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
             }
-            APP_STATE.peek().users.signal().set(user_details)
-        }
+        });
     };
 
-    let users = APP_STATE.peek().users.signal();
-
-    let remove_user = move |user: String| {
-        APP_STATE
-            .peek()
-            .users
-            .signal()
-            .set(users().remove_user(&user));
+    let person_field = |person: &PersonModel, slug: &str| {
+        person
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get(slug))
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_string()
     };
 
     rsx! {
         section { class: "flex grow select-none flex-row gap-6",
             div { class: "flex grow flex-col items-center gap-3",
-                div { class: "flex w-full flex-wrap z-[10]",
-                    div { class: "join",
-                        div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.clipboard_paste"),
-                            button {
-                                class: "join-item btn text-accent",
-                                onclick: users_from_clipboard,
-                                Icon {
-                                    width: 22,
-                                    height: 22,
-                                    fill: "currentColor",
-                                    icon: dioxus_free_icons::icons::fa_regular_icons::FaPaste
+                div { class: "flex w-full flex-wrap gap-2 z-[10]",
+                    if auth_state.is_permission("user::write") {
+                        div { class: "join",
+                            div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.clipboard_paste"),
+                                button {
+                                    class: "join-item btn text-accent",
+                                    disabled: is_busy(),
+                                    onclick: persons_from_clipboard,
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        fill: "currentColor",
+                                        icon: dioxus_free_icons::icons::fa_regular_icons::FaPaste
+                                    }
                                 }
                             }
-                        }
-                        div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.clipboard_copy"),
-                            button {
-                                class: "join-item btn",
-                                onclick: users_to_clipboard,
-                                Icon {
-                                    width: 22,
-                                    height: 22,
-                                    fill: "currentColor",
-                                    icon: dioxus_free_icons::icons::fa_regular_icons::FaCopy
-                                }
+                            input { class: "hidden",
+                                id: "persons-upload",
+                                r#type: "file",
+                                accept: ".tsv,.csv,.json,.txt",
+                                multiple: false,
+                                onchange: persons_upload
                             }
-                        }
-                        input { class: "hidden",
-                            id: "users-upload",
-                            r#type: "file",
-                            accept: ".json",
-                            multiple: true,
-                            onchange: upload_user_set
-                        }
-                        div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.upload"),
-                            button {
-                                class: "join-item btn text-accent",
-                                "onclick": "document.getElementById('users-upload').click()",
-                                Icon {
-                                    width: 22,
-                                    height: 22,
-                                    fill: "currentColor",
-                                    icon: dioxus_free_icons::icons::md_file_icons::MdFileUpload
+                            div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.upload"),
+                                button {
+                                    class: "join-item btn text-accent",
+                                    disabled: is_busy(),
+                                    "onclick": "document.getElementById('persons-upload').click()",
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        fill: "currentColor",
+                                        icon: dioxus_free_icons::icons::md_file_icons::MdFileUpload
+                                    }
                                 }
                             }
                         }
+                    }
+                    div { class: "join",
+                        select { class: "join-item select select-bordered",
+                            onchange: move |event| {
+                                export_format.set(match event.value().as_str() {
+                                    "csv" => PersonFormatModel::Csv,
+                                    "json" => PersonFormatModel::Json,
+                                    _ => PersonFormatModel::Tsv,
+                                })
+                            },
+                            option { value: "tsv", selected: export_format() == PersonFormatModel::Tsv, "TSV" }
+                            option { value: "csv", selected: export_format() == PersonFormatModel::Csv, "CSV" }
+                            option { value: "json", selected: export_format() == PersonFormatModel::Json, "JSON" }
+                        }
                         div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.download"),
                             button {
                                 class: "join-item btn",
-                                onclick: download_user_set,
+                                onclick: persons_download,
                                 Icon {
                                     width: 22,
                                     height: 22,
@@ -191,57 +270,72 @@ pub fn PersonsPage() -> Element {
                                 }
                             }
                         }
-                        div { class: "tooltip tooltip-bottom", "data-tip": translate!(i18, "messages.clear"),
-                            button {
-                                class: "join-item btn text-error",
-                                onclick: move |_| APP_STATE.peek().users.signal().set(BTreeMap::<String, UserDetailsModel>::new()),
+                    }
+                }
+                if let Some(result) = preview() {
+                    div { class: "flex w-full flex-col gap-2 rounded border p-3 input-bordered",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.persons_import_preview") } }
+                        span { { translate!(i18, "messages.persons_updated") } ": " { result.updated.len().to_string() } }
+                        if !result.unknown.is_empty() {
+                            span { class: "text-warning",
+                                { translate!(i18, "messages.persons_unknown") } ": " { result.unknown.join(", ") }
+                            }
+                        }
+                        for item in result.invalid.iter() {
+                            span { class: "text-error", { translate!(i18, "messages.persons_invalid") } ": " { item.clone() } }
+                        }
+                        div { class: "flex gap-2",
+                            button { class: "btn btn-primary btn-sm",
+                                disabled: is_busy() || result.updated.is_empty(),
+                                onclick: import_confirm,
                                 Icon {
-                                    width: 22,
-                                    height: 22,
-                                    fill: "currentColor",
-                                    icon: dioxus_free_icons::icons::md_content_icons::MdClear
+                                    width: 18,
+                                    height: 18,
+                                    icon: dioxus_free_icons::icons::md_navigation_icons::MdCheck
                                 }
-                            }    
+                                { translate!(i18, "messages.persons_import") }
+                            }
+                            button { class: "btn btn-ghost btn-sm",
+                                onclick: import_cancel,
+                                { translate!(i18, "messages.cancel") }
+                            }
                         }
                     }
                 }
                 table { class: "table table-xs table-pin-rows w-full",
                     thead {
                         tr {
-                            th { class: "w-6" }
                             th { { translate!(i18, "messages.login") } }
-                            th { { translate!(i18, "messages.rank") } }
-                            th { { translate!(i18, "messages.name") } }
+                            for field in profile() {
+                                th { { field.title.clone() } }
+                            }
                         }
                     }
                     tbody {
-                        for item in users(){
-                            tr { class: "cursor-pointer hover:bg-base-200 hover:shadow-md",
-                                td {
-                                    button { class: "btn btn-xs btn-ghost text-error",
-                                        onclick: move |_| remove_user(item.0.clone()),
-                                        Icon {
-                                            width: 16,
-                                            height: 16,
-                                            fill: "currentColor",
-                                            icon: dioxus_free_icons::icons::md_navigation_icons::MdClose
-                                        }
-                                    }
+                        for person in persons() {
+                            tr { class: "hover:bg-base-200 hover:shadow-md",
+                                td { { person.login.clone() } }
+                                for field in profile() {
+                                    td { { person_field(&person, &field.slug) } }
                                 }
-                                td { { item.0.clone() } }
-                                td { { item.1.rank.clone() } }
-                                td { { item.1.name.clone() } }
                             }
                         }
                     }
                 }
             }
-            if auth_state.is_permission("administrator") {
+            if auth_state.is_permission("schema::read") {
                 aside { class: "flex flex-col gap-3",
-//todo administrator actions
+                    button { class: "btn btn-ghost",
+                        onclick: move |_| { navigator().push(SchemaEditorPage { schema_prop: "users".to_string() }); },
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            icon: dioxus_free_icons::icons::md_action_icons::MdSettings
+                        }
+                        { translate!(i18, "messages.persons_profile") }
+                    }
                 }
             }
         }
-    }    
+    }
 }
-
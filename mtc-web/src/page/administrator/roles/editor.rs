@@ -17,6 +17,7 @@ use crate::handler::permissions_handler::PermissionsHandler;
 use crate::handler::role_handler::RoleHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
+use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
 #[component]
@@ -36,11 +37,14 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
     let is_new_role = use_memo(move || role_slug().eq("new"));
 
     let mut user_access_all = use_signal(|| false);
+    let mut two_factor = use_signal(|| false);
     let mut role_permissions = use_signal(BTreeSet::<String>::new);
     let mut all_permissions = use_signal(BTreeSet::<String>::new);
 
     let dummy_permissions_title = use_signal(BTreeMap::<String, String>::new);
 
+    let users_details = app_state.users.signal();
+
     let mut breadcrumbs = app_state.breadcrumbs.signal();
     use_effect(move || {
         breadcrumbs.set(vec![
@@ -77,6 +81,7 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                 match APP_STATE.peek().api.get_role(&role_slug()).await {
                     Ok(value) => {
                         user_access_all.set(value.user_access_all);
+                        two_factor.set(value.two_factor);
                         role.set(value)
                     }
                     Err(e) => {
@@ -144,6 +149,7 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                                     .get_int_option("user_access_level")
                                     .unwrap_or(999),
                                 user_access_all: user_access_all(),
+                                two_factor: two_factor(),
                                 permissions: permissions.clone(),
                             },
                         )
@@ -160,6 +166,7 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                                     .get_int_option("user_access_level")
                                     .unwrap_or(999),
                                 user_access_all: user_access_all(),
+                                two_factor: two_factor(),
                                 permissions: permissions.clone(),
                             },
                         )
@@ -178,6 +185,7 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                         title: event.get_string("title"),
                         user_access_level: event.get_int_option("user_access_level").unwrap_or(999),
                         user_access_all: user_access_all(),
+                        two_factor: two_factor(),
                         permissions,
                         created_at: role().created_at,
                         updated_at: role().updated_at,
@@ -304,6 +312,41 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                             }
                         }
                     }
+                    label { class: "w-fit form-control",
+                        div { class: "label",
+                            span { class: "label-text text-primary", { translate!(i18, "messages.two_factor") } }
+                        }
+                        label { class: "w-fit rounded border px-3 py-2 swap text-warning input-bordered",
+                            input {
+                                r#type: "checkbox",
+                                name: "two_factor",
+                                checked: two_factor(),
+                                onchange: move |event| two_factor.set(event.checked())
+                            }
+                            div { class: "swap-on",
+                                span { class: "inline-flex flex-nowrap gap-3 items-center",
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        fill: "currentColor",
+                                        icon: dioxus_free_icons::icons::md_action_icons::MdVerifiedUser
+                                    }
+                                    { translate!(i18, "messages.yes") }
+                                }
+                            }
+                            div { class: "swap-off",
+                                span { class: "inline-flex flex-nowrap gap-3 items-center",
+                                    Icon {
+                                        width: 22,
+                                        height: 22,
+                                        fill: "currentColor",
+                                        icon: dioxus_free_icons::icons::md_action_icons::MdLockOpen
+                                    }
+                                    { translate!(i18, "messages.no") }
+                                }
+                            }
+                        }
+                    }
                 }
                 ListSwitcherComponent {
                     title: translate!(i18, "messages.permissions"),
@@ -325,10 +368,10 @@ pub fn RoleEditorPage(role_prop: String) -> Element {
                 }
                 div { class: "flex flex-col gap-1 rounded border p-2 input-bordered label-text",
                     span { class: "italic label-text text-primary", { translate!(i18, "messages.created_at") } ":" }
-                    span { { role().created_by } }
+                    span { { users_details().get_user_title(&role().created_by) } }
                     span { class: "label-text-alt", { role().created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                     span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.updated_at") } ":" }
-                    span { { role().updated_by } }
+                    span { { users_details().get_user_title(&role().updated_by) } }
                     span { class: "label-text-alt", { role().updated_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                 }
 
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 
 use chrono::Local;
@@ -10,13 +10,19 @@ use dioxus_std::translate;
 use mtc_model::auth_model::AuthModelTrait;
 use mtc_model::field_model::{FieldModel, FieldTypeModel};
 use mtc_model::record_model::RecordModel;
-use mtc_model::schema_model::{SchemaCreateModel, SchemaModel, SchemaUpdateModel};
+use mtc_model::schema_model::{
+    FieldChangeModel, SchemaCreateModel, SchemaFieldsModel, SchemaMigrationModel, SchemaModel,
+    SchemaUpdateModel,
+};
 
 use crate::APP_STATE;
+use crate::component::list_switcher::ListSwitcherComponent;
 use crate::component::loading_box::LoadingBoxComponent;
+use crate::handler::group_handler::GroupHandler;
 use crate::handler::schema_handler::SchemaHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
+use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
 #[component]
@@ -39,6 +45,12 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
     let is_new_schema = use_memo(move || schema_slug().eq("new"));
 
     let mut fields = use_signal(BTreeMap::<usize, FieldModel>::new);
+    let mut migration = use_signal(|| None::<SchemaMigrationModel>);
+    let mut schema_groups = use_signal(BTreeSet::<String>::new);
+    let mut all_groups = use_signal(BTreeSet::<String>::new);
+    let mut groups_title = use_signal(BTreeMap::<String, String>::new);
+
+    let users_details = app_state.users.signal();
 
     let mut breadcrumbs = app_state.breadcrumbs.signal();
     use_effect(move || {
@@ -59,28 +71,55 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
     });
 
     use_effect(move || {
-        if is_new_schema() {
-            is_busy.set(false);
-            return;
-        }
-
         spawn(async move {
-            match APP_STATE.peek().api.get_schema(&schema_slug()).await {
-                Ok(value) => {
-                    form_is_collection.set(value.is_collection);
-                    form_is_public.set(value.is_public);
+            if !is_new_schema() {
+                match APP_STATE.peek().api.get_schema(&schema_slug()).await {
+                    Ok(value) => {
+                        form_is_collection.set(value.is_collection);
+                        form_is_public.set(value.is_public);
 
-                    schema.set(value)
-                }
-                Err(e) => {
-                    APP_STATE
-                        .peek()
-                        .modal
-                        .signal()
-                        .set(ModalModel::Error(e.message()));
-                    navigator().go_back()
+                        schema.set(value)
+                    }
+                    Err(e) => {
+                        APP_STATE
+                            .peek()
+                            .modal
+                            .signal()
+                            .set(ModalModel::Error(e.message()));
+                        navigator().go_back()
+                    }
                 }
             }
+
+            let mut groups_list = BTreeSet::<String>::new();
+            let groups_schema = schema()
+                .groups
+                .unwrap_or_default()
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<String>>();
+
+            if let Ok(groups_model) = APP_STATE.peek().api.get_group_all().await {
+                groups_title.set(
+                    groups_model
+                        .list
+                        .iter()
+                        .cloned()
+                        .map(|item| (item.slug, item.title))
+                        .collect::<BTreeMap<String, String>>(),
+                );
+                groups_list = groups_model
+                    .list
+                    .iter()
+                    .cloned()
+                    .map(|item| item.slug)
+                    .filter(|slug| !groups_schema.contains(slug))
+                    .collect::<BTreeSet<String>>();
+            }
+
+            all_groups.set(groups_list);
+            schema_groups.set(groups_schema);
+
             is_busy.set(false)
         });
     });
@@ -90,7 +129,11 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
             Some(items) => {
                 let mut result = BTreeMap::<usize, FieldModel>::new();
                 for (count, item) in items.iter().enumerate() {
-                    result.insert(count, item.clone());
+                    let mut field = item.clone();
+                    if field.id.is_empty() {
+                        field.id = field.slug.clone();
+                    }
+                    result.insert(count, field);
                 }
                 result
             }
@@ -101,6 +144,21 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
 
     let mut field_remove = move |item: &usize| {
         fields.try_write().unwrap().remove(item);
+        migration.set(None);
+    };
+
+    let mut field_slug = move |item: &usize, slug: String| {
+        if let Some(field) = fields.try_write().unwrap().get_mut(item) {
+            field.slug = slug;
+        }
+        migration.set(None);
+    };
+
+    let mut field_type = move |item: &usize, field_type: String| {
+        if let Some(field) = fields.try_write().unwrap().get_mut(item) {
+            field.field_type = FieldTypeModel::from_str(&field_type).unwrap();
+        }
+        migration.set(None);
     };
 
     //todo check for slug duplicates
@@ -115,6 +173,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
         };
 
         let new_field = FieldModel {
+            id: event.get_string("slug"),
             slug: event.get_string("slug"),
             title: event.get_string("title"),
             field_type: FieldTypeModel::from_str(event.get_string("field_type").as_str()).unwrap(),
@@ -126,6 +185,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
         };
 
         fields.try_write().unwrap().insert(id + 1, new_field);
+        migration.set(None);
     };
 
     let schema_submit = move |event: Event<FormData>| {
@@ -148,6 +208,36 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                 true => None,
                 false => Some(fields().values().cloned().collect::<Vec<FieldModel>>()),
             };
+            let groups = match schema_groups().is_empty() {
+                true => None,
+                false => Some(schema_groups().iter().cloned().collect::<Vec<String>>()),
+            };
+
+            if !is_new_schema() && migration().is_none() {
+                match app_state
+                    .api
+                    .get_migration_preview(
+                        &schema_slug(),
+                        &SchemaFieldsModel {
+                            fields: field_set.clone(),
+                            force: None,
+                        },
+                    )
+                    .await
+                {
+                    Ok(value) if !value.changes.is_empty() => {
+                        migration.set(Some(value));
+                        is_busy.set(false);
+                        return;
+                    }
+                    Ok(_) => (),
+                    Err(e) => {
+                        app_state.modal.signal().set(ModalModel::Error(e.message()));
+                        is_busy.set(false);
+                        return;
+                    }
+                }
+            }
 
             match match is_new_schema() {
                 false => {
@@ -157,7 +247,12 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                             &schema_slug(),
                             &SchemaUpdateModel {
                                 title: event.get_string("title"),
+                                is_collection: Some(is_collection),
+                                is_public: Some(is_public),
                                 fields: field_set.clone(),
+                                groups: groups.clone(),
+                                // the migration preview with its errors has been confirmed
+                                force: migration().map(|_| true),
                             },
                         )
                         .await
@@ -170,6 +265,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                             &SchemaCreateModel {
                                 title: event.get_string("title"),
                                 fields: field_set.clone(),
+                                groups: groups.clone(),
                                 is_collection,
                                 is_public,
                             },
@@ -191,6 +287,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                         is_collection,
                         is_public,
                         fields: field_set,
+                        groups,
                         created_at: schema().created_at,
                         updated_at: schema().updated_at,
                         created_by: schema().created_by,
@@ -233,7 +330,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                     id: "schema-form",
                     autocomplete: "off",
                     onsubmit: schema_submit,
-                    if is_new_schema() {
+                    if !schema().is_system {
                         div { class: "inline-flex gap-5",
                             label { class: "w-fit form-control",
                                 div { class: "label",
@@ -340,6 +437,7 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                         }
                         span {}
                     }
+                    ListSwitcherComponent { title: translate!(i18, "messages.audience"), items: schema_groups, all: all_groups, items_title: groups_title }
                 }
 
                 form { class: "mt-1 w-full",
@@ -376,8 +474,29 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                                             }    
                                         }
                                     }
-                                    td { { translate!(i18, ["fields.", field.field_type.to_string().as_str()].concat().as_str()) } }
-                                    td { { field.slug.clone() } }
+                                    if auth_state.is_permission("schema::write") {
+                                        td {
+                                            select { class: "select select-sm select-bordered",
+                                                onchange: move |event| field_type(&id, event.value()),
+                                                option { value: "str", selected: field.field_type == FieldTypeModel::Str, { translate!(i18, "fields.str") } }
+                                                option { value: "text", selected: field.field_type == FieldTypeModel::Text, { translate!(i18, "fields.text") } }
+                                                option { value: "html", selected: field.field_type == FieldTypeModel::Html, { translate!(i18, "fields.html") } }
+                                            }
+                                        }
+                                        td {
+                                            input { r#type: "text",
+                                                class: "input input-sm input-bordered",
+                                                minlength: 4,
+                                                maxlength: 30,
+                                                pattern: crate::SLUG_PATTERN,
+                                                initial_value: field.slug.clone(),
+                                                onchange: move |event| field_slug(&id, event.value())
+                                            }
+                                        }
+                                    } else {
+                                        td { { translate!(i18, ["fields.", field.field_type.to_string().as_str()].concat().as_str()) } }
+                                        td { { field.slug.clone() } }
+                                    }
                                     td { { field.title.clone() } }
                                 }
                             }
@@ -422,6 +541,49 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                         }
                     }
                 }
+
+                if let Some(preview) = migration() {
+                    div { class: "w-full rounded border p-3 border-warning",
+                        div { class: "label",
+                            span { class: "label-text text-warning", "⌘ " { translate!(i18, "messages.fields_migration") } }
+                        }
+                        ul { class: "list-disc pl-6",
+                            for change in preview.changes.iter() {
+                                li {
+                                    {
+                                        match change {
+                                            FieldChangeModel::Rename { from, to } =>
+                                                [translate!(i18, "messages.field_rename").as_str(), ": ", from, " → ", to].concat(),
+                                            FieldChangeModel::Retype { slug, from, to } =>
+                                                [translate!(i18, "messages.field_retype").as_str(), ": ", slug, " (", &from.to_string(), " → ", &to.to_string(), ")"].concat(),
+                                            FieldChangeModel::Remove { slug } =>
+                                                [translate!(i18, "messages.field_remove").as_str(), ": ", slug].concat(),
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        table { class: "table table-sm w-full",
+                            thead {
+                                tr {
+                                    th { { translate!(i18, "messages.slug") } }
+                                    th { { translate!(i18, "messages.title") } }
+                                    th { { translate!(i18, "messages.conversion_errors") } }
+                                }
+                            }
+                            tbody {
+                                for item in preview.items.iter() {
+                                    tr {
+                                        td { { item.slug.clone() } }
+                                        td { { item.title.clone() } }
+                                        td { class: "text-error", { item.errors.join(", ") } }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "label-text-alt", { translate!(i18, "messages.fields_migration_confirm") } }
+                    }
+                }
             }
 
             aside { class: "flex flex-col gap-3 pt-5 min-w-36",
@@ -436,10 +598,10 @@ pub fn SchemaEditorPage(schema_prop: String) -> Element {
                 }
                 div { class: "flex flex-col gap-1 rounded border p-2 input-bordered label-text",
                     span { class: "italic label-text text-primary", { translate!(i18, "messages.created_at") } ":" }
-                    span { { schema().created_by } }
+                    span { { users_details().get_user_title(&schema().created_by) } }
                     span { class: "label-text-alt", { schema().created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                     span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.updated_at") } ":" }
-                    span { { schema().updated_by } }
+                    span { { users_details().get_user_title(&schema().updated_by) } }
                     span { class: "label-text-alt", { schema().updated_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                 }
 
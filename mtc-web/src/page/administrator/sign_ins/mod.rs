@@ -0,0 +1,157 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_free_icons::Icon;
+use dioxus_std::i18n::use_i18;
+use dioxus_std::translate;
+
+use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::record_model::RecordModel;
+use mtc_model::sign_in_log_model::SignInLogFilterModel;
+
+use crate::APP_STATE;
+use crate::component::loading_box::LoadingBoxComponent;
+use crate::component::paginator::{PaginatorComponent, PaginatorComponentMode};
+use crate::component::reloading_box::ReloadingBoxComponent;
+use crate::handler::audit_handler::AuditHandler;
+use crate::page::not_found::NotFoundPage;
+use crate::service::validator_service::ValidatorService;
+
+#[component]
+pub fn SignInsPage() -> Element {
+    let app_state = APP_STATE.peek();
+    let auth_state = app_state.auth.read_unchecked();
+    let i18 = use_i18();
+
+    if !auth_state.is_permission("audit::read") {
+        return rsx! { NotFoundPage {} };
+    }
+
+    let mut breadcrumbs = app_state.breadcrumbs.signal();
+    use_effect(move || {
+        breadcrumbs.set(vec![
+            RecordModel { title: translate!(i18, "messages.administrator"), slug: "/administrator".to_string() },
+            RecordModel { title: translate!(i18, "messages.sign_ins"), slug: "/administrator/sign_ins".to_string() },
+        ]);
+    });
+
+    let mut page = use_signal(|| 1usize);
+    let mut filter = use_signal(SignInLogFilterModel::default);
+
+    let sign_ins_future =
+        use_resource(move || async move { APP_STATE.peek().api.get_sign_in_list(page(), &filter()).await });
+
+    let apply_filter = move |event: Event<FormData>| {
+        let field = |name: &str| event.get_string_option(name).filter(|value| !value.trim().is_empty());
+
+        filter.set(SignInLogFilterModel {
+            login: field("login").map(|value| value.trim().to_string()),
+            success: field("success").map(|value| value.eq("true")),
+            date_from: field("date_from"),
+            date_to: field("date_to"),
+        });
+        page.set(1);
+    };
+
+    rsx! {
+        section { class: "w-full flex-grow p-3",
+            form { class: "flex flex-wrap gap-2 items-end",
+                autocomplete: "off",
+                onsubmit: apply_filter,
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.login") } }
+                    }
+                    input { r#type: "text", name: "login",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().login.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.sign_in_result") } }
+                    }
+                    select { class: "select select-sm select-bordered",
+                        name: "success",
+                        option { value: "", selected: filter().success.is_none(), { translate!(i18, "messages.any") } }
+                        option { value: "true", selected: filter().success == Some(true), { translate!(i18, "messages.sign_in_success") } }
+                        option { value: "false", selected: filter().success == Some(false), { translate!(i18, "messages.sign_in_failure") } }
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.date_from") } }
+                    }
+                    input { r#type: "date", name: "date_from",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().date_from.unwrap_or_default(),
+                    }
+                }
+                label { class: "form-control",
+                    div { class: "label",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.date_to") } }
+                    }
+                    input { r#type: "date", name: "date_to",
+                        class: "input input-sm input-bordered",
+                        initial_value: filter().date_to.unwrap_or_default(),
+                    }
+                }
+                button { class: "btn btn-sm btn-primary",
+                    r#type: "submit",
+                    Icon {
+                        width: 18,
+                        height: 18,
+                        fill: "currentColor",
+                        icon: dioxus_free_icons::icons::md_content_icons::MdFilterList
+                    }
+                    { translate!(i18, "messages.filter") }
+                }
+            }
+            match &*sign_ins_future.read() {
+                Some(Ok(response)) => rsx! {
+                    table { class: "table table-sm w-full",
+                        thead {
+                            tr {
+                                th { { translate!(i18, "messages.created_at") } }
+                                th { { translate!(i18, "messages.login") } }
+                                th { { translate!(i18, "messages.sign_in_result") } }
+                                th { { translate!(i18, "messages.ip") } }
+                                th { { translate!(i18, "messages.user_agent") } }
+                            }
+                        }
+                        tbody {
+                            for item in response.data.iter() {
+                                tr { class: "hover:bg-base-200",
+                                    td { class: "whitespace-nowrap", { item.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
+                                    td { { item.login.clone() } }
+                                    td { class: if item.success { "text-success" } else { "text-error" },
+                                        {
+                                            match item.reason.clone() {
+                                                Some(reason) => translate!(i18, reason.as_str()),
+                                                None => translate!(i18, "messages.sign_in_success"),
+                                            }
+                                        }
+                                    }
+                                    td { { item.ip.clone() } }
+                                    td { class: "text-xs break-all", { item.user_agent.clone() } }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex w-full py-2 justify-center",
+                        PaginatorComponent { mode: PaginatorComponentMode::Full, page, pagination: response.pagination.clone().unwrap_or_default() }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: crate::DIV_CENTER,
+                        ReloadingBoxComponent { message: e.message(), resource: sign_ins_future }
+                    }
+                },
+                None => rsx! {
+                    div { class: crate::DIV_CENTER,
+                        LoadingBoxComponent {}
+                    }
+                },
+            }
+        }
+    }
+}
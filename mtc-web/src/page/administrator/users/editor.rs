@@ -1,13 +1,18 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use chrono::Local;
+use chrono::{Local, Utc};
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
 use dioxus_std::translate;
+use serde_json::{Map, Value};
 
 use mtc_model::auth_model::AuthModelTrait;
+use mtc_model::certificate_model::{CertificateCreateModel, CertificateKindModel, CertificateModel};
+use mtc_model::field_model::{FieldModel, FieldTypeModel};
+use mtc_model::identity_model::IdentityModel;
 use mtc_model::record_model::RecordModel;
+use mtc_model::sign_in_log_model::SignInLogModel;
 use mtc_model::user_model::{UserCreateModel, UserModel, UserUpdateModel};
 
 use crate::APP_STATE;
@@ -15,9 +20,11 @@ use crate::component::list_switcher::ListSwitcherComponent;
 use crate::component::loading_box::LoadingBoxComponent;
 use crate::handler::group_handler::GroupHandler;
 use crate::handler::role_handler::RoleHandler;
+use crate::handler::person_handler::PersonHandler;
 use crate::handler::user_handler::UserHandler;
 use crate::model::modal_model::ModalModel;
 use crate::page::not_found::NotFoundPage;
+use crate::router::Route::HomePage;
 use crate::service::user_service::UserService;
 use crate::service::validator_service::ValidatorService;
 
@@ -39,11 +46,19 @@ pub fn UserEditorPage(user_prop: String) -> Element {
     let mut user = use_signal(UserModel::default);
     let is_new_user = use_memo(move || user_login().eq("new"));
     let users_details = app_state.users.signal();
+    let mut profile = use_signal(Vec::<FieldModel>::new);
     let mut user_roles = use_signal(BTreeSet::<String>::new);
     let mut all_roles = use_signal(BTreeSet::<String>::new);
     let mut user_groups = use_signal(BTreeSet::<String>::new);
     let mut all_groups = use_signal(BTreeSet::<String>::new);
     let mut roles_title = use_signal(BTreeMap::<String, String>::new);
+    let mut inherited_roles = use_signal(BTreeSet::<String>::new);
+    let mut effective_permissions = use_signal(Vec::<String>::new);
+    let mut sign_ins = use_signal(Vec::<SignInLogModel>::new);
+    let mut certificates = use_signal(Vec::<CertificateModel>::new);
+    let mut certificate_kind = use_signal(CertificateKindModel::default);
+    let mut certificate_value = use_signal(String::new);
+    let mut identities = use_signal(Vec::<IdentityModel>::new);
     let mut groups_title = use_signal(BTreeMap::<String, String>::new);
 
     let mut breadcrumbs = app_state.breadcrumbs.signal();
@@ -109,6 +124,10 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                     .collect::<BTreeSet<String>>();
             }
 
+            if let Ok(value) = APP_STATE.peek().api.get_person_profile().await {
+                profile.set(value);
+            }
+
             if !is_new_user() {
                 match APP_STATE.peek().api.get_user(&user_login()).await {
                     Ok(value) => {
@@ -142,6 +161,26 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                         .cloned()
                         .collect::<BTreeSet<String>>();
                 }
+
+                if let Ok(roles_model) = APP_STATE.peek().api.get_user_inherited_roles(&user_login()).await {
+                    inherited_roles.set(roles_model.list.into_iter().collect());
+                }
+
+                if let Ok(permissions_model) = APP_STATE.peek().api.get_user_effective_permissions(&user_login()).await {
+                    effective_permissions.set(permissions_model.list);
+                }
+
+                if let Ok(sign_ins_model) = APP_STATE.peek().api.get_user_sign_ins(&user_login()).await {
+                    sign_ins.set(sign_ins_model);
+                }
+
+                if let Ok(certificates_model) = APP_STATE.peek().api.get_user_certificates(&user_login()).await {
+                    certificates.set(certificates_model);
+                }
+
+                if let Ok(identities_model) = APP_STATE.peek().api.get_user_identities(&user_login()).await {
+                    identities.set(identities_model);
+                }
             }
 
             groups_user.iter().for_each(|group| {
@@ -178,6 +217,20 @@ pub fn UserEditorPage(user_prop: String) -> Element {
             false => Some(user_groups().iter().cloned().collect::<Vec<String>>()),
         };
 
+        // without the profile schema the person details are left as they are
+        let fields = match profile().is_empty() {
+            true => None,
+            false => Some(Value::Object(
+                profile()
+                    .iter()
+                    .map(|field| {
+                        let value = event.get_string(&["profile-", &field.slug].concat());
+                        (field.slug.clone(), Value::String(value))
+                    })
+                    .collect::<Map<String, Value>>(),
+            )),
+        };
+
         if is_new_user() & !event.is_login_valid() {
             app_state
                 .modal
@@ -197,9 +250,11 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                             &UserUpdateModel {
                                 blocked: event.get_string_option("blocked").is_some(),
                                 password: event.get_string_option("password"),
+                                valid_from: event.get_datetime_option("valid_from").map(Into::into),
+                                valid_until: event.get_datetime_option("valid_until").map(Into::into),
                                 roles: roles.clone(),
                                 groups: groups.clone(),
-                                fields: None,
+                                fields: fields.clone(),
                             },
                         )
                         .await
@@ -212,6 +267,8 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                             &UserCreateModel {
                                 blocked: event.get_string_option("blocked").is_some(),
                                 password: event.get_string("password"),
+                                valid_from: event.get_datetime_option("valid_from").map(Into::into),
+                                valid_until: event.get_datetime_option("valid_until").map(Into::into),
                                 roles: roles.clone(),
                                 groups: groups.clone(),
                             },
@@ -230,9 +287,16 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                         },
                         password: "".to_string(),
                         blocked: form_blocked(),
+                        totp_enabled: user().totp_enabled,
                         access_level: user().access_level,
                         access_count: user().access_count,
                         last_access: user().last_access,
+                        failed_count: user().failed_count,
+                        locked_until: user().locked_until,
+                        must_change_password: user().must_change_password,
+                        password_changed_at: user().password_changed_at,
+                        valid_from: user().valid_from,
+                        valid_until: user().valid_until,
                         fields: user().fields,
                         created_at: user().created_at,
                         updated_at: user().updated_at,
@@ -261,6 +325,133 @@ pub fn UserEditorPage(user_prop: String) -> Element {
         });
     };
 
+    let user_unlock = move |_| {
+        let app_state = APP_STATE.read();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state.api.unlock_user(&user_login()).await {
+                Ok(_) => {
+                    user.write().failed_count = 0;
+                    user.write().locked_until = None;
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let certificate_add = move |_| {
+        let app_state = APP_STATE.read();
+
+        if certificate_value().trim().is_empty() {
+            app_state
+                .modal
+                .signal()
+                .set(ModalModel::Error(translate!(i18, "errors.fields")));
+            return;
+        }
+
+        spawn(async move {
+            match app_state
+                .api
+                .add_user_certificate(
+                    &user_login(),
+                    &CertificateCreateModel {
+                        kind: certificate_kind(),
+                        value: certificate_value(),
+                    },
+                )
+                .await
+            {
+                Ok(certificate_model) => {
+                    certificates.write().insert(0, certificate_model);
+                    certificate_value.set(String::new());
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    let certificate_delete = move |id: String| {
+        spawn(async move {
+            let app_state = APP_STATE.read();
+
+            match app_state.api.delete_user_certificate(&user_login(), &id).await {
+                Ok(_) => certificates.write().retain(|item| item.id.ne(&id)),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    let identity_delete = move |id: String| {
+        spawn(async move {
+            let app_state = APP_STATE.read();
+
+            match app_state.api.delete_user_identity(&user_login(), &id).await {
+                Ok(_) => identities.write().retain(|item| item.id.ne(&id)),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
+    let is_locked = use_memo(move || {
+        user()
+            .locked_until
+            .is_some_and(|value| value.0 > Utc::now())
+    });
+
+    let user_totp_reset = move |_| {
+        let app_state = APP_STATE.read();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state.api.reset_user_totp(&user_login()).await {
+                Ok(_) => {
+                    user.write().totp_enabled = false;
+                    app_state.modal.signal().set(ModalModel::Success(translate!(
+                        i18,
+                        "messages.totp_reset_success"
+                    )))
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let user_sign_out = move |_| {
+        let app_state = APP_STATE.read();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state.api.sign_out_user(&user_login()).await {
+                Ok(_) => app_state.modal.signal().set(ModalModel::Success(translate!(
+                    i18,
+                    "messages.sign_out_everywhere_success"
+                ))),
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let user_impersonate = move |_| {
+        let app_state = APP_STATE.read();
+        is_busy.set(true);
+
+        spawn(async move {
+            match app_state.api.impersonate_user(&user_login()).await {
+                Ok(auth_model) => {
+                    app_state.auth.signal().set(auth_model);
+                    navigator().push(HomePage {});
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+            is_busy.set(false);
+        });
+    };
+
     if is_busy() {
         return rsx! {
             div { class: crate::DIV_CENTER,
@@ -291,15 +482,39 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                         initial_value: user().login
                     }
                 }
-                if users_details().contains_key(&user().login) {
-                    label { class: "w-full form-control",
-                        div { class: "label",
-                            span { class: "label-text text-primary", { translate!(i18, "messages.rank") } " / " { translate!(i18, "messages.name") }}
-                        }
-                        input { r#type: "text", name: "name",
-                            value: [&users_details().get_user_rank(&user().login), " ", &users_details().get_user_name(&user().login)].concat(),
-                            class: "input input-bordered",
-                            disabled: true
+                if !is_new_user() {
+                    for field in profile() {
+                        {
+                            let name = ["profile-", &field.slug].concat();
+                            let value = user()
+                                .fields
+                                .as_ref()
+                                .and_then(|fields| fields.get(&field.slug))
+                                .and_then(|value| value.as_str())
+                                .unwrap_or_default()
+                                .to_string();
+
+                            rsx! {
+                                label { class: "w-full form-control",
+                                    div { class: "label",
+                                        span { class: "label-text text-primary", { field.title.clone() } }
+                                    }
+                                    match field.field_type {
+                                        FieldTypeModel::Text | FieldTypeModel::Html => rsx! {
+                                            textarea { class: "textarea textarea-bordered",
+                                                name: name,
+                                                initial_value: value
+                                            }
+                                        },
+                                        _ => rsx! {
+                                            input { r#type: "text", name: name,
+                                                class: "input input-bordered",
+                                                initial_value: value
+                                            }
+                                        },
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -315,8 +530,178 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                         maxlength: 15,
                     }
                 }
+                div { class: "flex w-full gap-3",
+                    label { class: "w-full form-control",
+                        div { class: "label",
+                            span { class: "label-text text-primary",
+                                { translate!(i18, "messages.valid_from") }
+                            }
+                        }
+                        input { r#type: "datetime-local", name: "valid_from",
+                            class: "input input-bordered",
+                            initial_value: user().valid_from.map(|value| value.with_timezone(&Local).format("%Y-%m-%dT%H:%M").to_string()).unwrap_or_default()
+                        }
+                    }
+                    label { class: "w-full form-control",
+                        div { class: "label",
+                            span { class: "label-text text-primary",
+                                { translate!(i18, "messages.valid_until") }
+                            }
+                        }
+                        input { r#type: "datetime-local", name: "valid_until",
+                            class: "input input-bordered",
+                            initial_value: user().valid_until.map(|value| value.with_timezone(&Local).format("%Y-%m-%dT%H:%M").to_string()).unwrap_or_default()
+                        }
+                    }
+                }
                 ListSwitcherComponent { title: translate!(i18, "messages.roles"), items: user_roles, all: all_roles, items_title: roles_title }
+                if !inherited_roles().is_empty() {
+                    div { class: "flex w-full flex-wrap items-center gap-2",
+                        span { class: "label-text text-primary", { translate!(i18, "messages.roles_inherited") } ":" }
+                        for role in inherited_roles() {
+                            span { class: if user_roles().contains(&role) { "badge badge-outline" } else { "badge badge-ghost" },
+                                { roles_title().get(&role).cloned().unwrap_or(role.clone()) }
+                            }
+                        }
+                    }
+                }
+                if !effective_permissions().is_empty() {
+                    details { class: "collapse collapse-arrow w-full border input-bordered",
+                        summary { class: "collapse-title label-text text-primary",
+                            { translate!(i18, "messages.permissions_effective") } " (" { effective_permissions().len().to_string() } ")"
+                        }
+                        div { class: "collapse-content flex flex-wrap gap-2",
+                            for permission in effective_permissions() {
+                                span { class: "badge badge-ghost", { permission } }
+                            }
+                        }
+                    }
+                }
                 ListSwitcherComponent { title: translate!(i18, "messages.groups"), items: user_groups, all: all_groups, items_title: groups_title }
+                if !is_new_user() {
+                    details { class: "collapse collapse-arrow w-full border input-bordered",
+                        summary { class: "collapse-title label-text text-primary",
+                            { translate!(i18, "messages.certificates") } " (" { certificates().len().to_string() } ")"
+                        }
+                        div { class: "collapse-content flex flex-col gap-2",
+                            span { class: "label-text-alt", { translate!(i18, "messages.certificates_hint") } }
+                            for item in certificates() {
+                                div { class: "flex flex-row items-center gap-3 rounded border p-2 input-bordered",
+                                    span { class: "badge badge-ghost",
+                                        {
+                                            match item.kind {
+                                                CertificateKindModel::Subject => translate!(i18, "messages.certificate_subject"),
+                                                CertificateKindModel::Serial => translate!(i18, "messages.certificate_serial"),
+                                            }
+                                        }
+                                    }
+                                    span { class: "grow break-all label-text", { item.value.clone() } }
+                                    if auth_state.is_permission("user::write") {
+                                        button { class: "btn btn-xs btn-ghost text-error",
+                                            r#type: "button",
+                                            onclick: move |_| certificate_delete(item.id.clone()),
+                                            Icon {
+                                                width: 16,
+                                                height: 16,
+                                                fill: "currentColor",
+                                                icon: dioxus_free_icons::icons::fa_regular_icons::FaTrashCan
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if auth_state.is_permission("user::write") {
+                                div { class: "flex flex-row items-center gap-2",
+                                    select { class: "select select-sm select-bordered",
+                                        onchange: move |event| certificate_kind.set(
+                                            match event.value().as_str() {
+                                                "serial" => CertificateKindModel::Serial,
+                                                _ => CertificateKindModel::Subject,
+                                            }
+                                        ),
+                                        option { value: "subject", selected: certificate_kind() == CertificateKindModel::Subject, { translate!(i18, "messages.certificate_subject") } }
+                                        option { value: "serial", selected: certificate_kind() == CertificateKindModel::Serial, { translate!(i18, "messages.certificate_serial") } }
+                                    }
+                                    input { class: "input input-sm input-bordered grow",
+                                        r#type: "text",
+                                        maxlength: 1024,
+                                        value: certificate_value(),
+                                        oninput: move |event| certificate_value.set(event.value())
+                                    }
+                                    button { class: "btn btn-sm btn-outline",
+                                        r#type: "button",
+                                        onclick: certificate_add,
+                                        Icon {
+                                            width: 18,
+                                            height: 18,
+                                            fill: "currentColor",
+                                            icon: dioxus_free_icons::icons::md_content_icons::MdAdd
+                                        }
+                                        { translate!(i18, "messages.certificate_add") }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if !identities().is_empty() {
+                    details { class: "collapse collapse-arrow w-full border input-bordered",
+                        summary { class: "collapse-title label-text text-primary",
+                            { translate!(i18, "messages.identities") } " (" { identities().len().to_string() } ")"
+                        }
+                        div { class: "collapse-content flex flex-col gap-2",
+                            for item in identities() {
+                                div { class: "flex flex-row items-center gap-3 rounded border p-2 input-bordered",
+                                    div { class: "flex grow flex-col overflow-hidden",
+                                        span { class: "break-all label-text", { item.subject.clone() } }
+                                        span { class: "break-all label-text-alt", { item.issuer.clone() } }
+                                    }
+                                    if auth_state.is_permission("user::write") {
+                                        button { class: "btn btn-xs btn-ghost text-error",
+                                            r#type: "button",
+                                            title: translate!(i18, "messages.identity_unlink"),
+                                            onclick: move |_| identity_delete(item.id.clone()),
+                                            Icon {
+                                                width: 16,
+                                                height: 16,
+                                                fill: "currentColor",
+                                                icon: dioxus_free_icons::icons::md_content_icons::MdLinkOff
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if !sign_ins().is_empty() {
+                    details { class: "collapse collapse-arrow w-full border input-bordered",
+                        summary { class: "collapse-title label-text text-primary",
+                            { translate!(i18, "messages.sign_ins") }
+                        }
+                        div { class: "collapse-content",
+                            table { class: "table table-sm w-full",
+                                tbody {
+                                    for item in sign_ins() {
+                                        tr {
+                                            td { class: "whitespace-nowrap", { item.created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
+                                            td { class: if item.success { "text-success" } else { "text-error" },
+                                                {
+                                                    match item.reason.clone() {
+                                                        Some(reason) => translate!(i18, reason.as_str()),
+                                                        None => translate!(i18, "messages.sign_in_success"),
+                                                    }
+                                                }
+                                            }
+                                            td { { item.ip.clone() } }
+                                            td { class: "break-all text-xs", { item.user_agent.clone() } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
 
             aside { class: "flex flex-col gap-3 pt-5 min-w-36",
@@ -331,11 +716,24 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                 }
                 div { class: "flex flex-col gap-1 rounded border p-2 input-bordered label-text",
                     span { class: "italic label-text text-primary", { translate!(i18, "messages.created_at") } ":" }
-                    span { { user().created_by } }
+                    span { { users_details().get_user_title(&user().created_by) } }
                     span { class: "label-text-alt", { user().created_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
                     span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.updated_at") } ":" }
-                    span { { user().updated_by } }
+                    span { { users_details().get_user_title(&user().updated_by) } }
                     span { class: "label-text-alt", { user().updated_at.with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() } }
+                    if user().failed_count > 0 {
+                        span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.failed_attempts") } ":" }
+                        span { class: "text-warning", { user().failed_count.to_string() } }
+                    }
+                    if user().must_change_password {
+                        span { class: "mt-1 italic label-text text-warning", { translate!(i18, "messages.must_change_password") } }
+                    }
+                    if is_locked() {
+                        span { class: "mt-1 italic label-text text-primary", { translate!(i18, "messages.locked_until") } ":" }
+                        span { class: "label-text-alt text-error",
+                            { user().locked_until.unwrap_or_default().with_timezone(&Local).format("%H:%M %d/%m/%Y").to_string() }
+                        }
+                    }
                 }
 
                 label { class:
@@ -383,6 +781,54 @@ pub fn UserEditorPage(user_prop: String) -> Element {
                         { translate!(i18, "messages.save") }
                     }
                 }
+                if auth_state.is_permission("user::write") && (is_locked() || user().failed_count > 0) {
+                    button { class: "btn btn-outline btn-warning",
+                        onclick: user_unlock,
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            fill: "currentColor",
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLockOpen
+                        }
+                        { translate!(i18, "messages.unlock") }
+                    }
+                }
+                if auth_state.is_permission("user::write") && user().totp_enabled {
+                    button { class: "btn btn-outline btn-warning",
+                        onclick: user_totp_reset,
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            fill: "currentColor",
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLockOpen
+                        }
+                        { translate!(i18, "messages.totp_reset") }
+                    }
+                }
+                if auth_state.is_permission("user::write") && !is_new_user() {
+                    button { class: "btn btn-outline btn-warning",
+                        onclick: user_sign_out,
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            fill: "currentColor",
+                            icon: dioxus_free_icons::icons::md_action_icons::MdLogout
+                        }
+                        { translate!(i18, "messages.sign_out_everywhere") }
+                    }
+                }
+                if auth_state.is_permission("user::impersonate") && !is_new_user() {
+                    button { class: "btn btn-outline",
+                        onclick: user_impersonate,
+                        Icon {
+                            width: 22,
+                            height: 22,
+                            fill: "currentColor",
+                            icon: dioxus_free_icons::icons::md_action_icons::MdVisibility
+                        }
+                        { translate!(i18, "messages.impersonate") }
+                    }
+                }
                 if auth_state.is_permission("user::delete") && !is_new_user() {
                     div { class: "divider" }
                     button { class: "btn btn-ghost text-error",
@@ -1,3 +1,4 @@
+use chrono::Utc;
 use dioxus::prelude::*;
 use dioxus_free_icons::Icon;
 use dioxus_std::i18n::use_i18;
@@ -6,20 +7,24 @@ use dioxus_std::translate;
 use mtc_model::auth_model::AuthModelTrait;
 use mtc_model::pagination_model::PaginationModel;
 use mtc_model::record_model::RecordModel;
+use mtc_model::user_model::{UserFilterModel, USER_SORT};
 
 use crate::APP_STATE;
 use crate::component::loading_box::LoadingBoxComponent;
 use crate::component::paginator::{PaginatorComponent, PaginatorComponentMode};
 use crate::component::reloading_box::ReloadingBoxComponent;
+use crate::handler::group_handler::GroupHandler;
+use crate::handler::role_handler::RoleHandler;
 use crate::handler::user_handler::UserHandler;
 use crate::page::not_found::NotFoundPage;
-use crate::router::Route::UserEditorPage;
+use crate::router::Route::{UserEditorPage, UsersPage};
 use crate::service::user_service::UserService;
+use crate::service::validator_service::ValidatorService;
 
 pub mod editor;
 
 #[component]
-pub fn UsersPage() -> Element {
+pub fn UsersPage(query: String) -> Element {
     let app_state = APP_STATE.peek();
     let auth_state = app_state.auth.read_unchecked();
     let i18 = use_i18();
@@ -37,14 +42,167 @@ pub fn UsersPage() -> Element {
     });
 
     let pagination = use_signal(|| PaginationModel::new(0, 10));
-    let page = use_signal(|| 1usize);
+    let mut page = use_signal(|| 1usize);
 
-    let users_future =
-        use_resource(move || async move { APP_STATE.peek().api.get_user_list(page()).await });
+    // the filter lives in the address, so it survives reloads and can be shared as a link
+    let filter = use_memo(use_reactive((&query,), |(query,)| {
+        serde_urlencoded::from_str::<UserFilterModel>(&query).unwrap_or_default()
+    }));
+
+    let users_future = use_resource(move || async move {
+        APP_STATE.peek().api.get_user_list(page(), &filter()).await
+    });
+
+    let roles_future = use_resource(move || async move { APP_STATE.peek().api.get_role_all().await });
+    let groups_future = use_resource(move || async move { APP_STATE.peek().api.get_group_all().await });
+
+    let apply_filter = move |event: Event<FormData>| {
+        let field = |name: &str| event.get_string_option(name).filter(|value| !value.trim().is_empty());
+
+        page.set(1);
+        navigator().push(UsersPage {
+            query: serde_urlencoded::to_string(UserFilterModel {
+                login: field("login").map(|value| value.trim().to_string()),
+                blocked: field("blocked").map(|value| value.eq("true")),
+                role: field("role"),
+                group: field("group"),
+                last_access_from: field("last_access_from"),
+                last_access_to: field("last_access_to"),
+                access_count_min: event.get_int_option("access_count_min"),
+                access_count_max: event.get_int_option("access_count_max"),
+                sort: field("sort").filter(|value| value.ne(USER_SORT[0])),
+            })
+            .unwrap_or_default(),
+        });
+    };
+
+    let clear_filter = move |_| {
+        page.set(1);
+        navigator().push(UsersPage { query: String::new() });
+    };
 
     let users_details = app_state.users.signal();
 
     rsx! {
+        form { class: "flex flex-wrap gap-2 items-end p-3",
+            autocomplete: "off",
+            onsubmit: apply_filter,
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.login") } }
+                }
+                input { r#type: "text", name: "login",
+                    class: "input input-sm input-bordered",
+                    value: filter().login.unwrap_or_default(),
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.user_state") } }
+                }
+                select { class: "select select-sm select-bordered",
+                    name: "blocked",
+                    option { value: "", selected: filter().blocked.is_none(), { translate!(i18, "messages.any") } }
+                    option { value: "false", selected: filter().blocked == Some(false), { translate!(i18, "messages.access_active") } }
+                    option { value: "true", selected: filter().blocked == Some(true), { translate!(i18, "messages.blocked") } }
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.role") } }
+                }
+                select { class: "select select-sm select-bordered",
+                    name: "role",
+                    option { value: "", selected: filter().role.is_none(), { translate!(i18, "messages.any") } }
+                    if let Some(Ok(roles)) = &*roles_future.read() {
+                        for item in roles.list.iter() {
+                            option { value: item.slug.clone(), selected: filter().role.as_ref() == Some(&item.slug), { item.title.clone() } }
+                        }
+                    }
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.group") } }
+                }
+                select { class: "select select-sm select-bordered",
+                    name: "group",
+                    option { value: "", selected: filter().group.is_none(), { translate!(i18, "messages.any") } }
+                    if let Some(Ok(groups)) = &*groups_future.read() {
+                        for item in groups.list.iter() {
+                            option { value: item.slug.clone(), selected: filter().group.as_ref() == Some(&item.slug), { item.title.clone() } }
+                        }
+                    }
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.last_access_from") } }
+                }
+                input { r#type: "date", name: "last_access_from",
+                    class: "input input-sm input-bordered",
+                    value: filter().last_access_from.unwrap_or_default(),
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.last_access_to") } }
+                }
+                input { r#type: "date", name: "last_access_to",
+                    class: "input input-sm input-bordered",
+                    value: filter().last_access_to.unwrap_or_default(),
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.access_count_min") } }
+                }
+                input { r#type: "number", name: "access_count_min",
+                    class: "input input-sm input-bordered w-24",
+                    min: 0,
+                    value: filter().access_count_min.map(|value| value.to_string()).unwrap_or_default(),
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.access_count_max") } }
+                }
+                input { r#type: "number", name: "access_count_max",
+                    class: "input input-sm input-bordered w-24",
+                    min: 0,
+                    value: filter().access_count_max.map(|value| value.to_string()).unwrap_or_default(),
+                }
+            }
+            label { class: "form-control",
+                div { class: "label",
+                    span { class: "label-text text-primary", { translate!(i18, "messages.sort") } }
+                }
+                select { class: "select select-sm select-bordered",
+                    name: "sort",
+                    for key in USER_SORT {
+                        option { value: key,
+                            selected: filter().sort.as_deref().unwrap_or(USER_SORT[0]) == key,
+                            { translate!(i18, ["messages.sort_", key].concat().as_str()) }
+                        }
+                    }
+                }
+            }
+            button { class: "btn btn-sm btn-primary",
+                r#type: "submit",
+                Icon {
+                    width: 18,
+                    height: 18,
+                    fill: "currentColor",
+                    icon: dioxus_free_icons::icons::md_content_icons::MdFilterList
+                }
+                { translate!(i18, "messages.filter") }
+            }
+            button { class: "btn btn-sm btn-ghost",
+                r#type: "button",
+                onclick: clear_filter,
+                { translate!(i18, "messages.clear") }
+            }
+        }
         match &*users_future.read() {
             Some(Ok(response)) => rsx! {
                 section { class: "w-full flex-grow p-3",
@@ -74,6 +232,13 @@ pub fn UsersPage() -> Element {
                                                         fill: "currentColor",
                                                         icon: dioxus_free_icons::icons::md_content_icons::MdBlock
                                                     }
+                                                } else if item.locked_until.as_ref().is_some_and(|value| value.0 > Utc::now()) {
+                                                    Icon {
+                                                        width: 16,
+                                                        height: 16,
+                                                        fill: "currentColor",
+                                                        icon: dioxus_free_icons::icons::md_action_icons::MdLock
+                                                    }
                                                 }
                                             }
                                             td { { item.login.clone() } }
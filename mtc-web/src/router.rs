@@ -14,8 +14,11 @@ use crate::component::main_menu_item::MainMenuItem;
 use crate::component::modal_box::ModalBoxComponent;
 use crate::element::footer::Footer;
 use crate::element::header::Header;
+use crate::element::user_dashboard::UserDashboard;
 use crate::handler::auth_handler::AuthHandler;
 use crate::handler::schema_handler::SchemaHandler;
+use crate::model::modal_model::ModalModel;
+use crate::page::administrator::audit::AuditPage;
 use crate::page::administrator::content::ContentPage;
 use crate::page::administrator::dashboard::AdministratorDashboardPage;
 use crate::page::administrator::editor::EditorPage;
@@ -26,12 +29,14 @@ use crate::page::administrator::roles::editor::RoleEditorPage;
 use crate::page::administrator::roles::RolesPage;
 use crate::page::administrator::schemas::editor::SchemaEditorPage;
 use crate::page::administrator::schemas::SchemasPage;
+use crate::page::administrator::sign_ins::SignInsPage;
 use crate::page::administrator::permissions::PermissionsPage;
 use crate::page::administrator::users::editor::UserEditorPage;
 use crate::page::administrator::users::UsersPage;
 use crate::page::dashboard::DashboardPage;
 use crate::page::home::HomePage;
 use crate::page::not_found::NotFoundPage;
+use crate::service::user_service::PersonService;
 
 #[derive(Routable, Clone, Debug, PartialEq)]
 #[rustfmt::skip]
@@ -56,14 +61,18 @@ pub enum Route {
     RolesPage {},
     #[route("/administrator/roles/:role_prop")]
     RoleEditorPage { role_prop: String },
-    #[route("/administrator/users")]
-    UsersPage {},
+    #[route("/administrator/users?:query")]
+    UsersPage { query: String },
     #[route("/administrator/users/:user_prop")]
     UserEditorPage { user_prop: String },
     #[route("/administrator/schemas")]
     SchemasPage {},
     #[route("/administrator/permissions")]
     PermissionsPage {},
+    #[route("/administrator/audit")]
+    AuditPage {},
+    #[route("/administrator/sign_ins")]
+    SignInsPage {},
     #[route("/administrator/schemas/:schema_prop")]
     SchemaEditorPage { schema_prop: String },
     #[route("/content/:schema_prop")]
@@ -99,6 +108,10 @@ fn RootLayout() -> Element {
                 }
             });
         }
+
+        if auth_state.is_permission("user::read") {
+            app_state.service.get_persons();
+        }
     });
 
     let sign_out = |_| {
@@ -112,7 +125,34 @@ fn RootLayout() -> Element {
         });
     };
 
+    let impersonation_end = |_| {
+        spawn(async move {
+            let app_state = APP_STATE.peek();
+
+            match app_state.api.end_impersonation().await {
+                Ok(auth_model) => {
+                    app_state.auth.signal().set(auth_model);
+                    navigator().push(Route::UsersPage { query: String::new() });
+                }
+                Err(e) => app_state.modal.signal().set(ModalModel::Error(e.message())),
+            }
+        });
+    };
+
     rsx! {
+        if let Some(impersonator) = auth_state.impersonator.clone() {
+            div { class: "sticky top-0 z-[50] flex w-full flex-wrap items-center justify-center gap-3 bg-warning px-3 py-1 text-warning-content",
+                span {
+                    { translate!(i18, "messages.impersonation") } ": "
+                    span { class: "font-semibold", { auth_state.id.clone() } }
+                    " (" { impersonator } ")"
+                }
+                button { class: "btn btn-xs btn-neutral",
+                    onclick: impersonation_end,
+                    { translate!(i18, "messages.impersonation_end") }
+                }
+            }
+        }
         div { class: "bg-base-100 drawer lg:drawer-open",
             input { class: "drawer-toggle",
                 id: "main-menu",
@@ -124,7 +164,13 @@ fn RootLayout() -> Element {
                 Header {}
                 div { class: "max-w-[100vw] px-6 pb-16 xl:pr-2",
                     div { class: "flex flex-col-reverse justify-between gap-6 xl:flex-row",
-                        Outlet::<Route> {}
+                        if auth_state.password_change {
+                            div { class: crate::DIV_CENTER,
+                                UserDashboard {}
+                            }
+                        } else {
+                            Outlet::<Route> {}
+                        }
                             /*
                             div { class: "prose prose-sm md:prose-base w-full max-w-4xl flex-grow pt-10",
                                 Outlet::<Route> {}
@@ -226,7 +272,9 @@ fn RootLayout() -> Element {
                                         MainMenuItem { route: Route::PermissionsPage {}, title: translate!(i18, "messages.permissions"), rights: Some("role::read".to_string()), toggle: main_menu_toggle }
                                         MainMenuItem { route: Route::GroupsPage {}, title: translate!(i18, "messages.groups"), rights: Some("group::read".to_string()), toggle: main_menu_toggle }
                                         MainMenuItem { route: Route::RolesPage {}, title: translate!(i18, "messages.roles"), rights: Some("role::read".to_string()), toggle: main_menu_toggle }
-                                        MainMenuItem { route: Route::UsersPage {}, title: translate!(i18, "messages.users"), rights: Some("user::read".to_string()), toggle: main_menu_toggle }
+                                        MainMenuItem { route: Route::UsersPage { query: String::new() }, title: translate!(i18, "messages.users"), rights: Some("user::read".to_string()), toggle: main_menu_toggle }
+                                        MainMenuItem { route: Route::AuditPage {}, title: translate!(i18, "messages.audit"), rights: Some("audit::read".to_string()), toggle: main_menu_toggle }
+                                        MainMenuItem { route: Route::SignInsPage {}, title: translate!(i18, "messages.sign_ins"), rights: Some("audit::read".to_string()), toggle: main_menu_toggle }
 
                                     }
                                 }
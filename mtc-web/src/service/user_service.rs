@@ -1,95 +1,90 @@
 use std::collections::BTreeMap;
 
-use serde_json::{Map, Value};
+use dioxus::prelude::*;
+use serde_json::Value;
+use tracing::error;
 
-use mtc_model::user_details_model::UserDetailsModel;
+use mtc_model::user_details_model::{PersonModel, UserDetailsModel};
+
+use crate::handler::person_handler::PersonHandler;
+use crate::service::AppService;
+use crate::APP_STATE;
 
 pub trait UserService {
-    fn import_str(&self, data: &str) -> Self;
-    fn import_json(&mut self, data: &str);
-    fn remove_user(&mut self, user: &str) -> Self;
-    fn get_users_string(&self) -> Value;
-    fn get_users_json(&self) -> Value;
+    fn from_persons(persons: &[PersonModel]) -> Self;
     fn get_user_rank(&self, login: &str) -> String;
     fn get_user_name(&self, login: &str) -> String;
+    fn get_user_title(&self, login: &str) -> String;
 }
 
 impl UserService for BTreeMap<String, UserDetailsModel> {
-    fn import_str(&self, data: &str) -> Self {
-        let mut user_details = self.clone();
-
-        let user_array = data
-            .split('\n')
-            .filter(|val| !val.is_empty())
-            //.map(|val| val.to_string())
-            .collect::<Vec<&str>>();
-
-        for item in user_array {
-            let user_fields = item
-                .split('\t')
-                //                .filter(|val| !val.trim().is_empty())
-                //                .map(|val| val.to_string())
-                .collect::<Vec<&str>>();
+    fn from_persons(persons: &[PersonModel]) -> Self {
+        let field = |fields: &Option<Value>, slug: &str| {
+            fields
+                .as_ref()
+                .and_then(|fields| fields.get(slug))
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
 
-            if user_fields.len().ge(&3) {
-                user_details.insert(
-                    user_fields[2].trim().to_uppercase(),
+        persons
+            .iter()
+            .map(|person| {
+                (
+                    person.login.to_uppercase(),
                     UserDetailsModel {
-                        rank: user_fields[0].trim().to_string(),
-                        name: user_fields[1].trim().to_string(),
+                        rank: field(&person.fields, "rank"),
+                        name: field(&person.fields, "name"),
                     },
-                );
-            }
-        }
-
-        user_details
+                )
+            })
+            .collect()
     }
 
-    fn import_json(&mut self, data: &str) {
-        let users =
-            serde_json::from_str(data).unwrap_or(BTreeMap::<String, UserDetailsModel>::new());
-        users.iter().for_each(|(login, details)| {
-            self.insert(login.clone(), details.clone());
-        })
+    fn get_user_rank(&self, login: &str) -> String {
+        self.get(&login.to_uppercase())
+            .map(|details| details.rank.clone())
+            .unwrap_or_default()
     }
 
-    fn remove_user(&mut self, user: &str) -> Self {
-        self.remove(user);
-        self.to_owned()
+    fn get_user_name(&self, login: &str) -> String {
+        self.get(&login.to_uppercase())
+            .map(|details| details.name.clone())
+            .unwrap_or_default()
     }
 
-    fn get_users_string(&self) -> Value {
-        std::iter::Map::collect::<String>(
-            self.iter()
-                //.map(|(login, user)| format!("{:1}\t{:2}\t{:3}\n", user.rank, user.name, login)))
-                .map(|(login, details)| {
-                    [&details.rank, "\t", &details.name, "\t", login, "\n"].concat()
-                }),
-        )
-        .into()
-    }
+    fn get_user_title(&self, login: &str) -> String {
+        let title = [self.get_user_rank(login), self.get_user_name(login)]
+            .iter()
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| value.trim())
+            .collect::<Vec<&str>>()
+            .join(" ");
 
-    fn get_users_json(&self) -> Value {
-        Value::Object(
-            self.iter()
-                .map(|(login, details)| (login.clone(), serde_json::to_value(details).unwrap()))
-                .collect::<Map<String, Value>>(),
-        )
-    }
-
-    fn get_user_rank(&self, login: &str) -> String {
-        let key = login.to_uppercase();
-        if !self.contains_key(&key) {
-            return String::new();
+        match title.is_empty() {
+            true => login.to_string(),
+            false => title,
         }
-        self.get_key_value(&key).unwrap().1.rank.clone()
     }
+}
 
-    fn get_user_name(&self, login: &str) -> String {
-        let key = login.to_uppercase();
-        if !self.contains_key(&key) {
-            return String::new();
-        }
-        self.get_key_value(&key).unwrap().1.name.clone()
+pub trait PersonService {
+    fn get_persons(&self);
+}
+
+impl PersonService for AppService {
+    fn get_persons(&self) {
+        spawn(async move {
+            let app_state = APP_STATE.peek();
+
+            match app_state.api.get_persons().await {
+                Ok(persons) => app_state
+                    .users
+                    .signal()
+                    .set(BTreeMap::<String, UserDetailsModel>::from_persons(&persons)),
+                Err(e) => error!("Get persons: {}", e.message()),
+            }
+        });
     }
 }
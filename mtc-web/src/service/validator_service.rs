@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use dioxus::prelude::*;
 
 pub trait ValidatorService {
@@ -8,6 +9,7 @@ pub trait ValidatorService {
     fn get_string(&self, field: &str) -> String;
     fn get_string_option(&self, field: &str) -> Option<String>;
     fn get_int_option(&self, field: &str) -> Option<i32>;
+    fn get_datetime_option(&self, field: &str) -> Option<DateTime<Utc>>;
 }
 
 impl ValidatorService for Event<FormData> {
@@ -59,4 +61,12 @@ impl ValidatorService for Event<FormData> {
             },
         }
     }
+
+    fn get_datetime_option(&self, field: &str) -> Option<DateTime<Utc>> {
+        // datetime-local inputs carry the local time without an offset
+        self.get_string_option(field)
+            .and_then(|value| NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M").ok())
+            .and_then(|value| value.and_local_timezone(Local).single())
+            .map(|value| value.with_timezone(&Utc))
+    }
 }